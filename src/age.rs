@@ -0,0 +1,159 @@
+//! Processes `DerivedAge.txt` to determine the Age property of code points:
+//! the version of Unicode in which each code point was assigned.
+//!
+//! `DerivedAge.txt` isn't embedded in this crate, so it must be read from a
+//! [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr44/#Character_Age> for details.
+
+use crate::code_point_table::CodePoint;
+use crate::enumerated_property::EnumeratedProperty;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+use crate::unicode_version::UnicodeVersion;
+use std::collections::BTreeMap;
+
+/// The name of the file processed by this module.
+pub(crate) const DERIVED_AGE_TXT: &str = "DerivedAge.txt";
+
+/// The Age value of unassigned code points.
+const UNASSIGNED: &str = "Unassigned";
+
+/// The Age of every assigned code point.
+pub struct AgeData {
+    /// The version in which each code point was assigned, or `None` if it's
+    /// unassigned.
+    age: EnumeratedProperty<Option<UnicodeVersion>>,
+
+    /// The code points assigned in each version, e.g. 1.1.0 or 13.0.0.
+    ages: BTreeMap<UnicodeVersion, CodePointSet>,
+}
+
+impl AgeData {
+    /// The version of Unicode in which `code` was assigned, or `None` if it's
+    /// unassigned.
+    pub fn age(&self, code: u32) -> Option<UnicodeVersion> {
+        self.age.get(code)
+    }
+
+    /// Return an iterator over every version that assigned code points, in
+    /// increasing order.
+    pub fn versions(&self) -> impl Iterator<Item = UnicodeVersion> + '_ {
+        self.ages.keys().copied()
+    }
+
+    /// Return the set of code points assigned in `version` itself, or `None`
+    /// if that version assigned none.
+    pub fn added_in(&self, version: UnicodeVersion) -> Option<&CodePointSet> {
+        self.ages.get(&version)
+    }
+
+    /// Return the set of code points assigned in `version` or any earlier
+    /// version, the code points matched by `\p{Age=<version>}`.
+    pub fn assigned_as_of(&self, version: UnicodeVersion) -> CodePointSet {
+        self.ages
+            .range(..=version)
+            .flat_map(|(_, set)| set.iter().copied())
+            .collect()
+    }
+
+    /// Return the set of every assigned code point, the code points matched by
+    /// `\p{Assigned}`.
+    pub fn assigned(&self) -> CodePointSet {
+        self.ages
+            .values()
+            .flat_map(|set| set.iter().copied())
+            .collect()
+    }
+}
+
+impl CodePoint {
+    /// The version of Unicode in which this code point was assigned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ages` doesn't list this code point, which happens only if
+    /// it's from a different Unicode version than `UnicodeData.txt`.
+    pub fn age(&self, ages: &AgeData) -> UnicodeVersion {
+        ages.age(self.code)
+            .expect("every code point in UnicodeData.txt is assigned")
+    }
+}
+
+/// Parse the Age of every assigned code point from the `DerivedAge.txt` in
+/// `source`.
+///
+/// Records consist of a code point range and a version written as
+/// `major.minor`, e.g. `0000..001F ; 1.1`.  The file's `@missing` line
+/// declares that unlisted code points are `Unassigned`.
+pub fn process_derived_age(source: &UcdSource) -> Result<AgeData, UcdError> {
+    parse_derived_age(UcdFile::read(source, DERIVED_AGE_TXT)?)
+}
+
+fn parse_derived_age(file: UcdFile) -> Result<AgeData, UcdError> {
+    let from_name = |name| match name {
+        UNASSIGNED => Some(None),
+        _ => UnicodeVersion::parse(name).map(Some),
+    };
+    let age = EnumeratedProperty::parse(file, "a Unicode version", from_name, None)?;
+
+    let mut ages = BTreeMap::<UnicodeVersion, CodePointSet>::new();
+    for (range, version) in age.ranges() {
+        if let Some(version) = version {
+            ages.entry(version).or_default().extend(range.iter());
+        }
+    }
+    Ok(AgeData { age, ages })
+}
+
+#[test]
+fn check_derived_age() {
+    let file = UcdFile::new(
+        DERIVED_AGE_TXT,
+        "# DerivedAge-16.0.0.txt\n\
+         # @missing: 0000..10FFFF; Unassigned\n\
+         0000..001F    ; 1.1 #  [32] <control-0000>..<control-001F>\n\
+         0020..007E    ; 1.1 #  [95] SPACE..TILDE\n\
+         20AC          ; 2.1 #       EURO SIGN\n\
+         1F600         ; 6.1 #       GRINNING FACE\n\
+         1F970         ; 11.0 #      SMILING FACE WITH SMILING EYES AND THREE HEARTS\n",
+    );
+
+    let version = |s| UnicodeVersion::parse(s).expect("version");
+    let ages = parse_derived_age(file).expect("DerivedAge.txt");
+    assert_eq!(ages.age('A' as u32), Some(version("1.1")));
+    assert_eq!(ages.age(0x20AC), Some(version("2.1.0")));
+    assert_eq!(ages.age(0x1F970), Some(version("11.0")));
+    assert_eq!(ages.age(0x007F), None);
+    assert_eq!(
+        ages.versions().collect::<Vec<_>>(),
+        [
+            version("1.1"),
+            version("2.1"),
+            version("6.1"),
+            version("11.0")
+        ]
+    );
+    assert_eq!(ages.added_in(version("2.1")).map(|set| set.len()), Some(1));
+    assert_eq!(ages.added_in(version("2.0")), None);
+    let as_of_6_1 = ages.assigned_as_of(version("6.1"));
+    assert!(as_of_6_1.contains(&0x1F600));
+    assert!(!as_of_6_1.contains(&0x1F970));
+    assert_eq!(as_of_6_1.len(), 32 + 95 + 2);
+    assert_eq!(ages.assigned().len(), 32 + 95 + 3);
+
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    let euro_sign = table.get(0x20AC).expect("U+20AC");
+    assert_eq!(euro_sign.age(&ages), version("2.1"));
+
+    let error = parse_derived_age(UcdFile::new(DERIVED_AGE_TXT, "0000 ; one point one\n"))
+        .err()
+        .expect("bad version");
+    assert_eq!(
+        error.to_string(),
+        "DerivedAge.txt:1: expected a Unicode version, found \"one point one\""
+    );
+}
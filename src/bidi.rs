@@ -0,0 +1,396 @@
+//! The Bidi_Class property of code points, and processing of the files that
+//! describe how the Unicode Bidirectional Algorithm treats them:
+//! `BidiMirroring.txt`, `BidiBrackets.txt`, and
+//! `extracted/DerivedBidiClass.txt`.
+//!
+//! None of these files is embedded in this crate, so they must be read from a
+//! [`UcdSource::Directory`].  The Bidi_Class of assigned code points is also
+//! available from `UnicodeData.txt`, through [`CodePoint::bidi_class`].
+//!
+//! See <https://www.unicode.org/reports/tr9/> for details.
+
+use crate::code_point_table::CodePoint;
+use crate::enumerated_property::EnumeratedProperty;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{Record, UcdFile};
+use crate::ucd_source::UcdSource;
+use std::collections::BTreeMap;
+
+/// The name of the file listing the Bidi_Mirroring_Glyph of code points.
+pub(crate) const BIDI_MIRRORING_TXT: &str = "BidiMirroring.txt";
+
+/// The name of the file listing the Bidi_Paired_Bracket and
+/// Bidi_Paired_Bracket_Type of code points.
+pub(crate) const BIDI_BRACKETS_TXT: &str = "BidiBrackets.txt";
+
+/// The name of the file listing the Bidi_Class of every code point, including
+/// the defaults for unassigned code points.
+pub(crate) const DERIVED_BIDI_CLASS_TXT: &str = "extracted/DerivedBidiClass.txt";
+
+/// The bidirectional class of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BidiClass {
+    ArabicLetter,
+    ArabicNumber,
+    ParagraphSeparator,
+    BoundaryNeutral,
+    CommonSeparator,
+    EuropeanNumber,
+    EuropeanSeparator,
+    EuropeanTerminator,
+    FirstStrongIsolate,
+    LeftToRight,
+    LeftToRightEmbedding,
+    LeftToRightIsolate,
+    LeftToRightOverride,
+    NonspacingMark,
+    OtherNeutral,
+    PopDirectionalFormat,
+    PopDirectionalIsolate,
+    RightToLeft,
+    RightToLeftEmbedding,
+    RightToLeftIsolate,
+    RightToLeftOverride,
+    SegmentSeparator,
+    WhiteSpace,
+}
+
+use BidiClass::*;
+
+/// Every bidi class, in the order `PropertyValueAliases.txt` lists them, with
+/// its short and long names.
+const CLASSES: [(BidiClass, &str, &str); 23] = [
+    (ArabicLetter, "AL", "Arabic_Letter"),
+    (ArabicNumber, "AN", "Arabic_Number"),
+    (ParagraphSeparator, "B", "Paragraph_Separator"),
+    (BoundaryNeutral, "BN", "Boundary_Neutral"),
+    (CommonSeparator, "CS", "Common_Separator"),
+    (EuropeanNumber, "EN", "European_Number"),
+    (EuropeanSeparator, "ES", "European_Separator"),
+    (EuropeanTerminator, "ET", "European_Terminator"),
+    (FirstStrongIsolate, "FSI", "First_Strong_Isolate"),
+    (LeftToRight, "L", "Left_To_Right"),
+    (LeftToRightEmbedding, "LRE", "Left_To_Right_Embedding"),
+    (LeftToRightIsolate, "LRI", "Left_To_Right_Isolate"),
+    (LeftToRightOverride, "LRO", "Left_To_Right_Override"),
+    (NonspacingMark, "NSM", "Nonspacing_Mark"),
+    (OtherNeutral, "ON", "Other_Neutral"),
+    (PopDirectionalFormat, "PDF", "Pop_Directional_Format"),
+    (PopDirectionalIsolate, "PDI", "Pop_Directional_Isolate"),
+    (RightToLeft, "R", "Right_To_Left"),
+    (RightToLeftEmbedding, "RLE", "Right_To_Left_Embedding"),
+    (RightToLeftIsolate, "RLI", "Right_To_Left_Isolate"),
+    (RightToLeftOverride, "RLO", "Right_To_Left_Override"),
+    (SegmentSeparator, "S", "Segment_Separator"),
+    (WhiteSpace, "WS", "White_Space"),
+];
+
+impl BidiClass {
+    /// Return an iterator over every bidi class.
+    pub fn all() -> impl Iterator<Item = BidiClass> {
+        CLASSES.iter().map(|(class, _, _)| *class)
+    }
+
+    /// The abbreviated name of this class, e.g. "L".
+    pub fn short_name(self) -> &'static str {
+        CLASSES[self as usize].1
+    }
+
+    /// The long name of this class, e.g. "Left_To_Right".
+    pub fn long_name(self) -> &'static str {
+        CLASSES[self as usize].2
+    }
+
+    /// Return the class with the abbreviated name `name`, as used in
+    /// `UnicodeData.txt`.
+    pub fn from_short_name(name: &str) -> Option<BidiClass> {
+        CLASSES
+            .iter()
+            .find(|(_, short, _)| *short == name)
+            .map(|(class, _, _)| *class)
+    }
+
+    /// Return the class with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<BidiClass> {
+        CLASSES
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(class, _, _)| *class)
+    }
+
+    /// Whether this class is strongly right-to-left (R or AL).
+    pub fn is_rtl(self) -> bool {
+        self == RightToLeft || self == ArabicLetter
+    }
+}
+
+/// The Bidi_Paired_Bracket_Type of a code point that is a paired bracket.
+/// (Other code points have the type None.)
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BracketType {
+    Open,
+    Close,
+}
+
+/// Every bracket type with its short and long names.
+const BRACKET_TYPES: [(BracketType, &str, &str); 2] = [
+    (BracketType::Open, "o", "Open"),
+    (BracketType::Close, "c", "Close"),
+];
+
+impl BracketType {
+    /// Return an iterator over every bracket type.
+    pub fn all() -> impl Iterator<Item = BracketType> {
+        BRACKET_TYPES
+            .iter()
+            .map(|(bracket_type, _, _)| *bracket_type)
+    }
+
+    /// The abbreviated name of this type, e.g. "o".
+    pub fn short_name(self) -> &'static str {
+        BRACKET_TYPES[self as usize].1
+    }
+
+    /// The long name of this type, e.g. "Open".
+    pub fn long_name(self) -> &'static str {
+        BRACKET_TYPES[self as usize].2
+    }
+
+    /// Return the type with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<BracketType> {
+        BRACKET_TYPES
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(bracket_type, _, _)| *bracket_type)
+    }
+}
+
+/// The bracket paired with a code point, and whether the code point opens or
+/// closes the pair.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct PairedBracket {
+    /// The Bidi_Paired_Bracket of the code point, e.g. U+0029 RIGHT
+    /// PARENTHESIS for U+0028 LEFT PARENTHESIS.
+    pub pair: u32,
+
+    /// The Bidi_Paired_Bracket_Type of the code point.
+    pub bracket_type: BracketType,
+}
+
+/// The bidi class, mirroring glyph, and paired bracket of every code point.
+pub struct BidiData {
+    /// The class of every code point, from `DerivedBidiClass.txt` and its
+    /// `@missing` lines.
+    classes: EnumeratedProperty<BidiClass>,
+
+    /// The Bidi_Mirroring_Glyph of every code point that has one.
+    mirroring_glyphs: BTreeMap<u32, u32>, // BTreeMap for sorting
+
+    /// The paired bracket of every code point that has one.
+    paired_brackets: BTreeMap<u32, PairedBracket>, // BTreeMap for sorting
+}
+
+impl BidiData {
+    /// The Bidi_Class of `code`, whether or not it's assigned.  Unassigned
+    /// code points have the default class for their range, e.g. R for
+    /// unassigned Hebrew code points.
+    pub fn bidi_class(&self, code: u32) -> BidiClass {
+        self.classes.get(code)
+    }
+
+    /// The code point whose glyph is the mirror image of the glyph of `code`,
+    /// e.g. U+0029 RIGHT PARENTHESIS for U+0028 LEFT PARENTHESIS, or `None`
+    /// if there's no such code point.
+    pub fn bidi_mirroring_glyph(&self, code: u32) -> Option<u32> {
+        self.mirroring_glyphs.get(&code).copied()
+    }
+
+    /// The Bidi_Mirroring_Glyph of every code point that has one.
+    pub fn mirroring_glyphs(&self) -> &BTreeMap<u32, u32> {
+        &self.mirroring_glyphs
+    }
+
+    /// The bracket paired with `code`, or `None` if it isn't a paired
+    /// bracket.
+    pub fn paired_bracket(&self, code: u32) -> Option<PairedBracket> {
+        self.paired_brackets.get(&code).copied()
+    }
+
+    /// The paired bracket of every code point that has one.
+    pub fn paired_brackets(&self) -> &BTreeMap<u32, PairedBracket> {
+        &self.paired_brackets
+    }
+}
+
+impl CodePoint {
+    /// The Bidi_Mirroring_Glyph of this code point, if it has one.
+    pub fn bidi_mirroring_glyph(&self, bidi: &BidiData) -> Option<u32> {
+        bidi.bidi_mirroring_glyph(self.code)
+    }
+
+    /// The bracket paired with this code point, if it's a paired bracket.
+    pub fn paired_bracket(&self, bidi: &BidiData) -> Option<PairedBracket> {
+        bidi.paired_bracket(self.code)
+    }
+}
+
+/// Parse the single code point in field `index` of `record`.
+fn parse_code(record: &Record, index: usize, expected: &str) -> Result<u32, UcdError> {
+    let field = record.field(index, expected)?;
+    u32::from_str_radix(field, 16).map_err(|_| record.error(field, expected))
+}
+
+/// Parse the `BidiMirroring.txt`, `BidiBrackets.txt`, and
+/// `extracted/DerivedBidiClass.txt` in `source`.
+///
+/// `BidiMirroring.txt` records consist of a code point and its mirroring
+/// glyph, e.g. `0028; 0029`.  `BidiBrackets.txt` records consist of a code
+/// point, its paired bracket, and its bracket type, e.g. `0028; 0029; o`.
+pub fn process_bidi(source: &UcdSource) -> Result<BidiData, UcdError> {
+    parse_bidi(
+        UcdFile::read(source, BIDI_MIRRORING_TXT)?,
+        UcdFile::read(source, BIDI_BRACKETS_TXT)?,
+        UcdFile::read(source, DERIVED_BIDI_CLASS_TXT)?,
+    )
+}
+
+fn parse_bidi(
+    mirroring_file: UcdFile,
+    brackets_file: UcdFile,
+    class_file: UcdFile,
+) -> Result<BidiData, UcdError> {
+    let mut mirroring_glyphs = BTreeMap::new();
+    for record in mirroring_file.records() {
+        let record = record?;
+        let glyph = parse_code(&record, 0, "a hexadecimal mirroring glyph")?;
+        mirroring_glyphs.extend(record.range.iter().map(|code| (code, glyph)));
+    }
+
+    let mut paired_brackets = BTreeMap::new();
+    for record in brackets_file.records() {
+        let record = record?;
+        let pair = parse_code(&record, 0, "a hexadecimal paired bracket")?;
+        let name = record.field(1, "a bracket type")?;
+        let bracket_type = match name {
+            "n" => continue,
+            _ => {
+                BracketType::from_name(name).ok_or_else(|| record.error(name, "a bracket type"))?
+            }
+        };
+        let bracket = PairedBracket { pair, bracket_type };
+        paired_brackets.extend(record.range.iter().map(|code| (code, bracket)));
+    }
+
+    let classes = EnumeratedProperty::parse(
+        class_file,
+        "a Bidi_Class value",
+        BidiClass::from_name,
+        LeftToRight,
+    )?;
+
+    Ok(BidiData {
+        classes,
+        mirroring_glyphs,
+        paired_brackets,
+    })
+}
+
+#[test]
+fn check_bidi() {
+    for (i, class) in BidiClass::all().enumerate() {
+        assert_eq!(class as usize, i, "CLASSES is in declaration order");
+        assert_eq!(BidiClass::from_short_name(class.short_name()), Some(class));
+        assert_eq!(BidiClass::from_name(class.long_name()), Some(class));
+    }
+    for (i, bracket_type) in BracketType::all().enumerate() {
+        assert_eq!(
+            bracket_type as usize, i,
+            "BRACKET_TYPES is in declaration order"
+        );
+        assert_eq!(
+            BracketType::from_name(bracket_type.short_name()),
+            Some(bracket_type)
+        );
+        assert_eq!(
+            BracketType::from_name(bracket_type.long_name()),
+            Some(bracket_type)
+        );
+    }
+
+    let mirroring_file = || {
+        UcdFile::new(
+            BIDI_MIRRORING_TXT,
+            "# BidiMirroring-16.0.0.txt\n\
+             0028; 0029 # LEFT PARENTHESIS\n\
+             0029; 0028 # RIGHT PARENTHESIS\n\
+             2208; 220B # ELEMENT OF\n",
+        )
+    };
+    let brackets_file = UcdFile::new(
+        BIDI_BRACKETS_TXT,
+        "# BidiBrackets-16.0.0.txt\n\
+         0028; 0029; o # LEFT PARENTHESIS\n\
+         0029; 0028; c # RIGHT PARENTHESIS\n",
+    );
+    let class_file = || {
+        UcdFile::new(
+            DERIVED_BIDI_CLASS_TXT,
+            "# DerivedBidiClass-16.0.0.txt\n\
+             # @missing: 0000..10FFFF; Left_To_Right\n\
+             # @missing: 0590..05FF; Right_To_Left\n\
+             # @missing: 0600..07BF; Arabic_Letter\n\
+             0000..0008    ; BN # Cc   [9] <control-0000>..<control-0008>\n\
+             0028          ; ON # Ps       LEFT PARENTHESIS\n\
+             0030..0039    ; EN # Nd  [10] DIGIT ZERO..DIGIT NINE\n\
+             05D0..05EA    ; R  # Lo  [27] HEBREW LETTER ALEF..HEBREW LETTER TAV\n",
+        )
+    };
+
+    let bidi = parse_bidi(mirroring_file(), brackets_file, class_file()).expect("bidi data");
+    assert_eq!(bidi.bidi_class(0x0000), BoundaryNeutral);
+    assert_eq!(bidi.bidi_class('(' as u32), OtherNeutral);
+    assert_eq!(bidi.bidi_class('7' as u32), EuropeanNumber);
+    assert_eq!(bidi.bidi_class(0x05D0), RightToLeft);
+    assert_eq!(bidi.bidi_class('A' as u32), LeftToRight);
+    // Unassigned code points in Hebrew and Arabic blocks default to R and AL.
+    assert_eq!(bidi.bidi_class(0x05FF), RightToLeft);
+    assert_eq!(bidi.bidi_class(0x07BF), ArabicLetter);
+    assert!(bidi.bidi_class(0x07BF).is_rtl());
+
+    assert_eq!(bidi.bidi_mirroring_glyph('(' as u32), Some(')' as u32));
+    assert_eq!(bidi.bidi_mirroring_glyph(0x2208), Some(0x220B));
+    assert_eq!(bidi.bidi_mirroring_glyph('A' as u32), None);
+    assert_eq!(bidi.mirroring_glyphs().len(), 3);
+    assert_eq!(
+        bidi.paired_bracket(')' as u32),
+        Some(PairedBracket {
+            pair: '(' as u32,
+            bracket_type: BracketType::Close
+        })
+    );
+    assert_eq!(bidi.paired_bracket(0x2208), None);
+
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    let paren = table.get('(' as u32).expect("U+0028");
+    assert_eq!(paren.bidi_class(), bidi.bidi_class(paren.code));
+    assert_eq!(paren.bidi_mirroring_glyph(&bidi), Some(')' as u32));
+    assert_eq!(
+        paren
+            .paired_bracket(&bidi)
+            .map(|bracket| bracket.bracket_type),
+        Some(BracketType::Open)
+    );
+
+    let error = parse_bidi(
+        mirroring_file(),
+        UcdFile::new(BIDI_BRACKETS_TXT, "0028; 0029; open # LEFT PARENTHESIS\n"),
+        class_file(),
+    )
+    .err()
+    .expect("bad bracket type");
+    assert_eq!(
+        error.to_string(),
+        "BidiBrackets.txt:1: expected a bracket type, found \"open\""
+    );
+}
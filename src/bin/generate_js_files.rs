@@ -11,10 +11,14 @@ use unicode_info::case_folding;
 use unicode_info::code_point_table;
 use unicode_info::constants::MAX_BMP;
 use unicode_info::derived_core_properties;
+use unicode_info::emoji_sequences;
 use unicode_info::non_bmp;
+use unicode_info::prop_list;
 use unicode_info::spaces;
-use unicode_info::special_casing;
+use unicode_info::special_casing::{self, CasingContext, Language};
 use unicode_info::types;
+use unicode_info::ucd_source;
+use unicode_info::unicode_version::{self, UnicodeVersion};
 
 const PRODUCTION: bool = false;
 
@@ -35,10 +39,25 @@ fn write_file(path: &str, s: String) -> io::Result<usize> {
 
 const WARNING_MESSAGE: &str = "/* Generated by make_unicode.py DO NOT MODIFY */\n";
 
-fn unicode_version_comment(version: &str) -> String {
+fn unicode_version_comment(version: &UnicodeVersion) -> String {
     format!("/* Unicode version: {version} */\n", version = version)
 }
 
+/// Return a JavaScript string literal containing `codes`, each escaped.
+fn js_string(codes: &[u32]) -> String {
+    let escapes: String = codes
+        .iter()
+        .map(|code| {
+            if *code <= MAX_BMP {
+                format!("\\u{:04X}", code)
+            } else {
+                format!("\\u{{{:X}}}", code)
+            }
+        })
+        .collect();
+    format!("\"{}\"", escapes)
+}
+
 const PUBLIC_DOMAIN: &str = r#"
 /*
  * Any copyright is dedicated to the Public Domain.
@@ -47,7 +66,7 @@ const PUBLIC_DOMAIN: &str = r#"
 "#;
 
 fn generate_regexp_character_class_escape_js(
-    version: &str,
+    version: &UnicodeVersion,
     space_set: &types::CodePointSet,
     table: &code_point_table::CodePointTable,
 ) -> io::Result<()> {
@@ -68,7 +87,7 @@ fn generate_regexp_character_class_escape_js(
     str += "var onlySpace = String.fromCodePoint(\n";
 
     str += Itertools::join(
-        &mut space_set.into_iter().map(|space| hex_and_name(*space)),
+        &mut space_set.iter().map(|space| hex_and_name(*space)),
         ",\n",
     )
     .as_str();
@@ -106,7 +125,7 @@ if (typeof reportCompare === "function")
 }
 
 fn generate_string_code_point_upper_lower_mapping_js(
-    version: &str,
+    version: &UnicodeVersion,
     table: &code_point_table::CodePointTable,
     non_bmp: &non_bmp::NonBMPInfo,
 ) -> io::Result<()> {
@@ -150,7 +169,7 @@ if (typeof reportCompare === "function")
 }
 
 fn generate_string_upper_lower_mapping_js(
-    version: &str,
+    version: &UnicodeVersion,
     cpt: &code_point_table::CodePointTable,
     special_casing: &special_casing::SpecialCasingData,
 ) -> io::Result<()> {
@@ -236,8 +255,137 @@ if (typeof reportCompare === "function")
     Ok(())
 }
 
+/// Return strings in which `code` is and isn't in `context`, to test a casing
+/// mapping that applies only in `context`.
+fn casing_context_samples(code: u32, context: Option<CasingContext>) -> Vec<Vec<u32>> {
+    const LATIN_CAPITAL_LETTER_A: u32 = 0x0041;
+    const LATIN_CAPITAL_LETTER_I: u32 = 0x0049;
+    const LATIN_SMALL_LETTER_I: u32 = 0x0069;
+    const COMBINING_ACUTE_ACCENT: u32 = 0x0301;
+    const COMBINING_DOT_ABOVE: u32 = 0x0307;
+    const COMBINING_GRAVE_ACCENT_BELOW: u32 = 0x0316; // combining class 220
+
+    let a = LATIN_CAPITAL_LETTER_A;
+    let below = COMBINING_GRAVE_ACCENT_BELOW;
+    match context {
+        None => vec![vec![code]],
+        Some(CasingContext::FinalSigma) => vec![
+            vec![a, code],
+            vec![a, code, a],
+            vec![a, '\'' as u32, code, '.' as u32],
+            vec![code],
+        ],
+        Some(CasingContext::AfterSoftDotted) => vec![
+            vec![LATIN_SMALL_LETTER_I, code],
+            vec![LATIN_SMALL_LETTER_I, below, code],
+            vec![LATIN_SMALL_LETTER_I, COMBINING_ACUTE_ACCENT, code],
+            vec![a, code],
+        ],
+        Some(CasingContext::MoreAbove) => vec![
+            vec![code, COMBINING_ACUTE_ACCENT],
+            vec![code, below, COMBINING_ACUTE_ACCENT],
+            vec![code],
+        ],
+        Some(CasingContext::BeforeDot) | Some(CasingContext::NotBeforeDot) => vec![
+            vec![code, COMBINING_DOT_ABOVE],
+            vec![code, below, COMBINING_DOT_ABOVE],
+            vec![code],
+        ],
+        Some(CasingContext::AfterI) => vec![
+            vec![LATIN_CAPITAL_LETTER_I, code],
+            vec![LATIN_CAPITAL_LETTER_I, below, code],
+            vec![a, code],
+        ],
+    }
+}
+
+fn generate_string_contextual_upper_lower_mapping_js(
+    version: &UnicodeVersion,
+    cpt: &code_point_table::CodePointTable,
+    special_casing: &special_casing::SpecialCasingData,
+    mapper: &special_casing::CaseMapper,
+    languages: &[Language],
+) -> io::Result<()> {
+    let mut str = String::new();
+
+    str += WARNING_MESSAGE;
+    str += unicode_version_comment(version).as_str();
+    str += PUBLIC_DOMAIN;
+
+    // Every code point with a conditional mapping, the context in which it
+    // applies, and the language (if any) in which it applies.
+    let mut conditions = special_casing
+        .conditional_tolower
+        .iter()
+        .chain(special_casing.conditional_toupper.iter())
+        .map(|(code, (_, context))| (None, *code, Some(*context)))
+        .collect::<Vec<_>>();
+    for language in languages {
+        conditions.extend(
+            special_casing
+                .lang_conditional_tolower
+                .get(language)
+                .into_iter()
+                .flatten()
+                .chain(
+                    special_casing
+                        .lang_conditional_toupper
+                        .get(language)
+                        .into_iter()
+                        .flatten(),
+                )
+                .map(|(code, (_, context))| (Some(*language), *code, *context)),
+        );
+    }
+
+    let samples = conditions
+        .into_iter()
+        .flat_map(|(language, code, context)| {
+            casing_context_samples(code, context)
+                .into_iter()
+                .map(move |sample| (language, sample))
+        })
+        .unique();
+    for (language, sample) in samples {
+        let names = sample.iter().map(|code| cpt.name(*code)).join(", ");
+        let (lower, upper) = match language {
+            None => ("toLowerCase()".to_string(), "toUpperCase()".to_string()),
+            Some(language) => (
+                format!("toLocaleLowerCase(\"{}\")", language.code()),
+                format!("toLocaleUpperCase(\"{}\")", language.code()),
+            ),
+        };
+        for (method, mapped) in &[
+            (lower, mapper.to_lower(&sample, language)),
+            (upper, mapper.to_upper(&sample, language)),
+        ] {
+            str += format!(
+                r#"assertEq({sample}.{method}, {mapped}); // {names}
+"#,
+                sample = js_string(&sample),
+                method = method,
+                mapped = js_string(mapped),
+                names = names,
+            )
+            .as_str();
+        }
+    }
+
+    str += r#"
+if (typeof reportCompare === "function")
+    reportCompare(true, true);
+"#;
+
+    write_file(
+        "js/src/tests/non262/String/string-contextual-upper-lower-mapping.js",
+        str,
+    )?;
+
+    Ok(())
+}
+
 fn generate_string_space_trim_js(
-    version: &str,
+    version: &UnicodeVersion,
     space_set: &types::CodePointSet,
     table: &code_point_table::CodePointTable,
 ) -> io::Result<()> {
@@ -258,7 +406,7 @@ fn generate_string_space_trim_js(
     str += "var onlySpace = String.fromCharCode(\n";
 
     str += Itertools::join(
-        &mut space_set.into_iter().map(|space| hex_and_name(*space)),
+        &mut space_set.iter().map(|space| hex_and_name(*space)),
         ",\n",
     )
     .as_str();
@@ -281,8 +429,8 @@ if (typeof reportCompare === "function")
 }
 
 fn generate_unicode_ignorecase_js(
-    version: &str,
-    all_codes_with_equivalents: &Vec<case_folding::CodeWithEquivalents>,
+    version: &UnicodeVersion,
+    all_codes_with_equivalents: &[case_folding::CodeWithEquivalents],
     table: &code_point_table::CodePointTable,
 ) -> io::Result<()> {
     fn to_hex(code: &u32) -> String {
@@ -344,39 +492,302 @@ if (typeof reportCompare === "function")
     Ok(())
 }
 
-fn main() -> io::Result<()> {
+/// Return the strings that aren't members of `property` that tests should
+/// check it doesn't match: each of its longer strings missing its last code
+/// point, and a few members of every other property.
+fn properties_of_strings_non_members(
+    sequences: &emoji_sequences::EmojiSequenceData,
+    property: emoji_sequences::StringProperty,
+) -> Vec<Vec<u32>> {
+    const SAMPLES_PER_OTHER_PROPERTY: usize = 3;
+
+    let set = sequences.get(property);
+    let incomplete = set
+        .multi_code_point_strings()
+        .map(|string| string[..string.len() - 1].to_vec());
+    let other_properties = emoji_sequences::StringProperty::all()
+        .filter(|other| *other != property)
+        .flat_map(|other| {
+            sequences
+                .get(other)
+                .iter()
+                .filter(|string| !set.contains(string))
+                .take(SAMPLES_PER_OTHER_PROPERTY)
+                .map(<[u32]>::to_vec)
+        });
+
+    incomplete
+        .chain(other_properties)
+        .filter(|string| !set.contains(string))
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn generate_regexp_properties_of_strings_js(
+    version: &UnicodeVersion,
+    sequences: &emoji_sequences::EmojiSequenceData,
+) -> io::Result<()> {
+    let mut str = String::new();
+
+    str += WARNING_MESSAGE;
+    str += unicode_version_comment(version).as_str();
+    str += PUBLIC_DOMAIN;
+
+    str += "var propertiesOfStrings = {\n";
+    for property in emoji_sequences::StringProperty::all() {
+        let set = sequences.get(property);
+        str += format!(
+            "  // {count} strings, {code_points} of them single code points\n",
+            count = set.len(),
+            code_points = set.code_points().len()
+        )
+        .as_str();
+        str += format!("  {name}: [\n", name = property.name()).as_str();
+        for string in set.iter() {
+            str += format!("    {},\n", js_string(string)).as_str();
+        }
+        str += "  ],\n";
+    }
+    str += "};\n";
+
+    str += "\n// Incomplete sequences, and sequences having only other properties.\n";
+    str += "var nonMembers = {\n";
+    for property in emoji_sequences::StringProperty::all() {
+        str += format!("  {name}: [\n", name = property.name()).as_str();
+        for string in properties_of_strings_non_members(sequences, property) {
+            str += format!("    {},\n", js_string(&string)).as_str();
+        }
+        str += "  ],\n";
+    }
+    str += "};\n";
+
+    str += r#"
+for (var [name, strings] of Object.entries(propertiesOfStrings)) {
+  var re = new RegExp("^\\p{" + name + "}$", "v");
+  var classRe = new RegExp("^[\\p{" + name + "}]$", "v");
+  for (var string of strings) {
+    assertEq(re.test(string), true);
+    assertEq(classRe.test(string), true);
+  }
+  for (var string of nonMembers[name]) {
+    assertEq(re.test(string), false);
+    assertEq(classRe.test(string), false);
+  }
+
+  // Properties of strings can't be negated.
+  assertThrowsInstanceOf(() => new RegExp("\\P{" + name + "}", "v"), SyntaxError);
+  assertThrowsInstanceOf(() => new RegExp("[^\\p{" + name + "}]", "v"), SyntaxError);
+
+  // Nor used without the v flag.
+  assertThrowsInstanceOf(() => new RegExp("\\p{" + name + "}", "u"), SyntaxError);
+}
+
+if (typeof reportCompare === "function")
+    reportCompare(true, true);
+"#;
+
+    write_file(
+        "js/src/tests/non262/RegExp/unicode-properties-of-strings.js",
+        str,
+    )?;
+
+    Ok(())
+}
+
+const MPL_LICENSE: &str = r#"/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+"#;
+
+fn generate_properties_of_strings_h(
+    version: &UnicodeVersion,
+    sequences: &emoji_sequences::EmojiSequenceData,
+) -> io::Result<()> {
+    // The name of the C++ arrays for `property`, e.g. "RGIEmojiFlagSequence".
+    fn array_name(property: emoji_sequences::StringProperty) -> String {
+        property.name().replace('_', "")
+    }
+
+    let mut str = String::new();
+
+    str += WARNING_MESSAGE;
+    str += unicode_version_comment(version).as_str();
+    str += MPL_LICENSE;
+
+    str += r#"
+#ifndef util_UnicodePropertiesOfStrings_h
+#define util_UnicodePropertiesOfStrings_h
+
+#include <stddef.h>
+
+namespace js {
+namespace unicode {
+
+/*
+ * The strings having a property of strings, e.g. RGI_Emoji.  Members that are
+ * a single code point are listed as inclusive ranges of code points.  Longer
+ * members are listed in `strings`, each preceded by its length.
+ */
+struct PropertyOfStrings {
+  const char* name;
+  const char32_t (*codePointRanges)[2];
+  size_t codePointRangesLength;
+  const char32_t* strings;
+  size_t stringsLength;
+};
+"#;
+
+    let mut descriptors = vec![];
+    for property in emoji_sequences::StringProperty::all() {
+        let set = sequences.get(property);
+        let name = array_name(property);
+
+        str += format!(
+            "\n// {property}: {count} strings, {code_points} of them single code points.\n",
+            property = property.name(),
+            count = set.len(),
+            code_points = set.code_points().len()
+        )
+        .as_str();
+
+        let mut ranges: Vec<(u32, u32)> = vec![];
+        for &code in set.code_points() {
+            match ranges.last_mut() {
+                Some((_, last)) if *last + 1 == code => *last = code,
+                _ => ranges.push((code, code)),
+            }
+        }
+        let ranges_array = if ranges.is_empty() {
+            "nullptr".to_string()
+        } else {
+            str += format!("static const char32_t {}CodePointRanges[][2] = {{\n", name).as_str();
+            for (first, last) in &ranges {
+                str += format!("    {{{:#06X}, {:#06X}}},\n", first, last).as_str();
+            }
+            str += "};\n";
+            format!("{}CodePointRanges", name)
+        };
+
+        let mut strings_length = 0;
+        let strings_array = if set.multi_code_point_strings().next().is_none() {
+            "nullptr".to_string()
+        } else {
+            str += format!("static const char32_t {}Strings[] = {{\n", name).as_str();
+            for string in set.multi_code_point_strings() {
+                str += format!(
+                    "    {}, {},\n",
+                    string.len(),
+                    string
+                        .iter()
+                        .map(|code| format!("{:#06X}", code))
+                        .join(", ")
+                )
+                .as_str();
+                strings_length += string.len() + 1;
+            }
+            str += "};\n";
+            format!("{}Strings", name)
+        };
+
+        descriptors.push(format!(
+            "    {{\"{property}\", {ranges_array}, {ranges_length}, {strings_array}, {strings_length}}},\n",
+            property = property.name(),
+            ranges_array = ranges_array,
+            ranges_length = ranges.len(),
+            strings_array = strings_array,
+            strings_length = strings_length,
+        ));
+    }
+
+    str += "\nstatic const PropertyOfStrings propertiesOfStrings[] = {\n";
+    for descriptor in descriptors {
+        str += descriptor.as_str();
+    }
+    str += "};\n";
+
+    str += r#"
+}  // namespace unicode
+}  // namespace js
+
+#endif /* util_UnicodePropertiesOfStrings_h */
+"#;
+
+    write_file("js/src/util/UnicodePropertiesOfStrings.h", str)?;
+
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let program = match std::env::args().next() {
+        Some(arg) => arg,
+        None => "generate_js_files".to_string(),
+    };
+
     {
         let base_path = std::env::current_dir().expect("couldn't get current working directory");
         if !base_path.ends_with("js/src/util") {
-            panic!(
-                "{} must be run from js/src/util",
-                match std::env::args().next() {
-                    Some(arg) => arg,
-                    None => "generate_js_files".to_string(),
-                }
-            );
+            panic!("{} must be run from js/src/util", program);
         }
     }
 
+    // Process the UCD files embedded in this crate, unless a directory
+    // containing an unpacked UCD (e.g. for a Unicode beta) is passed.
+    let source = match std::env::args_os().nth(1) {
+        Some(dir) => ucd_source::UcdSource::Directory(PathBuf::from(dir)),
+        None => ucd_source::UcdSource::Embedded,
+    };
+
     // Every input file contains an embedded Unicode version somewhere...except
-    // `UnicodeData.txt`.  Alas.
-    let version: &'static str = derived_core_properties::unicode_version();
+    // `UnicodeData.txt`.  Alas.  Verify the versions of the rest agree.
+    let version = unicode_version::check_unicode_version(&source)?;
 
-    let table = code_point_table::generate_code_point_table();
-    let dcp = derived_core_properties::process_derived_core_properties();
+    let table = code_point_table::generate_code_point_table(&source)?;
+    let dcp = derived_core_properties::process_derived_core_properties(&source)?;
     let bmp = bmp::generate_bmp_info(&table, &dcp);
-    let non_bmp = non_bmp::generate_non_bmp_info(&table);
+    let non_bmp = non_bmp::generate_non_bmp_info(&source, &table)?;
     let space_set = spaces::compute_white_space(&table);
 
-    let case_folding = case_folding::process_case_folding();
+    let case_folding = case_folding::process_case_folding(&source)?;
 
-    let special_casing = special_casing::process_special_casing(&bmp);
+    let special_casing = special_casing::process_special_casing(&source, &table)?;
+
+    let soft_dotted = prop_list::process_soft_dotted(&source)?;
+    let languages = Language::all().collect::<Vec<_>>();
+    let mapper = special_casing::CaseMapper::new(
+        &special_casing,
+        &table,
+        &bmp,
+        &non_bmp,
+        &dcp,
+        &soft_dotted,
+    );
 
     generate_regexp_character_class_escape_js(&version, &space_set, &table)?;
     generate_string_space_trim_js(&version, &space_set, &table)?;
     generate_string_code_point_upper_lower_mapping_js(&version, &table, &non_bmp)?;
     generate_string_upper_lower_mapping_js(&version, &table, &special_casing)?;
+    generate_string_contextual_upper_lower_mapping_js(
+        &version,
+        &table,
+        &special_casing,
+        &mapper,
+        &languages,
+    )?;
     generate_unicode_ignorecase_js(&version, &case_folding.all_codes_with_equivalents, &table)?;
 
+    // The emoji sequence files aren't part of the UCD proper, so only generate
+    // properties of strings when they're present.
+    match emoji_sequences::process_emoji_sequences(&source) {
+        Ok(sequences) => {
+            generate_properties_of_strings_h(&version, &sequences)?;
+            generate_regexp_properties_of_strings_js(&version, &sequences)?;
+        }
+        Err(error) if error.is_not_found() => {
+            eprintln!("skipping properties of strings: {}", error);
+        }
+        Err(error) => return Err(error.into()),
+    }
+
     Ok(())
 }
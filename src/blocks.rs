@@ -0,0 +1,171 @@
+//! Processes `Blocks.txt` to determine the Block property of code points: the
+//! named, contiguous range of code points to which each code point belongs.
+//!
+//! `Blocks.txt` isn't embedded in this crate, so it must be read from a
+//! [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr44/#Blocks.txt> for details.
+
+use crate::code_point_table::CodePoint;
+use crate::property_aliases::loose_matching_key;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{CodePointRange, UcdFile};
+use crate::ucd_source::UcdSource;
+
+/// The name of the file processed by this module.
+pub(crate) const BLOCKS_TXT: &str = "Blocks.txt";
+
+/// A block, e.g. Basic Latin (U+0000..U+007F).
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Block {
+    /// The name of the block, e.g. "Basic Latin".
+    pub name: &'static str,
+
+    /// The code points in the block, assigned or not.
+    pub range: CodePointRange,
+}
+
+impl Block {
+    /// Return the set of every code point in this block.
+    pub fn code_points(&self) -> CodePointSet {
+        self.range.iter().collect()
+    }
+}
+
+/// Every block.
+pub struct BlockData {
+    /// Every block, in increasing order of code points.  Code points in no
+    /// block have the Block value No_Block.
+    blocks: Vec<Block>,
+}
+
+impl BlockData {
+    /// The block containing `code`, or `None` if it's in no block.
+    pub fn block(&self, code: u32) -> Option<&Block> {
+        let i = self.blocks.partition_point(|block| block.range.last < code);
+        self.blocks
+            .get(i)
+            .filter(|block| block.range.contains(code))
+    }
+
+    /// Return an iterator over every block, in increasing order of code points.
+    pub fn iter(&self) -> impl Iterator<Item = &Block> {
+        self.blocks.iter()
+    }
+
+    /// Return the block named `name`, compared using loose matching, so that
+    /// e.g. "Basic_Latin" and "basiclatin" name the Basic Latin block.
+    pub fn get(&self, name: &str) -> Option<&Block> {
+        let key = loose_matching_key(name);
+        self.blocks
+            .iter()
+            .find(|block| loose_matching_key(block.name) == key)
+    }
+}
+
+impl CodePoint {
+    /// The block containing this code point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this code point isn't in a block in `blocks`, which happens
+    /// only if it's from a different Unicode version than `UnicodeData.txt`.
+    pub fn block<'a>(&self, blocks: &'a BlockData) -> &'a Block {
+        blocks
+            .block(self.code)
+            .expect("every code point in UnicodeData.txt is in a block")
+    }
+}
+
+/// Parse every block from the `Blocks.txt` in `source`.
+///
+/// Records consist of a code point range and a block name, e.g.
+/// `0000..007F; Basic Latin`.
+pub fn process_blocks(source: &UcdSource) -> Result<BlockData, UcdError> {
+    parse_blocks(UcdFile::read(source, BLOCKS_TXT)?)
+}
+
+fn parse_blocks(file: UcdFile) -> Result<BlockData, UcdError> {
+    let mut blocks = vec![];
+    for record in file.records() {
+        let record = record?;
+        let name = record.field(0, "a block name")?;
+        if blocks
+            .last()
+            .is_some_and(|previous: &Block| previous.range.last >= record.range.first)
+        {
+            let range = format!("{:04X}..{:04X}", record.range.first, record.range.last);
+            return Err(record.error(&range, "blocks in increasing order"));
+        }
+        blocks.push(Block {
+            name,
+            range: record.range,
+        });
+    }
+    Ok(BlockData { blocks })
+}
+
+#[test]
+fn check_blocks() {
+    let file = UcdFile::new(
+        BLOCKS_TXT,
+        "# Blocks-16.0.0.txt\n\
+         # @missing: 0000..10FFFF; No_Block\n\
+         0000..007F; Basic Latin\n\
+         0080..00FF; Latin-1 Supplement\n\
+         0370..03FF; Greek and Coptic\n\
+         1F600..1F64F; Emoticons\n",
+    );
+
+    let blocks = parse_blocks(file).expect("Blocks.txt");
+    assert_eq!(blocks.iter().count(), 4);
+    assert_eq!(
+        blocks.block('A' as u32).map(|block| block.name),
+        Some("Basic Latin")
+    );
+    assert_eq!(
+        blocks.block(0x00FF).map(|block| block.name),
+        Some("Latin-1 Supplement")
+    );
+    assert_eq!(
+        blocks.block(0x1F600).map(|block| block.name),
+        Some("Emoticons")
+    );
+    assert_eq!(blocks.block(0x0100), None);
+    assert_eq!(blocks.block(0x10FFFF), None);
+
+    let greek = blocks.get("Greek_And_Coptic").expect("Greek and Coptic");
+    assert_eq!(
+        greek.range,
+        CodePointRange {
+            first: 0x0370,
+            last: 0x03FF
+        }
+    );
+    assert_eq!(greek.code_points().len(), 0x90);
+    assert_eq!(
+        blocks
+            .get("latin1supplement")
+            .map(|block| block.range.first),
+        Some(0x0080)
+    );
+    assert!(blocks.get("Greek").is_none());
+
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    let sigma = table.get(0x03C2).expect("U+03C2");
+    assert_eq!(sigma.block(&blocks), greek);
+
+    let error = parse_blocks(UcdFile::new(
+        BLOCKS_TXT,
+        "0000..007F; Basic Latin\n\
+         0070..00FF; Latin-1 Supplement\n",
+    ))
+    .err()
+    .expect("overlapping blocks");
+    assert_eq!(
+        error.to_string(),
+        "Blocks.txt:2: expected blocks in increasing order, found \"0070..00FF\""
+    );
+}
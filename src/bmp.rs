@@ -2,7 +2,8 @@
 
 use crate::code_point_table;
 use crate::constants::{COMPATIBILITY_IDENTIFIER_PART, LINE_TERMINATOR, MAX_BMP, WHITE_SPACE};
-use crate::derived_core_properties;
+use crate::derived_core_properties::{self, DerivedProperty};
+use crate::general_category::GeneralCategory;
 use crate::types::{Flags, MappedCodePoint};
 use proc_macro2;
 use quote::quote;
@@ -139,7 +140,7 @@ pub fn generate_bmp_info(
         .filter(|code_point| code_point.code <= MAX_BMP)
     {
         let code = code_point.code;
-        let category = code_point.category();
+        let category = code_point.general_category();
         let uppercase = code_point.uppercase();
         let lowercase = code_point.lowercase();
 
@@ -151,13 +152,16 @@ pub fn generate_bmp_info(
 
         let mut flags = Flags(0);
 
-        if category == "Zs" || WHITE_SPACE.contains(&code) || LINE_TERMINATOR.contains(&code) {
+        if category == GeneralCategory::SpaceSeparator
+            || WHITE_SPACE.contains(&code)
+            || LINE_TERMINATOR.contains(&code)
+        {
             flags.set_space();
         }
 
-        if derived_properties.id_start.contains(&code) {
+        if derived_properties.contains(DerivedProperty::IdStart, code) {
             flags.set_unicode_id_start();
-        } else if derived_properties.id_continue.contains(&code)
+        } else if derived_properties.contains(DerivedProperty::IdContinue, code)
             || COMPATIBILITY_IDENTIFIER_PART.contains(&code)
         {
             flags.set_unicode_id_continue_only();
@@ -4,26 +4,48 @@
 extern crate proc_macro2;
 
 use crate::constants::MAX_BMP;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{Entry, UcdFile};
+use crate::ucd_source::UcdSource;
 use quote::quote;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
-static CASE_FOLDING_TXT: &str = include_str!("data/CaseFolding.txt");
+/// The name of the file parsed by `CaseFoldingParse`.
+const CASE_FOLDING_TXT: &str = "CaseFolding.txt";
+
+/// The status of a `CaseFolding.txt` mapping.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Status {
+    /// A mapping shared by simple and full case folding.
+    Common,
+
+    /// A simple case folding mapping, for a code point that also has a full
+    /// mapping.
+    Simple,
+
+    /// A full case folding mapping, to a sequence of code points.
+    Full,
+
+    /// A mapping for Turkic languages, replacing the common mapping of
+    /// I and U+0130 LATIN CAPITAL LETTER I WITH DOT ABOVE.
+    Turkic,
+}
 
 struct CaseFoldingParse {
-    lines: std::str::Lines<'static>,
+    file: UcdFile,
 }
 
 impl CaseFoldingParse {
-    fn simple_and_common_foldings() -> CaseFoldingParse {
-        CaseFoldingParse {
-            lines: CASE_FOLDING_TXT.lines(),
-        }
+    fn all_foldings(source: &UcdSource) -> Result<CaseFoldingParse, UcdError> {
+        Ok(CaseFoldingParse {
+            file: UcdFile::read(source, CASE_FOLDING_TXT)?,
+        })
     }
 }
 
 impl Iterator for CaseFoldingParse {
-    type Item = (u32, u32);
+    type Item = Result<(u32, Status, Vec<u32>), UcdError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
@@ -31,31 +53,49 @@ impl Iterator for CaseFoldingParse {
             // format:
             //
             // <code>; <status>; <mapping>; # <name>
-            let line = self
-                .lines
-                .next()?
-                .split('#')
-                .nth(0)
-                .expect("splitting returns at least one string");
-            if line == "" {
-                continue;
+            let record = match self.file.next()? {
+                Ok(Entry::Record(record)) => record,
+                Ok(Entry::Missing(_)) => continue,
+                Err(error) => return Some(Err(error)),
+            };
+            if record.fields.len() != 2 || record.range.first != record.range.last {
+                return Some(Err(
+                    record.error(&record.fields.join("; "), "<code>; <status>; <mapping>;")
+                ));
             }
 
-            let row = line.split("; ").collect::<Vec<&'static str>>();
-            assert!(row.len() == 4);
-
-            // Unicode regular expression support depends only on common/simple
-            // foldings.
-            if ["C", "S"].contains(&row[1]) {
-                let code = u32::from_str_radix(row[0], 16).expect("hex code");
-                let mapping = u32::from_str_radix(row[2], 16).expect("hex mapping");
-                return Some((code, mapping));
-            }
-
-            assert!(
-                ["F", "T"].contains(&row[1]),
-                "should see (C)ommon, (S)imple, (F)ull, and (T)urkish foldings"
-            );
+            let status = match record.fields[0] {
+                "C" => Status::Common,
+                "S" => Status::Simple,
+                "F" => Status::Full,
+                "T" => Status::Turkic,
+                status => {
+                    return Some(Err(record.error(
+                        status,
+                        "a (C)ommon, (S)imple, (F)ull, or (T)urkish folding status",
+                    )))
+                }
+            };
+
+            // Only full foldings map to more than one code point.
+            let mapping = record.fields[1]
+                .split_whitespace()
+                .map(|code| u32::from_str_radix(code, 16).ok())
+                .collect::<Option<Vec<u32>>>()
+                .filter(|mapping| match status {
+                    Status::Full => !mapping.is_empty(),
+                    _ => mapping.len() == 1,
+                });
+            return Some(match mapping {
+                Some(mapping) => Ok((record.range.first, status, mapping)),
+                None => Err(record.error(
+                    record.fields[1],
+                    match status {
+                        Status::Full => "a hexadecimal code point sequence mapping",
+                        _ => "a single hexadecimal code point mapping",
+                    },
+                )),
+            });
         }
     }
 }
@@ -114,12 +154,54 @@ pub struct CaseFoldingData {
     ///
     /// we will have `bmp_folding_table[bmp_folding_index[0x0041] as usize] == Delta(0x0061 - 0x0041)`.
     pub bmp_folding_index: Vec<u32>,
+
+    /// The common and simple foldings of every code point that has one,
+    /// across the full BMP and non-BMP gamut.
+    pub simple_folding: std::collections::BTreeMap<u32, u32>,
+
+    /// The full foldings of every code point whose full folding differs from
+    /// its simple folding, e.g. U+00DF LATIN SMALL LETTER SHARP S -> "ss".
+    /// Every other code point's full folding is its common folding, if any.
+    pub full_folding: std::collections::BTreeMap<u32, Vec<u32>>,
+
+    /// The foldings that replace common foldings in Turkic languages:
+    /// I -> U+0131 LATIN SMALL LETTER DOTLESS I, and U+0130 LATIN CAPITAL
+    /// LETTER I WITH DOT ABOVE -> i.
+    pub turkic_folding: std::collections::BTreeMap<u32, u32>,
+}
+
+impl CaseFoldingData {
+    /// Return the full case folding of `codes`, as used in case-insensitive
+    /// comparison of strings: each code point is replaced by its full
+    /// folding, or its common folding if it has no full folding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use unicode_info::case_folding::process_case_folding;
+    /// # use unicode_info::ucd_source::UcdSource;
+    /// let folding = process_case_folding(&UcdSource::Embedded).unwrap();
+    /// let strasse = "Stra\u{DF}e".chars().map(|c| c as u32).collect::<Vec<_>>();
+    /// let folded = folding.fold_full(&strasse);
+    /// assert_eq!(folded, "strasse".chars().map(|c| c as u32).collect::<Vec<_>>());
+    /// ```
+    pub fn fold_full(&self, codes: &[u32]) -> Vec<u32> {
+        let mut folded = Vec::with_capacity(codes.len());
+        for code in codes {
+            match self.full_folding.get(code) {
+                Some(mapping) => folded.extend(mapping),
+                None => folded.push(*self.simple_folding.get(code).unwrap_or(code)),
+            }
+        }
+        folded
+    }
 }
 
 type SortedMap<K, V> = std::collections::BTreeMap<K, V>;
 type SortedSet<T> = std::collections::BTreeSet<T>;
 
-/// Generate common and simple case-folding information from `CaseFolding.txt`.
+/// Generate common and simple case-folding information from the
+/// `CaseFolding.txt` in `source`.
 ///
 /// Case folding is the process of converting code point sequences to a
 /// canonical, folded form.  JavaScript depends upon the case folding process to
@@ -143,9 +225,10 @@ type SortedSet<T> = std::collections::BTreeSet<T>;
 /// code point, in four different and potentially overlapping ways.  Because
 /// Unicode regular expressions
 /// [depend](https://tc39.es/ecma262/#sec-runtime-semantics-canonicalize-ch)
-/// upon only  "simple" and "common" foldings, we discard "Turkish" and "Full"
-/// foldings during processing.
-pub fn process_case_folding() -> CaseFoldingData {
+/// upon only  "simple" and "common" foldings, our generated tables use only
+/// those.  "Full" and "Turkish" foldings are kept separately, in
+/// [`CaseFoldingData::full_folding`] and [`CaseFoldingData::turkic_folding`].
+pub fn process_case_folding(source: &UcdSource) -> Result<CaseFoldingData, UcdError> {
     // Basic map of code -> folded for all Common/Simple mappings.
     let mut folding_map = SortedMap::<u32, u32>::new();
 
@@ -155,14 +238,28 @@ pub fn process_case_folding() -> CaseFoldingData {
     // FINAL SIGMA fold to U+03C3 GREEK SMALL LETTER SIGMA.)
     let mut reverse_folding_map = SortedMap::<u32, Vec<u32>>::new();
 
-    // Compute both of the above maps from the full set of one-way mappings.
-    for (code, mapping) in CaseFoldingParse::simple_and_common_foldings() {
-        folding_map.insert(code, mapping);
-
-        reverse_folding_map
-            .entry(mapping)
-            .or_insert_with(|| vec![])
-            .push(code);
+    // Full and Turkic mappings, which Unicode regular expressions don't use.
+    let mut full_folding = SortedMap::<u32, Vec<u32>>::new();
+    let mut turkic_folding = SortedMap::<u32, u32>::new();
+
+    // Compute all of the above maps from the full set of one-way mappings.
+    for folding in CaseFoldingParse::all_foldings(source)? {
+        let (code, status, mapping) = folding?;
+        match status {
+            Status::Common | Status::Simple => {
+                folding_map.insert(code, mapping[0]);
+                reverse_folding_map
+                    .entry(mapping[0])
+                    .or_default()
+                    .push(code);
+            }
+            Status::Full => {
+                full_folding.insert(code, mapping);
+            }
+            Status::Turkic => {
+                turkic_folding.insert(code, mapping[0]);
+            }
+        }
     }
 
     // Build a (sorted) set of all code points participating in non-identity
@@ -244,11 +341,14 @@ pub fn process_case_folding() -> CaseFoldingData {
         bmp_folding_index[code as usize] = index;
     }
 
-    CaseFoldingData {
+    Ok(CaseFoldingData {
         all_codes_with_equivalents,
         bmp_folding_table,
         bmp_folding_index,
-    }
+        simple_folding: folding_map,
+        full_folding,
+        turkic_folding,
+    })
 }
 
 #[test]
@@ -257,7 +357,8 @@ fn check_case_folding() {
         all_codes_with_equivalents,
         bmp_folding_index,
         bmp_folding_table,
-    } = process_case_folding();
+        ..
+    } = process_case_folding(&UcdSource::Embedded).expect("embedded data");
 
     assert!(
         all_codes_with_equivalents.contains(&(0x0399, vec![0x03B9, 0x0345, 0x1FBE])),
@@ -279,3 +380,34 @@ fn check_case_folding() {
         let _idx = code;
     }
 }
+
+#[test]
+fn check_full_and_turkic_folding() {
+    let folding = process_case_folding(&UcdSource::Embedded).expect("embedded data");
+
+    assert_eq!(
+        folding.full_folding.get(&0x00DF),
+        Some(&vec![0x0073, 0x0073])
+    );
+    assert_eq!(
+        folding.full_folding.get(&0x0130),
+        Some(&vec![0x0069, 0x0307]),
+        "LATIN CAPITAL LETTER I WITH DOT ABOVE"
+    );
+    assert_eq!(folding.full_folding.get(&0x0041), None);
+    assert_eq!(folding.simple_folding.get(&0x1E9E), Some(&0x00DF));
+    assert_eq!(
+        folding.full_folding.get(&0x1E9E),
+        Some(&vec![0x0073, 0x0073])
+    );
+    assert_eq!(folding.turkic_folding.get(&0x0049), Some(&0x0131));
+    assert_eq!(folding.turkic_folding.get(&0x0130), Some(&0x0069));
+    assert_eq!(folding.turkic_folding.len(), 2);
+
+    // U+FB03 LATIN SMALL LIGATURE FFI, U+10400 DESERET CAPITAL LETTER LONG I
+    assert_eq!(
+        folding.fold_full(&[0x0041, 0xFB03, 0x10400, 0x00DF, 0x03A3]),
+        [0x0061, 0x0066, 0x0066, 0x0069, 0x10428, 0x0073, 0x0073, 0x03C3]
+    );
+    assert_eq!(folding.fold_full(&[]), []);
+}
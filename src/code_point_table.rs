@@ -1,7 +1,162 @@
 //! Parse the contents of `UnicodeData.txt`, the central code point registry
 //! file, into queryable and iterable form.
 
-static UNICODE_DATA_TXT: &str = include_str!("data/UnicodeData.txt");
+use crate::bidi::BidiClass;
+use crate::general_category::GeneralCategory;
+use crate::name_aliases::{self, AliasType, NameAlias};
+use crate::names::{self, NameIndex};
+use crate::ucd_error::UcdError;
+use crate::ucd_source::UcdSource;
+
+/// The formatting tag of a compatibility decomposition, indicating how the
+/// decomposed form differs from the code point it decomposes.
+///
+/// See <https://www.unicode.org/reports/tr44/#Character_Decomposition_Mappings>
+/// for details.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DecompositionTag {
+    /// `<font>`: a font variant (e.g. a blackletter form).
+    Font,
+    /// `<noBreak>`: a no-break version of a space or hyphen.
+    NoBreak,
+    /// `<initial>`: an initial presentation form (Arabic).
+    Initial,
+    /// `<medial>`: a medial presentation form (Arabic).
+    Medial,
+    /// `<final>`: a final presentation form (Arabic).
+    Final,
+    /// `<isolated>`: an isolated presentation form (Arabic).
+    Isolated,
+    /// `<circle>`: an encircled form.
+    Circle,
+    /// `<super>`: a superscript form.
+    Super,
+    /// `<sub>`: a subscript form.
+    Sub,
+    /// `<vertical>`: a vertical layout presentation form.
+    Vertical,
+    /// `<wide>`: a wide (or zenkaku) compatibility character.
+    Wide,
+    /// `<narrow>`: a narrow (or hankaku) compatibility character.
+    Narrow,
+    /// `<small>`: a small variant form (CNS compatibility).
+    Small,
+    /// `<square>`: a CJK squared font variant.
+    Square,
+    /// `<fraction>`: a vulgar fraction form.
+    Fraction,
+    /// `<compat>`: otherwise unspecified compatibility character.
+    Compat,
+}
+
+impl DecompositionTag {
+    /// Return the tag corresponding to `tag`, written as in `UnicodeData.txt`
+    /// including angle brackets (e.g. `"<noBreak>"`).
+    pub fn from_tag(tag: &str) -> Option<DecompositionTag> {
+        use DecompositionTag::*;
+        Some(match tag {
+            "<font>" => Font,
+            "<noBreak>" => NoBreak,
+            "<initial>" => Initial,
+            "<medial>" => Medial,
+            "<final>" => Final,
+            "<isolated>" => Isolated,
+            "<circle>" => Circle,
+            "<super>" => Super,
+            "<sub>" => Sub,
+            "<vertical>" => Vertical,
+            "<wide>" => Wide,
+            "<narrow>" => Narrow,
+            "<small>" => Small,
+            "<square>" => Square,
+            "<fraction>" => Fraction,
+            "<compat>" => Compat,
+            _ => return None,
+        })
+    }
+}
+
+/// The decomposition mapping of a code point.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Decomposition {
+    /// The formatting tag of a compatibility decomposition, or `None` if this
+    /// is a canonical decomposition.
+    pub tag: Option<DecompositionTag>,
+
+    /// The code points the code point decomposes to.  (This is a single
+    /// level of decomposition: the code points here may themselves have
+    /// decompositions.)
+    pub mapping: Vec<u32>,
+}
+
+impl Decomposition {
+    /// Whether this is a canonical (rather than compatibility) decomposition.
+    pub fn is_canonical(&self) -> bool {
+        self.tag.is_none()
+    }
+}
+
+/// A rational number, the form of a code point's Numeric_Value property.
+///
+/// For example, U+00BD VULGAR FRACTION ONE HALF has numeric value `1/2`, and
+/// U+0F33 TIBETAN DIGIT HALF ZERO has numeric value `-1/2`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Rational {
+    pub numerator: i64,
+    pub denominator: u64,
+}
+
+impl Rational {
+    /// Parse a rational number written as in `UnicodeData.txt`: an integer,
+    /// optionally followed by `/` and a positive integer denominator.
+    fn parse(s: &str) -> Option<Rational> {
+        let mut parts = s.splitn(2, '/');
+        let numerator = parts.next()?.parse::<i64>().ok()?;
+        let denominator = match parts.next() {
+            Some(denominator) => denominator.parse::<u64>().ok()?,
+            None => 1,
+        };
+        if denominator == 0 {
+            return None;
+        }
+        Some(Rational {
+            numerator,
+            denominator,
+        })
+    }
+}
+
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// Parse a decomposition written as in `UnicodeData.txt`: an optional `<tag>`
+/// followed by space-separated hexadecimal codes.
+fn parse_decomposition(s: &str) -> Option<Decomposition> {
+    let mut parts = s.split(' ').peekable();
+    let tag = match parts.peek() {
+        Some(part) if part.starts_with('<') => {
+            let tag = DecompositionTag::from_tag(part)?;
+            parts.next();
+            Some(tag)
+        }
+        _ => None,
+    };
+    let mapping = parts
+        .map(|code| u32::from_str_radix(code, 16).ok())
+        .collect::<Option<Vec<u32>>>()?;
+    if mapping.is_empty() {
+        return None;
+    }
+
+    Some(Decomposition { tag, mapping })
+}
 
 /// Information about a particular code point.
 #[derive(Copy, Clone, Debug)]
@@ -10,9 +165,32 @@ struct CodePointInfo {
     /// LATIN CAPITAL LETTER A.
     name: &'static str,
 
-    /// The Unicode category of the code point, in its abbreviated form: for
-    /// example, "Zs" rather than "Space_Separator".
-    category: &'static str,
+    /// The Unicode general category of the code point.
+    category: GeneralCategory,
+
+    /// The canonical combining class of the code point.
+    combining_class: u8,
+
+    /// The bidirectional class of the code point.
+    bidi_class: BidiClass,
+
+    /// The decomposition of the code point: an optional `<tag>` followed by
+    /// space-separated codes, or empty if the code point doesn't decompose.
+    /// (Validated during parsing, but only converted to a `Decomposition` on
+    /// request, so that `CodePointInfo` can remain `Copy`.)
+    decomposition: &'static str,
+
+    /// The decimal digit value of the code point, if it has one.
+    decimal: Option<u8>,
+
+    /// The digit value of the code point, if it has one.
+    digit: Option<u8>,
+
+    /// The numeric value of the code point, if it has one.
+    numeric: Option<Rational>,
+
+    /// Whether the code point is mirrored in bidirectional text.
+    mirrored: bool,
 
     /// The alias of the code point, if any.
     ///
@@ -20,13 +198,17 @@ struct CodePointInfo {
     /// alias.
     alias: &'static str,
 
-    /// The code for the uppercase form of the associated code point, or empty
+    /// The code for the uppercase form of the associated code point, or `None`
     /// if it's the same as the associated code point.
-    upper: &'static str,
+    upper: Option<u32>,
 
-    /// The code for the lowercase form of the associated code point, or empty
+    /// The code for the lowercase form of the associated code point, or `None`
     /// if it's the same as the associated code point.
-    lower: &'static str,
+    lower: Option<u32>,
+
+    /// The code for the titlecase form of the associated code point, or `None`
+    /// if it's the same as the associated code point's uppercase form.
+    title: Option<u32>,
 }
 
 /// Code point info, including its code.
@@ -45,6 +227,11 @@ impl CodePoint {
     }
 
     pub fn category(&self) -> &'static str {
+        self.info.category.short_name()
+    }
+
+    /// The general category of this code point.
+    pub fn general_category(&self) -> GeneralCategory {
         self.info.category
     }
 
@@ -52,16 +239,60 @@ impl CodePoint {
         self.info.alias
     }
 
+    /// The canonical combining class of this code point, used in the
+    /// Canonical Ordering Algorithm.  Most code points have combining class
+    /// `0`.
+    pub fn combining_class(&self) -> u8 {
+        self.info.combining_class
+    }
+
+    /// The bidirectional class of this code point.
+    pub fn bidi_class(&self) -> BidiClass {
+        self.info.bidi_class
+    }
+
+    /// The decomposition mapping of this code point, or `None` if it doesn't
+    /// decompose.
+    pub fn decomposition(&self) -> Option<Decomposition> {
+        if self.info.decomposition.is_empty() {
+            None
+        } else {
+            Some(
+                parse_decomposition(self.info.decomposition)
+                    .expect("decomposition validated during parsing"),
+            )
+        }
+    }
+
+    /// The value of this code point as a decimal digit, if it's a decimal digit
+    /// (general category Nd).
+    pub fn decimal_value(&self) -> Option<u8> {
+        self.info.decimal
+    }
+
+    /// The value of this code point as a digit, if it's a digit (including
+    /// digits that aren't decimal digits, such as superscript digits).
+    pub fn digit_value(&self) -> Option<u8> {
+        self.info.digit
+    }
+
+    /// The numeric value of this code point, if it has one.
+    pub fn numeric_value(&self) -> Option<Rational> {
+        self.info.numeric
+    }
+
+    /// Whether this code point is mirrored in bidirectional text, i.e. has the
+    /// Bidi_Mirrored property.
+    pub fn bidi_mirrored(&self) -> bool {
+        self.info.mirrored
+    }
+
     /// The code for the uppercase form of this code point.
     ///
     /// If this code point doesn't have an uppercase form, this will be the code
     /// point itself.
     pub fn uppercase(&self) -> u32 {
-        if self.info.upper.is_empty() {
-            self.code
-        } else {
-            u32::from_str_radix(self.info.upper, 16).expect("bad hex code")
-        }
+        self.info.upper.unwrap_or(self.code)
     }
 
     /// The code for the lowercase form of this code point.
@@ -69,11 +300,16 @@ impl CodePoint {
     /// If this code point doesn't have a lowercase form, this will be the code
     /// point itself.
     pub fn lowercase(&self) -> u32 {
-        if self.info.lower.is_empty() {
-            self.code
-        } else {
-            u32::from_str_radix(self.info.lower, 16).expect("bad hex code")
-        }
+        self.info.lower.unwrap_or(self.code)
+    }
+
+    /// The code for the titlecase form of this code point.
+    ///
+    /// `UnicodeData.txt` leaves this field empty when the titlecase form is
+    /// the uppercase form, so if this code point doesn't have an uppercase form
+    /// either, this will be the code point itself.
+    pub fn titlecase(&self) -> u32 {
+        self.info.title.unwrap_or_else(|| self.uppercase())
     }
 }
 
@@ -83,25 +319,167 @@ struct CodePointRange {
     info: CodePointInfo,
 }
 
+/// The name of the file parsed by `UnicodeDataParse`.
+const UNICODE_DATA_TXT: &str = "UnicodeData.txt";
+
 /// A structure representing parse state during a parse of the contents of
 /// `UnicodeData.txt`.
 struct UnicodeDataParse {
     within_range: Option<CodePointRange>,
-    lines: std::str::Lines<'static>,
+    lines: std::iter::Enumerate<std::str::Lines<'static>>,
 }
 
 impl UnicodeDataParse {
-    /// Produce an iterator over the structured contents of `UnicodeData.txt`.
-    fn parse() -> UnicodeDataParse {
+    /// Produce an iterator over the structured contents of `contents`, the
+    /// contents of `UnicodeData.txt`.
+    fn new(contents: &'static str) -> UnicodeDataParse {
         UnicodeDataParse {
             within_range: None,
-            lines: UNICODE_DATA_TXT.lines(),
+            lines: contents.lines().enumerate(),
         }
     }
+
+    /// Parse the line `line`, numbered `line_number`.  Returns the code point
+    /// described by the line, or `None` if the line starts a range of code
+    /// points (in which case `self.within_range` is set to it).
+    fn parse_line(
+        &mut self,
+        line_number: usize,
+        line: &'static str,
+    ) -> Result<Option<CodePoint>, UcdError> {
+        fn to_fields(
+            line_number: usize,
+            line: &'static str,
+        ) -> Result<Vec<&'static str>, UcdError> {
+            // UnicodeData.txt consists of semicolon-delimited fields: a
+            // leading field containing the hexadecimal code value, then
+            // fourteen additional fields.  See
+            // http://www.unicode.org/reports/tr44/#UnicodeData.txt
+            // for details.
+            let fields = line.split(';').collect::<Vec<&'static str>>();
+            if fields.len() != 15 {
+                return Err(UcdError::parse(
+                    UNICODE_DATA_TXT,
+                    line_number,
+                    line,
+                    "fifteen semicolon-delimited fields",
+                ));
+            }
+            Ok(fields)
+        }
+
+        fn get_code(line_number: usize, fields: &[&'static str]) -> Result<u32, UcdError> {
+            u32::from_str_radix(fields[0], 16).map_err(|_| {
+                UcdError::parse(
+                    UNICODE_DATA_TXT,
+                    line_number,
+                    fields[0],
+                    "a hexadecimal code point",
+                )
+            })
+        }
+
+        /// Convert the fields of a line into a `CodePointInfo`, or return the
+        /// offending field and a description of what was expected.
+        fn decompose_fields(
+            fields: &[&'static str],
+        ) -> Result<CodePointInfo, (&'static str, &'static str)> {
+            fn optional<T>(
+                field: &'static str,
+                parse: impl Fn(&str) -> Option<T>,
+                expected: &'static str,
+            ) -> Result<Option<T>, (&'static str, &'static str)> {
+                if field.is_empty() {
+                    Ok(None)
+                } else {
+                    parse(field).map(Some).ok_or((field, expected))
+                }
+            }
+            let code = |s: &str| u32::from_str_radix(s, 16).ok();
+            let digit = |s: &str| s.parse::<u8>().ok().filter(|d| *d <= 9);
+
+            let category = GeneralCategory::from_short_name(fields[2])
+                .ok_or((fields[2], "a general category abbreviation"))?;
+            let combining_class = fields[3]
+                .parse::<u8>()
+                .map_err(|_| (fields[3], "a canonical combining class"))?;
+            let bidi_class = BidiClass::from_short_name(fields[4])
+                .ok_or((fields[4], "a bidi class abbreviation"))?;
+            optional(fields[5], parse_decomposition, "a decomposition mapping")?;
+            let mirrored = match fields[9] {
+                "Y" => true,
+                "N" => false,
+                _ => return Err((fields[9], "Y or N")),
+            };
+
+            Ok(CodePointInfo {
+                name: fields[1],
+                category,
+                combining_class,
+                bidi_class,
+                decomposition: fields[5],
+                decimal: optional(fields[6], digit, "a decimal digit value")?,
+                digit: optional(fields[7], digit, "a digit value")?,
+                numeric: optional(fields[8], Rational::parse, "a numeric value")?,
+                mirrored,
+                alias: fields[10],
+                upper: optional(fields[12], code, "a hexadecimal uppercase mapping")?,
+                lower: optional(fields[13], code, "a hexadecimal lowercase mapping")?,
+                title: optional(fields[14], code, "a hexadecimal titlecase mapping")?,
+            })
+        }
+
+        let fields = to_fields(line_number, line)?;
+        let code = get_code(line_number, &fields)?;
+        let mut info = decompose_fields(&fields).map_err(|(text, expected)| {
+            UcdError::parse(UNICODE_DATA_TXT, line_number, text, expected)
+        })?;
+
+        // A consecutive code point pair may represent a range of code
+        // points, for example
+        //
+        //   D800;<Non Private Use High Surrogate, First>;Cs;0;L;;;;;N;;;;;
+        //   DB7F;<Non Private Use High Surrogate, Last>;Cs;0;L;;;;;N;;;;;
+        if info.name.starts_with('<') && info.name.ends_with("First>") {
+            let (index, range_end_line) = self.lines.next().ok_or_else(|| {
+                UcdError::parse(
+                    UNICODE_DATA_TXT,
+                    line_number,
+                    line,
+                    "a following <..., Last> line",
+                )
+            })?;
+            let range_end_fields = to_fields(index + 1, range_end_line)?;
+            if !range_end_fields[1].ends_with("Last>") {
+                return Err(UcdError::parse(
+                    UNICODE_DATA_TXT,
+                    index + 1,
+                    range_end_fields[1],
+                    "the <..., Last> end of a range",
+                ));
+            }
+
+            let last_code = get_code(index + 1, &range_end_fields)?;
+
+            // Remove "<" and ", First>" to extract the general name of
+            // all code points in the range.
+            info.name = &info.name[1..info.name.len() - 8];
+
+            // Pause examining UnicodeData.txt lines, and resume at start
+            // of the loop yielding code points within the defined range.
+            self.within_range = Some(CodePointRange {
+                range: code..=last_code,
+                info,
+            });
+            return Ok(None);
+        }
+
+        Ok(Some(CodePoint { code, info }))
+    }
 }
 
 impl Iterator for UnicodeDataParse {
-    type Item = CodePoint;
+    type Item = Result<CodePoint, UcdError>;
 
     /// Yields information about the next code point described in
     /// `UnicodeData.txt`.
@@ -114,7 +492,7 @@ impl Iterator for UnicodeDataParse {
                         code,
                         info: within_range.info,
                     };
-                    return Some(code_point);
+                    return Some(Ok(code_point));
                 }
 
                 // Once all code points in the range have been produced, resume
@@ -122,78 +500,14 @@ impl Iterator for UnicodeDataParse {
                 self.within_range = None;
             }
 
-            // Then loop over remaining lines in UnicodeData.txt.
-            loop {
-                let line = match self.lines.next() {
-                    None => {
-                        // There are no more lines to parse, so we're done.
-                        return None;
-                    }
-                    Some(line) => line,
-                };
-
-                fn to_fields(line: &'static str) -> Vec<&'static str> {
-                    // UnicodeData.txt consists of semicolon-delimited fields: a
-                    // leading field containing the hexadecimal code value, then
-                    // fourteen additional fields.  See
-                    // http://www.unicode.org/reports/tr44/#UnicodeData.txt
-                    // for details.
-                    let fields = line.split(';').collect::<Vec<&'static str>>();
-                    assert_eq!(fields.len(), 15);
-                    fields
-                }
-
-                let fields = to_fields(&line);
-
-                fn get_code(fields: &Vec<&'static str>) -> u32 {
-                    u32::from_str_radix(fields[0], 16).expect("hex code")
-                }
-
-                fn decompose_fields(fields: &Vec<&'static str>) -> CodePointInfo {
-                    CodePointInfo {
-                        name: fields[1],
-                        category: fields[2],
-                        alias: fields[10],
-                        upper: fields[12],
-                        lower: fields[13],
-                    }
-                }
-
-                let code = get_code(&fields);
-                let mut info = decompose_fields(&fields);
-
-                // A consecutive code point pair may represent a range of code
-                // points, for example
-                //
-                //   D800;<Non Private Use High Surrogate, First>;Cs;0;L;;;;;N;;;;;
-                //   DB7F;<Non Private Use High Surrogate, Last>;Cs;0;L;;;;;N;;;;;
-                if info.name.starts_with('<') && info.name.ends_with("First>") {
-                    let range_end_line = self.lines.next().expect("second line in range");
-                    let range_end_fields = to_fields(&range_end_line);
-
-                    let last_code = get_code(&range_end_fields);
-
-                    // Remove "<" and ", First>" to extract the general name of
-                    // all code points in the range.
-                    info.name = &info.name[1..info.name.len() - 8];
-
-                    let range = CodePointRange {
-                        range: code..=last_code,
-                        info,
-                    };
-
-                    // Resume at start of the outer loop yielding code points
-                    // within the defined range.
-                    self.within_range = Some(range);
-                    break;
-                }
-
-                let code_point = CodePoint { code, info };
-                return Some(code_point);
+            // Then process the next line in UnicodeData.txt.  (Once there are
+            // no more lines to parse, we're done.)
+            let (index, line) = self.lines.next()?;
+            match self.parse_line(index + 1, line) {
+                Ok(Some(code_point)) => return Some(Ok(code_point)),
+                Ok(None) => continue,
+                Err(error) => return Some(Err(error)),
             }
-
-            // Pause examining UnicodeData.txt lines to process a code point
-            // range.
         }
     }
 }
@@ -209,6 +523,17 @@ type CodePointMap = std::collections::BTreeMap<u32, CodePointInfo>;
 /// [`iter()`](CodePointTable.iter).
 pub struct CodePointTable {
     map: CodePointMap,
+
+    /// The formal aliases of code points, from `NameAliases.txt` if it's
+    /// available.
+    aliases: std::collections::HashMap<u32, Vec<NameAlias>>,
+
+    /// Named character sequences, from `NamedSequences.txt` if it's
+    /// available.
+    named_sequences: Vec<(&'static str, Vec<u32>)>,
+
+    /// An index of every name, built on first use.
+    name_index: std::sync::OnceLock<NameIndex>,
 }
 
 /// An iterator over the code points in a `CodePointTable`.
@@ -220,13 +545,10 @@ impl Iterator for CodePointTableIter<'_> {
     type Item = CodePoint;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.iter.next() {
-            None => None,
-            Some((code, info)) => Some(CodePoint {
-                code: *code,
-                info: *info,
-            }),
-        }
+        self.iter.next().map(|(code, info)| CodePoint {
+            code: *code,
+            info: *info,
+        })
     }
 }
 
@@ -234,19 +556,58 @@ impl CodePointTable {
     /// Return a string containing the code point's name and (if it has one) its
     /// alias.
     ///
+    /// Hangul syllables and ideographs listed as ranges in `UnicodeData.txt`
+    /// are named algorithmically, e.g. HANGUL SYLLABLE GAG or CJK UNIFIED
+    /// IDEOGRAPH-4E00.
+    ///
+    /// If `NameAliases.txt` is available, the name is the code point's
+    /// corrected name if it has one, and control characters are named by their
+    /// first control alias (e.g. CHARACTER TABULATION rather than
+    /// `<control>`).  In those cases the alias is the code point's first
+    /// abbreviation, e.g. HT.
+    ///
     /// # Example
     ///
     /// ```
     /// # use unicode_info::code_point_table::CodePointTable;
     /// # use unicode_info::code_point_table::generate_code_point_table;
-    /// let table: CodePointTable = generate_code_point_table();
+    /// # use unicode_info::ucd_source::UcdSource;
+    /// let table: CodePointTable = generate_code_point_table(&UcdSource::Embedded).unwrap();
     /// assert_eq!(table.name('A' as u32), "LATIN CAPITAL LETTER A");
     /// assert_eq!(table.name(0xFEFF),
     ///            "ZERO WIDTH NO-BREAK SPACE (BYTE ORDER MARK)");
+    /// assert_eq!(table.name(0x0009), "CHARACTER TABULATION (HT)");
+    /// assert_eq!(table.name(0xAC01), "HANGUL SYLLABLE GAG");
     /// ```
     pub fn name(&self, code: u32) -> String {
         let CodePointInfo { name, alias, .. } = self.map.get(&code).expect("code point");
-        let mut s = String::from(*name);
+        let aliases = self.aliases(code);
+        let is = |alias_type| move |alias: &&NameAlias| alias.alias_type == alias_type;
+
+        // Corrections are listed oldest first.
+        let formal_name = aliases
+            .iter()
+            .rev()
+            .find(is(AliasType::Correction))
+            .or_else(|| {
+                if name.starts_with('<') {
+                    aliases.iter().find(is(AliasType::Control))
+                } else {
+                    None
+                }
+            });
+        let (name, alias) = match formal_name {
+            Some(formal_name) => (
+                formal_name.alias,
+                aliases
+                    .iter()
+                    .find(is(AliasType::Abbreviation))
+                    .map_or("", |abbreviation| abbreviation.alias),
+            ),
+            None => (*name, *alias),
+        };
+
+        let mut s = names::algorithmic_name(code, name).unwrap_or_else(|| String::from(name));
         if !alias.is_empty() {
             s.push_str(&format!(" ({alias})", alias = alias));
         }
@@ -261,7 +622,8 @@ impl CodePointTable {
     /// ```
     /// # use unicode_info::code_point_table::CodePointTable;
     /// # use unicode_info::code_point_table::generate_code_point_table;
-    /// let table: CodePointTable = generate_code_point_table();
+    /// # use unicode_info::ucd_source::UcdSource;
+    /// let table: CodePointTable = generate_code_point_table(&UcdSource::Embedded).unwrap();
     /// assert_eq!(table.full_name('A' as u32),
     ///            "U+0041 LATIN CAPITAL LETTER A");
     /// assert_eq!(table.full_name(0xFEFF),
@@ -271,40 +633,96 @@ impl CodePointTable {
         format!("U+{code:04X} {name}", code = code, name = self.name(code))
     }
 
+    /// Return every formal alias of the code point, in the order
+    /// `NameAliases.txt` lists them, or no aliases if it isn't available.
+    pub fn aliases(&self, code: u32) -> &[NameAlias] {
+        self.aliases.get(&code).map_or(&[], |aliases| aliases)
+    }
+
+    /// Return every named character sequence and its name, in the order
+    /// `NamedSequences.txt` lists them, or none if it isn't available.
+    pub fn named_sequences(&self) -> &[(&'static str, Vec<u32>)] {
+        &self.named_sequences
+    }
+
+    /// Return the code point named `name`, which may be its name in
+    /// `UnicodeData.txt`, an algorithmically derived name, or any of its
+    /// aliases.  Names are compared using UAX44-LM2 loose matching: see
+    /// [`name_matching_key`](names::name_matching_key).
+    ///
+    /// This doesn't accept the names of named character sequences, which name
+    /// several code points and so can't be returned as one: `None` is
+    /// returned for them, and
+    /// [`lookup_named_sequence`](CodePointTable::lookup_named_sequence) finds
+    /// them instead.  To resolve a `\N{...}`-style reference that may name
+    /// either, try both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use unicode_info::code_point_table::generate_code_point_table;
+    /// # use unicode_info::ucd_source::UcdSource;
+    /// let table = generate_code_point_table(&UcdSource::Embedded).unwrap();
+    /// assert_eq!(table.lookup_name("GREEK SMALL LETTER FINAL SIGMA"), Some(0x03C2));
+    /// assert_eq!(table.lookup_name("greek small letter final_sigma"), Some(0x03C2));
+    /// assert_eq!(table.lookup_name("HANGUL SYLLABLE GAG"), Some(0xAC01));
+    /// assert_eq!(table.lookup_name("GREEK SMALL LETTER FINAL"), None);
+    /// ```
+    pub fn lookup_name(&self, name: &str) -> Option<u32> {
+        self.name_index().code_point(name)
+    }
+
+    /// Return the named character sequence named `name`, compared like
+    /// [`lookup_name`](CodePointTable::lookup_name) compares names.
+    pub fn lookup_named_sequence(&self, name: &str) -> Option<&[u32]> {
+        self.name_index().sequence(name)
+    }
+
+    /// The index of every name in this table, which is built the first time
+    /// it's needed.
+    fn name_index(&self) -> &NameIndex {
+        self.name_index.get_or_init(|| NameIndex::new(self))
+    }
+
     /// Get the `CodePoint` for the provided code, if `UnicodeData.txt`
     /// includes it.
     pub fn get(&self, code: u32) -> Option<CodePoint> {
-        match self.map.get(&code) {
-            Some(info) => Some(CodePoint { code, info: *info }),
-            None => None,
-        }
+        self.map
+            .get(&code)
+            .map(|info| CodePoint { code, info: *info })
     }
 
     /// Return an iterator (in sorted order) over all code points and their info
     /// in this table.
-    pub fn iter(&self) -> CodePointTableIter {
+    pub fn iter(&self) -> CodePointTableIter<'_> {
         CodePointTableIter {
             iter: self.map.iter(),
         }
     }
 }
 
-/// Generate a table of all code points, mapping code to characteristics.
-pub fn generate_code_point_table() -> CodePointTable {
+/// Generate a table of all code points, mapping code to characteristics, from
+/// the `UnicodeData.txt` and (if they're present) `NameAliases.txt` and
+/// `NamedSequences.txt` in `source`.
+pub fn generate_code_point_table(source: &UcdSource) -> Result<CodePointTable, UcdError> {
     let mut code_point_map = CodePointMap::new();
 
-    for code_point in UnicodeDataParse::parse() {
+    for code_point in UnicodeDataParse::new(source.read(UNICODE_DATA_TXT)?) {
+        let code_point = code_point?;
         code_point_map.insert(code_point.code, code_point.info);
     }
 
-    CodePointTable {
+    Ok(CodePointTable {
         map: code_point_map,
-    }
+        aliases: name_aliases::parse_name_aliases(source)?,
+        named_sequences: names::parse_named_sequences(source)?,
+        name_index: std::sync::OnceLock::new(),
+    })
 }
 
 #[test]
 fn check_unicode_data() {
-    let table = generate_code_point_table();
+    let table = generate_code_point_table(&UcdSource::Embedded).expect("UnicodeData.txt");
     assert_eq!(
         table.name('A' as u32),
         "LATIN CAPITAL LETTER A",
@@ -320,4 +738,225 @@ fn check_unicode_data() {
         "U+1F4A9 PILE OF POO",
         "sanity check of a non-BMP code point"
     );
+    assert_eq!(
+        table.full_name(0x4E00),
+        "U+4E00 CJK UNIFIED IDEOGRAPH-4E00",
+        "sanity check of an algorithmically named ideograph"
+    );
+    assert_eq!(table.name(0x17000), "TANGUT IDEOGRAPH-17000");
+    assert_eq!(table.name(0xD4DB), "HANGUL SYLLABLE PWILH");
+    assert_eq!(table.name(0x18B00), "KHITAN SMALL SCRIPT CHARACTER-18B00");
+    assert_eq!(table.name(0x1B170), "NUSHU CHARACTER-1B170");
+}
+
+#[test]
+fn check_unicode_data_fields() {
+    let table = generate_code_point_table(&UcdSource::Embedded).expect("UnicodeData.txt");
+    let get = |code| table.get(code).expect("code point");
+
+    // U+00C5 LATIN CAPITAL LETTER A WITH RING ABOVE
+    let a_ring = get(0x00C5);
+    assert_eq!(a_ring.combining_class(), 0);
+    assert_eq!(a_ring.bidi_class(), BidiClass::LeftToRight);
+    assert_eq!(
+        a_ring.decomposition(),
+        Some(Decomposition {
+            tag: None,
+            mapping: vec![0x0041, 0x030A],
+        })
+    );
+    assert_eq!(a_ring.titlecase(), 0x00C5);
+
+    // U+0301 COMBINING ACUTE ACCENT
+    assert_eq!(get(0x0301).combining_class(), 230);
+
+    // U+01C4 LATIN CAPITAL LETTER DZ WITH CARON has a distinct titlecase form,
+    // U+01C5 LATIN CAPITAL LETTER D WITH SMALL LETTER Z WITH CARON.
+    let dz = get(0x01C4);
+    assert_eq!(dz.titlecase(), 0x01C5);
+    assert_eq!(
+        dz.decomposition().expect("decomposes").tag,
+        Some(DecompositionTag::Compat)
+    );
+
+    // U+0061 LATIN SMALL LETTER A titlecases to its uppercase form.
+    assert_eq!(get('a' as u32).titlecase(), 'A' as u32);
+
+    // U+2075 SUPERSCRIPT FIVE
+    let five = get(0x2075);
+    assert_eq!(five.decimal_value(), None);
+    assert_eq!(five.digit_value(), Some(5));
+    assert_eq!(
+        five.numeric_value(),
+        Some(Rational {
+            numerator: 5,
+            denominator: 1
+        })
+    );
+    assert_eq!(get('7' as u32).decimal_value(), Some(7));
+
+    // U+0F33 TIBETAN DIGIT HALF ZERO
+    let half_zero = get(0x0F33).numeric_value().expect("numeric");
+    assert_eq!(half_zero.to_string(), "-1/2");
+
+    // U+16B61 PAHAWH HMONG NUMBER TRILLIONS
+    assert_eq!(
+        get(0x16B61).numeric_value().expect("numeric").numerator,
+        1_000_000_000_000
+    );
+
+    // U+3000 IDEOGRAPHIC SPACE
+    let ideographic_space = get(0x3000).general_category();
+    assert_eq!(ideographic_space, GeneralCategory::SpaceSeparator);
+    assert!(ideographic_space.is_in(crate::general_category::GeneralCategoryGroup::Separator));
+
+    // U+0028 LEFT PARENTHESIS
+    assert!(get('(' as u32).bidi_mirrored());
+    assert!(!get('A' as u32).bidi_mirrored());
+}
+
+#[test]
+fn check_unicode_data_errors() {
+    let error = UnicodeDataParse::new(
+        "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n\
+         0042;LATIN CAPITAL LETTER B;Lu;0;L;;;;;N;;;;006G;\n",
+    )
+    .collect::<Result<Vec<_>, _>>()
+    .expect_err("malformed lowercase mapping");
+    assert_eq!(
+        error.to_string(),
+        "UnicodeData.txt:2: expected a hexadecimal lowercase mapping, found \"006G\""
+    );
+}
+
+#[test]
+fn check_name_aliases() {
+    let dir = crate::ucd_source::TestDirectory::new("check_code_point_name_aliases");
+    dir.write(
+        UNICODE_DATA_TXT,
+        "0009;<control>;Cc;0;S;;;;;N;CHARACTER TABULATION;;;;\n\
+         0085;<control>;Cc;0;B;;;;;N;NEXT LINE (NEL);;;;\n\
+         01A2;LATIN CAPITAL LETTER OI;Lu;0;L;;;;;N;LATIN CAPITAL LETTER O I;;;01A3;\n\
+         FEFF;ZERO WIDTH NO-BREAK SPACE;Cf;0;BN;;;;;N;BYTE ORDER MARK;;;;\n",
+    )
+    .write(
+        name_aliases::NAME_ALIASES_TXT,
+        "# NameAliases-13.0.0.txt\n\
+         0009;CHARACTER TABULATION;control\n\
+         0009;HORIZONTAL TABULATION;control\n\
+         0009;HT;abbreviation\n\
+         0009;TAB;abbreviation\n\
+         0085;NEXT LINE;control\n\
+         0085;NEL;abbreviation\n\
+         01A2;LATIN CAPITAL LETTER GHA;correction\n\
+         FEFF;BYTE ORDER MARK;alternate\n\
+         FEFF;BOM;abbreviation\n\
+         FEFF;ZWNBSP;abbreviation\n",
+    );
+
+    let table = generate_code_point_table(&dir.source()).expect("UnicodeData.txt");
+    assert_eq!(table.name(0x0009), "CHARACTER TABULATION (HT)");
+    assert_eq!(table.full_name(0x0085), "U+0085 NEXT LINE (NEL)");
+    assert_eq!(table.name(0x01A2), "LATIN CAPITAL LETTER GHA");
+    assert_eq!(
+        table.name(0xFEFF),
+        "ZERO WIDTH NO-BREAK SPACE (BYTE ORDER MARK)"
+    );
+    assert_eq!(table.aliases(0x0009).len(), 4);
+    assert_eq!(
+        table.aliases(0x01A2),
+        [NameAlias {
+            alias: "LATIN CAPITAL LETTER GHA",
+            alias_type: AliasType::Correction
+        }]
+    );
+    assert_eq!(table.aliases('A' as u32), []);
+
+    let embedded = generate_code_point_table(&UcdSource::Embedded).expect("UnicodeData.txt");
+    assert_eq!(embedded.name(0x0009), "CHARACTER TABULATION (HT)");
+    assert_eq!(embedded.name(0x0085), "NEXT LINE (NEL)");
+    assert_eq!(embedded.aliases(0x0009), table.aliases(0x0009));
+
+    // Without NameAliases.txt, names come from UnicodeData.txt alone.
+    let bare = crate::ucd_source::TestDirectory::new("check_code_point_names_without_aliases");
+    bare.write(
+        UNICODE_DATA_TXT,
+        "0009;<control>;Cc;0;S;;;;;N;CHARACTER TABULATION;;;;\n",
+    );
+    let table = generate_code_point_table(&bare.source()).expect("UnicodeData.txt");
+    assert_eq!(table.name(0x0009), "<control> (CHARACTER TABULATION)");
+    assert_eq!(table.aliases(0x0009), []);
+}
+
+#[test]
+fn check_lookup_name() {
+    use crate::constants;
+
+    let dir = crate::ucd_source::TestDirectory::new("check_lookup_name");
+    dir.write(
+        UNICODE_DATA_TXT,
+        "0009;<control>;Cc;0;S;;;;;N;CHARACTER TABULATION;;;;\n\
+         0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n\
+         0F60;TIBETAN LETTER -A;Lo;0;L;;;;;N;;;;;\n\
+         0F68;TIBETAN LETTER A;Lo;0;L;;;;;N;;;;;\n\
+         116C;HANGUL JUNGSEONG OE;Lo;0;L;;;;;N;;;;;\n\
+         1180;HANGUL JUNGSEONG O-E;Lo;0;L;;;;;N;;;;;\n\
+         4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;\n\
+         9FFC;<CJK Ideograph, Last>;Lo;0;L;;;;;N;;;;;\n\
+         E000;<Private Use, First>;Co;0;L;;;;;N;;;;;\n\
+         F8FF;<Private Use, Last>;Co;0;L;;;;;N;;;;;\n\
+         FE18;PRESENTATION FORM FOR VERTICAL RIGHT WHITE LENTICULAR BRAKCET;Pe;0;ON;<vertical> 3017;;;;N;;;;;\n",
+    )
+    .write(
+        name_aliases::NAME_ALIASES_TXT,
+        "0009;CHARACTER TABULATION;control\n\
+         0009;TAB;abbreviation\n\
+         FE18;PRESENTATION FORM FOR VERTICAL RIGHT WHITE LENTICULAR BRACKET;correction\n",
+    )
+    .write(
+        names::NAMED_SEQUENCES_TXT,
+        "LATIN CAPITAL LETTER A WITH MACRON AND GRAVE;0100 0300\n",
+    );
+
+    let table = generate_code_point_table(&dir.source()).expect("UnicodeData.txt");
+    assert_eq!(table.lookup_name("LATIN CAPITAL LETTER A"), Some(0x0041));
+    assert_eq!(table.lookup_name("Latin capital letter-a"), Some(0x0041));
+    assert_eq!(table.lookup_name("latin_capital_letter_a"), Some(0x0041));
+    assert_eq!(
+        table.lookup_name("character tabulation"),
+        Some(constants::CHARACTER_TABULATION)
+    );
+    assert_eq!(table.lookup_name("TAB"), Some(0x0009));
+    assert_eq!(table.lookup_name("<control>"), None);
+    assert_eq!(table.lookup_name("TIBETAN LETTER -A"), Some(0x0F60));
+    assert_eq!(table.lookup_name("TIBETAN LETTER A"), Some(0x0F68));
+    assert_eq!(table.lookup_name("HANGUL JUNGSEONG OE"), Some(0x116C));
+    assert_eq!(table.lookup_name("HANGUL JUNGSEONG O-E"), Some(0x1180));
+    // Only the hyphen between the O and the E distinguishes U+1180.
+    assert_eq!(table.lookup_name("HANGUL-JUNGSEONG OE"), Some(0x116C));
+    assert_eq!(table.lookup_name("HANGUL-JUNGSEONG O-E"), Some(0x1180));
+    assert_eq!(
+        table.lookup_name("CJK UNIFIED IDEOGRAPH-4E00"),
+        Some(0x4E00)
+    );
+    assert_eq!(table.lookup_name("CJK Ideograph"), None);
+    assert_eq!(table.lookup_name("Private Use"), None);
+    // Both the original and the corrected name are recognized.
+    assert_eq!(
+        table.lookup_name("PRESENTATION FORM FOR VERTICAL RIGHT WHITE LENTICULAR BRAKCET"),
+        Some(0xFE18)
+    );
+    assert_eq!(
+        table.lookup_name("PRESENTATION FORM FOR VERTICAL RIGHT WHITE LENTICULAR BRACKET"),
+        Some(0xFE18)
+    );
+    assert_eq!(
+        table.lookup_name("LATIN CAPITAL LETTER A WITH MACRON AND GRAVE"),
+        None
+    );
+    assert_eq!(
+        table.lookup_named_sequence("Latin Capital Letter A with Macron and Grave"),
+        Some(&[0x0100, 0x0300][..])
+    );
+    assert_eq!(table.lookup_named_sequence("LATIN CAPITAL LETTER A"), None);
 }
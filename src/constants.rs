@@ -4,6 +4,9 @@
 /// unit.
 pub const MAX_BMP: u32 = 0xFFFF;
 
+/// The maximum code point value.
+pub const MAX_CODE_POINT: u32 = 0x10FFFF;
+
 /// Code for U+0009 CHARACTER TABULATION.
 pub const CHARACTER_TABULATION: u32 = 0x0009;
 /// Code for U+000B LINE TABULATION.
@@ -20,6 +23,8 @@ pub const SPACE: u32 = 0x0020;
 pub const DOLLAR_SIGN: u32 = 0x0024;
 /// Code for U+0048 LATIN CAPITAL LETTER H.
 pub const LATIN_CAPITAL_LETTER_H: u32 = 0x0048;
+/// Code for U+0049 LATIN CAPITAL LETTER I.
+pub const LATIN_CAPITAL_LETTER_I: u32 = 0x0049;
 /// Code for U+0053 LATIN CAPITAL LETTER S.
 pub const LATIN_CAPITAL_LETTER_S: u32 = 0x0053;
 /// Code for U+005F LOW LINE.
@@ -1,173 +1,210 @@
-//! Processes `DerivedCoreProperties.txt` to extract all ID_Start and
-//! ID_Continue code points.
-
-use std::collections::HashSet;
-
-static DERIVED_CORE_TXT: &str = include_str!("data/DerivedCoreProperties.txt");
-
-struct CodePointAndProperty {
-    code_point: u32,
-    property: &'static str,
-}
-
-struct CodePointAndPropertyIter {
-    range: std::ops::RangeInclusive<u32>,
-    property: &'static str,
+//! Processes `DerivedCoreProperties.txt` to extract the code points having
+//! each derived property: Alphabetic, Cased, ID_Start, and so on, and the
+//! Indic_Conjunct_Break value of each code point.
+
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+use std::collections::HashMap;
+
+/// The name of the file processed by this module.
+pub(crate) const DERIVED_CORE_PROPERTIES_TXT: &str = "DerivedCoreProperties.txt";
+
+/// A binary property listed in `DerivedCoreProperties.txt`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DerivedProperty {
+    Math,
+    Alphabetic,
+    Lowercase,
+    Uppercase,
+    Cased,
+    CaseIgnorable,
+    ChangesWhenLowercased,
+    ChangesWhenUppercased,
+    ChangesWhenTitlecased,
+    ChangesWhenCasefolded,
+    ChangesWhenCasemapped,
+    IdStart,
+    IdContinue,
+    XidStart,
+    XidContinue,
+    DefaultIgnorableCodePoint,
+    GraphemeExtend,
+    GraphemeBase,
+    /// Deprecated, but still listed.
+    GraphemeLink,
 }
 
-impl Iterator for CodePointAndPropertyIter {
-    type Item = CodePointAndProperty;
+use DerivedProperty::*;
+
+/// Every derived property, in the order `DerivedCoreProperties.txt` lists
+/// them, with its name in that file.
+const PROPERTIES: [(DerivedProperty, &str); 19] = [
+    (Math, "Math"),
+    (Alphabetic, "Alphabetic"),
+    (Lowercase, "Lowercase"),
+    (Uppercase, "Uppercase"),
+    (Cased, "Cased"),
+    (CaseIgnorable, "Case_Ignorable"),
+    (ChangesWhenLowercased, "Changes_When_Lowercased"),
+    (ChangesWhenUppercased, "Changes_When_Uppercased"),
+    (ChangesWhenTitlecased, "Changes_When_Titlecased"),
+    (ChangesWhenCasefolded, "Changes_When_Casefolded"),
+    (ChangesWhenCasemapped, "Changes_When_Casemapped"),
+    (IdStart, "ID_Start"),
+    (IdContinue, "ID_Continue"),
+    (XidStart, "XID_Start"),
+    (XidContinue, "XID_Continue"),
+    (DefaultIgnorableCodePoint, "Default_Ignorable_Code_Point"),
+    (GraphemeExtend, "Grapheme_Extend"),
+    (GraphemeBase, "Grapheme_Base"),
+    (GraphemeLink, "Grapheme_Link"),
+];
+
+impl DerivedProperty {
+    /// Return an iterator over every derived property.
+    pub fn all() -> impl Iterator<Item = DerivedProperty> {
+        PROPERTIES.iter().map(|(property, _)| *property)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.range.next().map(|code_point| CodePointAndProperty {
-            code_point,
-            property: self.property,
-        })
+    /// The name of this property, e.g. "Case_Ignorable".
+    pub fn name(self) -> &'static str {
+        PROPERTIES[self as usize].1
     }
-}
 
-struct DerivedCoreProperties {
-    within_range: Option<CodePointAndPropertyIter>,
-    lines: std::str::Lines<'static>,
+    /// Return the property named `name` in `DerivedCoreProperties.txt`.
+    pub fn from_name(name: &str) -> Option<DerivedProperty> {
+        PROPERTIES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(property, _)| *property)
+    }
 }
 
-impl DerivedCoreProperties {
-    fn read() -> DerivedCoreProperties {
-        DerivedCoreProperties {
-            within_range: None,
-            lines: DERIVED_CORE_TXT.lines(),
-        }
-    }
+/// The value of the Indic_Conjunct_Break (InCB) property, used by grapheme
+/// cluster rule GB9c.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IndicConjunctBreak {
+    Linker,
+    Consonant,
+    Extend,
+    None,
 }
 
-impl Iterator for DerivedCoreProperties {
-    type Item = CodePointAndProperty;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if let Some(ref mut range) = self.within_range {
-                if let Some(within_range) = range.next() {
-                    return Some(within_range);
-                }
-
-                self.within_range = None;
-            }
-
-            loop {
-                let line_with_comment = self.lines.next()?;
-                let line = line_with_comment
-                    .split('#')
-                    .nth(0)
-                    .expect("splitting returns at least one string");
-                if line.is_empty() {
-                    continue;
-                }
-
-                let mut fields = line.split(';');
-
-                let range = fields.next().expect("single code point or range").trim();
-                let property = fields.next().expect("property").trim();
-
-                if range.contains("..") {
-                    let mut nums = range.split("..");
-                    let start =
-                        u32::from_str_radix(nums.next().expect("start"), 16).expect("hex start");
-                    let end = u32::from_str_radix(nums.next().expect("end"), 16).expect("hex end");
-                    let iter = CodePointAndPropertyIter {
-                        range: start..=end,
-                        property,
-                    };
-                    self.within_range = Some(iter);
-                    break;
-                }
-
-                let code_point = u32::from_str_radix(range, 16).expect("hex code point");
-                return Some(CodePointAndProperty {
-                    code_point,
-                    property,
-                });
-            }
+impl IndicConjunctBreak {
+    /// Return the value named `name`, e.g. "Linker".
+    pub fn from_name(name: &str) -> Option<IndicConjunctBreak> {
+        match name {
+            "Linker" => Some(IndicConjunctBreak::Linker),
+            "Consonant" => Some(IndicConjunctBreak::Consonant),
+            "Extend" => Some(IndicConjunctBreak::Extend),
+            "None" => Some(IndicConjunctBreak::None),
+            _ => Option::None,
         }
     }
 }
 
-/// Computed information about select derived properties of code points.
+/// Computed information about the derived properties of code points.
 ///
 /// A derived property is one that can be indirectly computed from the contents
-/// of `UnicodeData.txt`, that for convenience's sake is separately computed and
-/// recorded in `DerivedCoreProperties.txt`.  In principle we could compute this
-/// information looping over and appropriately filtering and mapping contents of
+/// of `UnicodeData.txt` and other files, that for convenience's sake is
+/// separately computed and recorded in `DerivedCoreProperties.txt`.  In
+/// principle we could compute this information looping over and appropriately
+/// filtering and mapping contents of
 /// [`code_point_table::generate_code_point_table`](crate::code_point_table::generate_code_point_table).
 /// Debatably,
 /// it's less error-prone to parse the derived database for it.
+///
+/// # Example
+///
+/// ```
+/// # use unicode_info::derived_core_properties::*;
+/// # use unicode_info::ucd_source::UcdSource;
+/// let dcp = process_derived_core_properties(&UcdSource::Embedded).expect("embedded data");
+/// assert!(dcp.get(DerivedProperty::Cased).contains(&('A' as u32)));
+/// assert!(!dcp.get(DerivedProperty::Cased).contains(&('1' as u32)));
+/// ```
 pub struct DerivedCorePropertyData {
-    /// The set of all code points in the ID_Start category, denoting code
-    /// points that can appear at the start of an identifier.
-    ///
-    /// Note that as pertains to ECMAScript, U+0024 DOLLAR SIGN ("$") and
-    /// U+005F LOW LINE ("_")  may appear at the start of an identifier even
-    /// though they're not in the ID_Start category and aren't in this set.
-    pub id_start: HashSet<u32>,
+    /// The set of code points having each property, indexed by property.
+    sets: Vec<CodePointSet>,
 
-    /// The set of all code points in the ID_Continue category, denoting code
-    /// points that can appear within an identifier after its initial code
-    /// point.
-    ///
-    /// Note that as pertains to ECMAScript, U+0024 DOLLAR SIGN ("$") may appear
-    /// after the start of an identifier even though it's not in the ID_Start
-    /// category and isn't in this set.
-    pub id_continue: HashSet<u32>,
+    /// The Indic_Conjunct_Break value of every code point whose value isn't
+    /// `None`.  (Unicode versions before 15.1 don't list this property.)
+    indic_conjunct_break: HashMap<u32, IndicConjunctBreak>,
 }
 
-/// Generate sets containing code points within salient categories.
-pub fn process_derived_core_properties() -> DerivedCorePropertyData {
-    let mut id_start = HashSet::<u32>::new();
-    let mut id_continue = HashSet::<u32>::new();
-
-    for CodePointAndProperty {
-        code_point,
-        property,
-    } in DerivedCoreProperties::read()
-    {
-        let s = match property {
-            "ID_Start" => &mut id_start,
-            "ID_Continue" => &mut id_continue,
-            _ => {
-                continue;
-            }
-        };
-
-        s.insert(code_point);
+impl DerivedCorePropertyData {
+    /// Return the set of all code points having `property`.
+    ///
+    /// Note that as pertains to ECMAScript, U+0024 DOLLAR SIGN ("$") and
+    /// U+005F LOW LINE ("_") may appear at the start of an identifier even
+    /// though they're not ID_Start, and "$" may appear after the start of an
+    /// identifier even though it's not ID_Continue.
+    pub fn get(&self, property: DerivedProperty) -> &CodePointSet {
+        &self.sets[property as usize]
     }
 
-    DerivedCorePropertyData {
-        id_start,
-        id_continue,
+    /// Whether `code` has `property`.
+    pub fn contains(&self, property: DerivedProperty, code: u32) -> bool {
+        self.get(property).contains(&code)
+    }
+
+    /// The Indic_Conjunct_Break value of `code`.
+    pub fn indic_conjunct_break(&self, code: u32) -> IndicConjunctBreak {
+        self.indic_conjunct_break
+            .get(&code)
+            .copied()
+            .unwrap_or(IndicConjunctBreak::None)
     }
 }
 
-/// Return the Unicode version of `DerivedCoreProperties.txt`.
-pub fn unicode_version() -> &'static str {
-    // The first line looks like "# DerivedCoreProperties-13.0.0.txt".
-    let first_line = DERIVED_CORE_TXT
-        .lines()
-        .nth(0)
-        .expect("always have a first line");
+/// Generate sets containing the code points having each derived property, from
+/// the `DerivedCoreProperties.txt` in `source`.
+///
+/// Properties this crate doesn't know about, as a future Unicode version might
+/// add, are ignored.
+pub fn process_derived_core_properties(
+    source: &UcdSource,
+) -> Result<DerivedCorePropertyData, UcdError> {
+    parse_derived_core_properties(UcdFile::read(source, DERIVED_CORE_PROPERTIES_TXT)?)
+}
 
-    const PREFIX: &str = "# DerivedCoreProperties-";
-    assert!(first_line.starts_with(PREFIX));
+/// Generate sets containing the code points having each derived property, from
+/// `file`, the tokenized `DerivedCoreProperties.txt`.
+pub(crate) fn parse_derived_core_properties(
+    file: UcdFile,
+) -> Result<DerivedCorePropertyData, UcdError> {
+    let mut sets = vec![CodePointSet::new(); PROPERTIES.len()];
+    let mut indic_conjunct_break = HashMap::new();
+
+    for record in file.records() {
+        let record = record?;
+        let name = record.field(0, "a property name")?;
+        if name == "InCB" {
+            let value = record.field(1, "an Indic_Conjunct_Break value")?;
+            let value = IndicConjunctBreak::from_name(value)
+                .ok_or_else(|| record.error(value, "an Indic_Conjunct_Break value"))?;
+            indic_conjunct_break.extend(record.range.iter().map(|code| (code, value)));
+            continue;
+        }
 
-    const SUFFIX: &str = ".txt";
-    assert!(first_line.ends_with(SUFFIX));
+        if let Some(property) = DerivedProperty::from_name(name) {
+            sets[property as usize].extend(record.range);
+        }
+    }
 
-    &first_line[PREFIX.len()..first_line.len() - SUFFIX.len()]
+    Ok(DerivedCorePropertyData {
+        sets,
+        indic_conjunct_break,
+    })
 }
 
 #[test]
 fn check_derived_core_properties() {
-    let dcp = process_derived_core_properties();
+    let dcp = process_derived_core_properties(&UcdSource::Embedded).expect("embedded data");
 
-    let starts = dcp.id_start;
+    let starts = dcp.get(IdStart);
     let starts_count = starts.len();
 
     const DOLLAR_SIGN: u32 = '$' as u32;
@@ -176,7 +213,7 @@ fn check_derived_core_properties() {
     assert!(!starts.contains(&DOLLAR_SIGN));
     assert!(!starts.contains(&LOW_LINE));
 
-    let continues = dcp.id_continue;
+    let continues = dcp.get(IdContinue);
     let continues_count = continues.len();
 
     assert!(!continues.contains(&DOLLAR_SIGN));
@@ -187,3 +224,70 @@ fn check_derived_core_properties() {
     assert_eq!(starts_count, 131_482);
     assert_eq!(continues_count, 134_434);
 }
+
+#[test]
+fn check_all_derived_properties() {
+    for (i, property) in DerivedProperty::all().enumerate() {
+        assert_eq!(property as usize, i, "PROPERTIES is in declaration order");
+        assert_eq!(DerivedProperty::from_name(property.name()), Some(property));
+    }
+
+    let dcp = process_derived_core_properties(&UcdSource::Embedded).expect("embedded data");
+
+    // Counts from the comments after respective sections in
+    // DerivedCoreProperties.txt, in the order of `PROPERTIES`.
+    let counts = [
+        2_310, 132_875, 2_344, 1_911, 4_286, 2_413, 1_393, 1_485, 1_412, 1_466, 2_847, 131_482,
+        134_434, 131_459, 134_415, 4_173, 1_979, 141_814, 61,
+    ];
+    for (property, count) in DerivedProperty::all().zip(counts.iter()) {
+        assert_eq!(dcp.get(property).len(), *count, "{:?}", property);
+    }
+
+    const CAPITAL_SIGMA: u32 = 0x03A3;
+    const COMBINING_ACUTE_ACCENT: u32 = 0x0301;
+    assert!(dcp.contains(Uppercase, CAPITAL_SIGMA));
+    assert!(dcp.contains(ChangesWhenLowercased, CAPITAL_SIGMA));
+    assert!(!dcp.contains(ChangesWhenUppercased, CAPITAL_SIGMA));
+    assert!(dcp.contains(CaseIgnorable, COMBINING_ACUTE_ACCENT));
+    assert!(dcp.contains(GraphemeExtend, COMBINING_ACUTE_ACCENT));
+    assert!(!dcp.contains(Alphabetic, COMBINING_ACUTE_ACCENT));
+
+    // Unicode 13.0 predates Indic_Conjunct_Break.
+    assert_eq!(dcp.indic_conjunct_break(0x094D), IndicConjunctBreak::None);
+}
+
+#[test]
+fn check_indic_conjunct_break() {
+    let file = UcdFile::new(
+        DERIVED_CORE_PROPERTIES_TXT,
+        "# DerivedCoreProperties-15.1.0.txt\n\
+         0915..0939    ; InCB; Consonant # Lo  [37] DEVANAGARI LETTER KA..DEVANAGARI LETTER HA\n\
+         094D          ; InCB; Linker # Mn       DEVANAGARI SIGN VIRAMA\n\
+         0300..034E    ; InCB; Extend # Mn  [79] COMBINING GRAVE ACCENT..\n\
+         0041..005A    ; Cased # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n\
+         0041          ; Some_Future_Property # L       LATIN CAPITAL LETTER A\n",
+    );
+
+    let dcp = parse_derived_core_properties(file).expect("DerivedCoreProperties.txt");
+    assert_eq!(
+        dcp.indic_conjunct_break(0x0915),
+        IndicConjunctBreak::Consonant
+    );
+    assert_eq!(dcp.indic_conjunct_break(0x094D), IndicConjunctBreak::Linker);
+    assert_eq!(dcp.indic_conjunct_break(0x0301), IndicConjunctBreak::Extend);
+    assert_eq!(dcp.indic_conjunct_break(0x0041), IndicConjunctBreak::None);
+    assert_eq!(dcp.get(Cased).len(), 26);
+
+    let file = UcdFile::new(
+        DERIVED_CORE_PROPERTIES_TXT,
+        "094D ; InCB; Joiner # Mn DEVANAGARI SIGN VIRAMA\n",
+    );
+    let error = parse_derived_core_properties(file)
+        .err()
+        .expect("bad InCB value");
+    assert_eq!(
+        error.to_string(),
+        "DerivedCoreProperties.txt:1: expected an Indic_Conjunct_Break value, found \"Joiner\""
+    );
+}
@@ -0,0 +1,159 @@
+//! Processes `EastAsianWidth.txt` to determine the East_Asian_Width property
+//! of code points, as used to compute the column width of text in a console.
+//!
+//! `EastAsianWidth.txt` isn't embedded in this crate, so it must be read from
+//! a [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr11/> for details.
+
+use crate::code_point_table::CodePoint;
+use crate::enumerated_property::EnumeratedProperty;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+
+/// The name of the file processed by this module.
+pub(crate) const EAST_ASIAN_WIDTH_TXT: &str = "EastAsianWidth.txt";
+
+/// The East_Asian_Width value of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EastAsianWidth {
+    Ambiguous,
+    Fullwidth,
+    Halfwidth,
+    Neutral,
+    Narrow,
+    Wide,
+}
+
+use EastAsianWidth::*;
+
+/// Every East_Asian_Width value with its short and long names.
+const WIDTHS: [(EastAsianWidth, &str, &str); 6] = [
+    (Ambiguous, "A", "Ambiguous"),
+    (Fullwidth, "F", "Fullwidth"),
+    (Halfwidth, "H", "Halfwidth"),
+    (Neutral, "N", "Neutral"),
+    (Narrow, "Na", "Narrow"),
+    (Wide, "W", "Wide"),
+];
+
+impl EastAsianWidth {
+    /// Return an iterator over every East_Asian_Width value.
+    pub fn all() -> impl Iterator<Item = EastAsianWidth> {
+        WIDTHS.iter().map(|(width, _, _)| *width)
+    }
+
+    /// The abbreviated name of this value, e.g. "Na".
+    pub fn short_name(self) -> &'static str {
+        WIDTHS[self as usize].1
+    }
+
+    /// The long name of this value, e.g. "Narrow".
+    pub fn long_name(self) -> &'static str {
+        WIDTHS[self as usize].2
+    }
+
+    /// Return the value with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<EastAsianWidth> {
+        WIDTHS
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(width, _, _)| *width)
+    }
+
+    /// Whether characters with this width occupy two columns in East Asian
+    /// contexts.  (Ambiguous characters occupy two columns only in legacy East
+    /// Asian contexts, so they aren't considered wide.)
+    pub fn is_wide(self) -> bool {
+        self == Fullwidth || self == Wide
+    }
+}
+
+/// The East_Asian_Width of every code point.
+pub struct EastAsianWidthData {
+    /// The width of every code point, from `EastAsianWidth.txt` and its
+    /// `@missing` lines.
+    widths: EnumeratedProperty<EastAsianWidth>,
+}
+
+impl EastAsianWidthData {
+    /// The East_Asian_Width of `code`.
+    pub fn width(&self, code: u32) -> EastAsianWidth {
+        self.widths.get(code)
+    }
+}
+
+impl CodePoint {
+    /// The East_Asian_Width of this code point.
+    pub fn east_asian_width(&self, widths: &EastAsianWidthData) -> EastAsianWidth {
+        widths.width(self.code)
+    }
+}
+
+/// Parse the East_Asian_Width of every code point from the
+/// `EastAsianWidth.txt` in `source`.
+pub fn process_east_asian_width(source: &UcdSource) -> Result<EastAsianWidthData, UcdError> {
+    parse_east_asian_width(UcdFile::read(source, EAST_ASIAN_WIDTH_TXT)?)
+}
+
+pub(crate) fn parse_east_asian_width(file: UcdFile) -> Result<EastAsianWidthData, UcdError> {
+    Ok(EastAsianWidthData {
+        widths: EnumeratedProperty::parse(
+            file,
+            "an East_Asian_Width value",
+            EastAsianWidth::from_name,
+            Neutral,
+        )?,
+    })
+}
+
+#[test]
+fn check_east_asian_width() {
+    for (i, width) in EastAsianWidth::all().enumerate() {
+        assert_eq!(width as usize, i, "WIDTHS is in declaration order");
+        assert_eq!(EastAsianWidth::from_name(width.short_name()), Some(width));
+        assert_eq!(EastAsianWidth::from_name(width.long_name()), Some(width));
+    }
+
+    let file = UcdFile::new(
+        EAST_ASIAN_WIDTH_TXT,
+        "# EastAsianWidth-16.0.0.txt\n\
+         # @missing: 0000..10FFFF; N\n\
+         # @missing: 3400..4DBF; W\n\
+         # @missing: 4E00..9FFF; W\n\
+         0020..007E     ; Na # Zs    [95] SPACE..TILDE\n\
+         00A1           ; A  # Po         INVERTED EXCLAMATION MARK\n\
+         3000           ; F  # Zs         IDEOGRAPHIC SPACE\n\
+         4E00..9FFF     ; W  # Lo [20992] CJK UNIFIED IDEOGRAPH-4E00..CJK UNIFIED IDEOGRAPH-9FFF\n\
+         FF61           ; H  # Po         HALFWIDTH IDEOGRAPHIC FULL STOP\n",
+    );
+
+    let widths = parse_east_asian_width(file).expect("East_Asian_Width data");
+    assert_eq!(widths.width('A' as u32), Narrow);
+    assert_eq!(widths.width(0xA1), Ambiguous);
+    assert_eq!(widths.width(0x3000), Fullwidth);
+    assert_eq!(widths.width(0x4E00), Wide);
+    assert_eq!(widths.width(0xFF61), Halfwidth);
+    assert_eq!(widths.width(0x0300), Neutral);
+    // Unassigned code points in CJK blocks default to Wide.
+    assert_eq!(widths.width(0x4DBF), Wide);
+    assert!(widths.width(0x3000).is_wide());
+    assert!(!widths.width(0xA1).is_wide());
+
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    let ideograph = table.get(0x4E00).expect("U+4E00");
+    assert_eq!(ideograph.east_asian_width(&widths), Wide);
+
+    let error = parse_east_asian_width(UcdFile::new(
+        EAST_ASIAN_WIDTH_TXT,
+        "0020 ; Narrowish # SPACE\n",
+    ))
+    .err()
+    .expect("bad width");
+    assert_eq!(
+        error.to_string(),
+        "EastAsianWidth.txt:1: expected an East_Asian_Width value, found \"Narrowish\""
+    );
+}
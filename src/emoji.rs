@@ -0,0 +1,133 @@
+//! Processes `emoji/emoji-data.txt` to extract the code points having each
+//! emoji property: Emoji, Emoji_Presentation, Emoji_Modifier,
+//! Emoji_Modifier_Base, Emoji_Component, and Extended_Pictographic.
+//!
+//! `emoji-data.txt` isn't embedded in this crate, so it must be read from a
+//! [`UcdSource::Directory`].  Its version is checked against the other UCD
+//! files by [`check_unicode_version`](crate::unicode_version::check_unicode_version).
+//!
+//! See <https://www.unicode.org/reports/tr51/#Emoji_Properties> for details.
+
+use crate::prop_list;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+
+/// The name of the file processed by this module, relative to the UCD
+/// directory.
+pub(crate) const EMOJI_DATA_TXT: &str = "emoji/emoji-data.txt";
+
+/// A binary property listed in `emoji-data.txt`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EmojiProperty {
+    Emoji,
+    EmojiPresentation,
+    EmojiModifier,
+    EmojiModifierBase,
+    EmojiComponent,
+    ExtendedPictographic,
+}
+
+use EmojiProperty::*;
+
+/// Every emoji property, in the order `emoji-data.txt` lists them, with its
+/// name in that file.
+const PROPERTIES: [(EmojiProperty, &str); 6] = [
+    (Emoji, "Emoji"),
+    (EmojiPresentation, "Emoji_Presentation"),
+    (EmojiModifier, "Emoji_Modifier"),
+    (EmojiModifierBase, "Emoji_Modifier_Base"),
+    (EmojiComponent, "Emoji_Component"),
+    (ExtendedPictographic, "Extended_Pictographic"),
+];
+
+impl EmojiProperty {
+    /// Return an iterator over every emoji property.
+    pub fn all() -> impl Iterator<Item = EmojiProperty> {
+        PROPERTIES.iter().map(|(property, _)| *property)
+    }
+
+    /// The name of this property, e.g. "Extended_Pictographic".
+    pub fn name(self) -> &'static str {
+        PROPERTIES[self as usize].1
+    }
+
+    /// Return the property named `name` in `emoji-data.txt`.
+    pub fn from_name(name: &str) -> Option<EmojiProperty> {
+        PROPERTIES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(property, _)| *property)
+    }
+}
+
+/// The code points having each emoji property.
+pub struct EmojiData {
+    /// The set of code points having each property, indexed by property.
+    sets: Vec<CodePointSet>,
+}
+
+impl EmojiData {
+    /// Return the set of all code points having `property`.
+    pub fn get(&self, property: EmojiProperty) -> &CodePointSet {
+        &self.sets[property as usize]
+    }
+
+    /// Whether `code` has `property`.
+    pub fn contains(&self, property: EmojiProperty, code: u32) -> bool {
+        self.get(property).contains(&code)
+    }
+}
+
+/// Generate the set of code points having each emoji property, from the
+/// `emoji/emoji-data.txt` in `source`.
+///
+/// Properties this crate doesn't know about are ignored.
+pub fn process_emoji_data(source: &UcdSource) -> Result<EmojiData, UcdError> {
+    parse_emoji_data(UcdFile::read(source, EMOJI_DATA_TXT)?)
+}
+
+/// Generate the set of code points having each emoji property, from `file`,
+/// the tokenized `emoji/emoji-data.txt`.
+pub(crate) fn parse_emoji_data(file: UcdFile) -> Result<EmojiData, UcdError> {
+    let mut properties = prop_list::parse_binary_properties(file)?;
+    let sets = EmojiProperty::all()
+        .map(|property| properties.remove(property.name()).unwrap_or_default())
+        .collect();
+    Ok(EmojiData { sets })
+}
+
+#[test]
+fn check_emoji_data() {
+    for (i, property) in EmojiProperty::all().enumerate() {
+        assert_eq!(property as usize, i, "PROPERTIES is in declaration order");
+        assert_eq!(EmojiProperty::from_name(property.name()), Some(property));
+    }
+
+    let file = UcdFile::new(
+        EMOJI_DATA_TXT,
+        "# emoji-data.txt\n\
+         # Emoji Data for UTS #51\n\
+         # Version: 13.0\n\
+         0023          ; Emoji                # E0.0   [1] (#\u{fe0f})       number sign\n\
+         1F600         ; Emoji                # E1.0   [1] (\u{1f600})       grinning face\n\
+         1F600         ; Emoji_Presentation   # E1.0   [1] (\u{1f600})       grinning face\n\
+         1F3FB..1F3FF  ; Emoji_Modifier       # E1.0   [5] (\u{1f3fb}..\u{1f3ff})    light skin tone..dark skin tone\n\
+         261D          ; Emoji_Modifier_Base  # E0.6   [1] (\u{261d}\u{fe0f})       index pointing up\n\
+         0023          ; Emoji_Component      # E0.0   [1] (#\u{fe0f})       number sign\n\
+         1F3FB..1F3FF  ; Emoji_Component      # E1.0   [5] (\u{1f3fb}..\u{1f3ff})    light skin tone..dark skin tone\n\
+         00A9          ; Extended_Pictographic# E0.6   [1] (\u{a9}\u{fe0f})       copyright\n\
+         1FC00..1FFFD  ; Extended_Pictographic# E0.0[1022] (\u{1fc00}..\u{1fffd}) <reserved-1FC00>..<reserved-1FFFD>\n",
+    );
+
+    let emoji = parse_emoji_data(file).expect("emoji data");
+    assert_eq!(emoji.get(Emoji).len(), 2);
+    assert!(emoji.contains(Emoji, '#' as u32));
+    assert!(!emoji.contains(EmojiPresentation, '#' as u32));
+    assert!(emoji.contains(EmojiPresentation, 0x1F600));
+    assert_eq!(emoji.get(EmojiModifier).len(), 5);
+    assert!(emoji.contains(EmojiModifierBase, 0x261D));
+    assert_eq!(emoji.get(EmojiComponent).len(), 6);
+    assert_eq!(emoji.get(ExtendedPictographic).len(), 1_023);
+}
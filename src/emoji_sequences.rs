@@ -0,0 +1,219 @@
+//! Processes `emoji-sequences.txt` and `emoji-zwj-sequences.txt` to compute
+//! the properties of strings that RegExp `v`-flag property escapes support:
+//! Basic_Emoji, Emoji_Keycap_Sequence, RGI_Emoji_Modifier_Sequence,
+//! RGI_Emoji_Flag_Sequence, RGI_Emoji_Tag_Sequence, RGI_Emoji_ZWJ_Sequence,
+//! and their union RGI_Emoji.
+//!
+//! These files are published with emoji data (in `Public/emoji/<version>`)
+//! rather than in the UCD proper, and aren't embedded in this crate.  They're
+//! read from the `emoji` subdirectory of a [`UcdSource::Directory`], alongside
+//! `emoji-data.txt`.
+//!
+//! See <https://www.unicode.org/reports/tr51/#Emoji_Sets> for details.
+
+use crate::types::StringSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{CodePointRange, DataLines};
+use crate::ucd_source::UcdSource;
+
+/// The name of the file listing every emoji sequence other than ZWJ
+/// sequences, relative to the UCD directory.
+pub(crate) const EMOJI_SEQUENCES_TXT: &str = "emoji/emoji-sequences.txt";
+
+/// The name of the file listing RGI emoji ZWJ sequences, relative to the UCD
+/// directory.
+pub(crate) const EMOJI_ZWJ_SEQUENCES_TXT: &str = "emoji/emoji-zwj-sequences.txt";
+
+/// A property of strings.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StringProperty {
+    BasicEmoji,
+    EmojiKeycapSequence,
+    RgiEmojiModifierSequence,
+    RgiEmojiFlagSequence,
+    RgiEmojiTagSequence,
+    RgiEmojiZwjSequence,
+    /// The union of every other property of strings.
+    RgiEmoji,
+}
+
+use StringProperty::*;
+
+/// Every property of strings with its name.
+const PROPERTIES: [(StringProperty, &str); 7] = [
+    (BasicEmoji, "Basic_Emoji"),
+    (EmojiKeycapSequence, "Emoji_Keycap_Sequence"),
+    (RgiEmojiModifierSequence, "RGI_Emoji_Modifier_Sequence"),
+    (RgiEmojiFlagSequence, "RGI_Emoji_Flag_Sequence"),
+    (RgiEmojiTagSequence, "RGI_Emoji_Tag_Sequence"),
+    (RgiEmojiZwjSequence, "RGI_Emoji_ZWJ_Sequence"),
+    (RgiEmoji, "RGI_Emoji"),
+];
+
+impl StringProperty {
+    /// Return an iterator over every property of strings.
+    pub fn all() -> impl Iterator<Item = StringProperty> {
+        PROPERTIES.iter().map(|(property, _)| *property)
+    }
+
+    /// The name of this property, e.g. "RGI_Emoji_Flag_Sequence".
+    pub fn name(self) -> &'static str {
+        PROPERTIES[self as usize].1
+    }
+
+    /// Return the property named `name`.
+    pub fn from_name(name: &str) -> Option<StringProperty> {
+        PROPERTIES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(property, _)| *property)
+    }
+}
+
+/// The strings having each property of strings.
+pub struct EmojiSequenceData {
+    /// The strings having each property, indexed by property.
+    sets: Vec<StringSet>,
+}
+
+impl EmojiSequenceData {
+    /// Return the set of all strings having `property`.
+    pub fn get(&self, property: StringProperty) -> &StringSet {
+        &self.sets[property as usize]
+    }
+}
+
+/// Add the sequences listed in `lines`, the data lines of the file named
+/// `file`, to `sets`.
+///
+/// Lines consist of a code point range (each code point in which is a
+/// single-code-point string) or a space-separated code point sequence, a
+/// property name, and a description, e.g.
+/// `1F1E6 1F1E8 ; RGI_Emoji_Flag_Sequence ; flag: Ascension Island # E2.0`.
+fn parse_sequences(
+    file: &'static str,
+    lines: DataLines,
+    sets: &mut [StringSet],
+) -> Result<(), UcdError> {
+    for line in lines {
+        let error = |text: &str, expected| UcdError::parse(file, line.line, text, expected);
+
+        let name = line
+            .fields
+            .get(1)
+            .copied()
+            .ok_or_else(|| error(&line.fields.join("; "), "a property name"))?;
+        let property = match StringProperty::from_name(name) {
+            Some(property) if property != RgiEmoji => property,
+            _ => return Err(error(name, "an emoji sequence property name")),
+        };
+        let set = &mut sets[property as usize];
+
+        let codes = line.fields[0];
+        if codes.contains("..") {
+            let range = CodePointRange::parse(codes)
+                .ok_or_else(|| error(codes, "a hexadecimal code point range"))?;
+            for code in range {
+                set.insert(vec![code]);
+            }
+        } else {
+            let string = codes
+                .split_whitespace()
+                .map(|code| u32::from_str_radix(code, 16))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| error(codes, "a sequence of hexadecimal code points"))?;
+            if string.is_empty() {
+                return Err(error(codes, "a sequence of hexadecimal code points"));
+            }
+            set.insert(string);
+        }
+    }
+    Ok(())
+}
+
+/// Generate the set of strings having each property of strings, from the
+/// `emoji/emoji-sequences.txt` and `emoji/emoji-zwj-sequences.txt` in
+/// `source`.
+pub fn process_emoji_sequences(source: &UcdSource) -> Result<EmojiSequenceData, UcdError> {
+    parse_emoji_sequences(
+        DataLines::new(source.read(EMOJI_SEQUENCES_TXT)?),
+        DataLines::new(source.read(EMOJI_ZWJ_SEQUENCES_TXT)?),
+    )
+}
+
+/// Generate the set of strings having each property of strings, from
+/// `sequence_lines` and `zwj_sequence_lines`, the data lines of
+/// `emoji/emoji-sequences.txt` and `emoji/emoji-zwj-sequences.txt`.
+fn parse_emoji_sequences(
+    sequence_lines: DataLines,
+    zwj_sequence_lines: DataLines,
+) -> Result<EmojiSequenceData, UcdError> {
+    let mut sets = vec![StringSet::new(); PROPERTIES.len()];
+    parse_sequences(EMOJI_SEQUENCES_TXT, sequence_lines, &mut sets)?;
+    parse_sequences(EMOJI_ZWJ_SEQUENCES_TXT, zwj_sequence_lines, &mut sets)?;
+
+    let mut rgi_emoji = StringSet::new();
+    for set in &sets {
+        rgi_emoji.extend(set);
+    }
+    sets[RgiEmoji as usize] = rgi_emoji;
+
+    Ok(EmojiSequenceData { sets })
+}
+
+#[test]
+fn check_emoji_sequences() {
+    for (i, property) in StringProperty::all().enumerate() {
+        assert_eq!(property as usize, i, "PROPERTIES is in declaration order");
+        assert_eq!(StringProperty::from_name(property.name()), Some(property));
+    }
+
+    let sequence_lines = DataLines::new(
+        "# emoji-sequences.txt\n\
+         # Version: 13.0\n\
+         231A..231B    ; Basic_Emoji                  ; watch                                                          # E0.6   [2] (\u{231a}..\u{231b})\n\
+         2648 FE0F     ; Basic_Emoji                  ; Aries                                                          # E0.6   [1] (\u{2648}\u{fe0f})\n\
+         0023 FE0F 20E3; Emoji_Keycap_Sequence        ; keycap: \\x{23}                                                 # E0.6   [1] (#\u{fe0f}\u{20e3})\n\
+         1F1E6 1F1E8   ; RGI_Emoji_Flag_Sequence      ; flag: Ascension Island                                         # E2.0   [1] (\u{1f1e6}\u{1f1e8})\n\
+         261D 1F3FB    ; RGI_Emoji_Modifier_Sequence  ; index pointing up: light skin tone                             # E1.0   [1] (\u{261d}\u{1f3fb})\n\
+         1F3F4 E0067 E0062 E0065 E006E E0067 E007F; RGI_Emoji_Tag_Sequence; flag: England                               # E5.0   [1] (\u{1f3f4}\u{e0067}\u{e0062}\u{e0065}\u{e006e}\u{e0067}\u{e007f})\n",
+    );
+    let zwj_sequence_lines = DataLines::new(
+        "# emoji-zwj-sequences.txt\n\
+         # Version: 13.0\n\
+         1F468 200D 2764 FE0F 200D 1F468             ; RGI_Emoji_ZWJ_Sequence  ; couple with heart: man, man                                    # E2.0   [1] (\u{1f468}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f468})\n",
+    );
+
+    let sequences =
+        parse_emoji_sequences(sequence_lines, zwj_sequence_lines).expect("emoji sequences");
+    let basic_emoji = sequences.get(BasicEmoji);
+    assert_eq!(basic_emoji.len(), 3);
+    assert_eq!(basic_emoji.code_points().len(), 2);
+    assert!(basic_emoji.contains(&[0x231A]));
+    assert!(basic_emoji.contains(&[0x2648, 0xFE0F]));
+    assert_eq!(
+        basic_emoji.multi_code_point_strings().collect::<Vec<_>>(),
+        [&[0x2648, 0xFE0F]]
+    );
+    assert!(sequences
+        .get(EmojiKeycapSequence)
+        .contains(&[0x23, 0xFE0F, 0x20E3]));
+    assert_eq!(sequences.get(RgiEmojiTagSequence).len(), 1);
+    assert!(sequences
+        .get(RgiEmojiZwjSequence)
+        .contains(&[0x1F468, 0x200D, 0x2764, 0xFE0F, 0x200D, 0x1F468]));
+    assert_eq!(sequences.get(RgiEmoji).len(), 8);
+    assert_eq!(sequences.get(RgiEmoji).code_points().len(), 2);
+
+    let error = parse_emoji_sequences(
+        DataLines::new(""),
+        DataLines::new("1F468 200D 1F468 ; RGI_Emoji ; man, man\n"),
+    )
+    .err()
+    .expect("RGI_Emoji isn't listed");
+    assert_eq!(
+        error.to_string(),
+        "emoji/emoji-zwj-sequences.txt:1: expected an emoji sequence property name, \
+         found \"RGI_Emoji\""
+    );
+}
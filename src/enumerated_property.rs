@@ -0,0 +1,245 @@
+//! The values of an enumerated property listed in a UCD file whose records
+//! consist of a code point range and a value name, e.g. `Scripts.txt`:
+//!
+//! ```text
+//! # @missing: 0000..10FFFF; Unknown
+//! 0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z
+//! ```
+//!
+//! Code points the file doesn't list have the value given by its `@missing`
+//! lines, or a property-specific default if no `@missing` line covers them.
+//!
+//! # Example
+//!
+//! ```
+//! # use unicode_info::enumerated_property::EnumeratedProperty;
+//! # use unicode_info::ucd_file::UcdFile;
+//! let file = UcdFile::new(
+//!     "Example.txt",
+//!     "# @missing: 0590..05FF; R\n\
+//!      05D0..05EA ; R\n\
+//!      0041..005A ; L\n",
+//! );
+//! let property = EnumeratedProperty::parse(file, "a value", |name| Some(name), "L")
+//!     .expect("well-formed");
+//! assert_eq!(property.get('Q' as u32), "L");
+//! assert_eq!(property.get(0x05FF), "R");
+//! assert_eq!(property.code_points("R").len(), 0x70);
+//! ```
+
+use crate::constants::MAX_CODE_POINT;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{CodePointRange, Entry, Record, UcdFile};
+
+/// The value of an enumerated property for every code point.
+#[derive(Clone, Debug)]
+pub struct EnumeratedProperty<T> {
+    /// Every range listed in the file and its value, in increasing order of
+    /// code points.
+    ranges: Vec<(CodePointRange, T)>,
+
+    /// The value of code points not in `ranges`: disjoint ranges, in
+    /// increasing order, covering every code point.  Computed from the file's
+    /// `@missing` lines, of which later lines take precedence.
+    defaults: Vec<(CodePointRange, T)>,
+}
+
+impl<T: Copy + PartialEq> EnumeratedProperty<T> {
+    /// Parse the value of every code point from `file`.  `from_name` converts
+    /// a value name into a value, or returns `None` if the name isn't one of
+    /// the values described by `expected`, e.g. "a script name".  Code points
+    /// that neither records nor `@missing` lines cover have the value
+    /// `default`.
+    pub fn parse(
+        file: UcdFile,
+        expected: &str,
+        from_name: impl Fn(&'static str) -> Option<T>,
+        default: T,
+    ) -> Result<EnumeratedProperty<T>, UcdError> {
+        let parse_value = |record: &Record| {
+            let name = record.field(0, expected)?;
+            from_name(name).ok_or_else(|| record.error(name, expected))
+        };
+
+        let mut ranges = vec![];
+        let mut defaults = vec![(
+            CodePointRange {
+                first: 0,
+                last: MAX_CODE_POINT,
+            },
+            default,
+        )];
+        for entry in file {
+            match entry? {
+                Entry::Record(record) => ranges.push((record.range, parse_value(&record)?)),
+                Entry::Missing(record) => {
+                    overwrite(&mut defaults, record.range, parse_value(&record)?);
+                }
+            }
+        }
+        ranges.sort_by_key(|(range, _)| range.first);
+
+        Ok(EnumeratedProperty { ranges, defaults })
+    }
+
+    /// The value of `code`.
+    pub fn get(&self, code: u32) -> T {
+        find(&self.ranges, code)
+            .or_else(|| find(&self.defaults, code))
+            .expect("defaults cover every code point")
+    }
+
+    /// Return an iterator over every range listed in the file and its value,
+    /// in increasing order of code points.
+    pub fn ranges(&self) -> impl Iterator<Item = (CodePointRange, T)> + '_ {
+        self.ranges.iter().copied()
+    }
+
+    /// Return the set of every code point whose value is `value`.
+    pub fn code_points(&self, value: T) -> CodePointSet {
+        let mut set = CodePointSet::new();
+        for (range, _) in self.ranges.iter().filter(|(_, v)| *v == value) {
+            set.extend(range.iter());
+        }
+
+        // Add the unlisted code points in each default range with `value`: the
+        // gaps between listed ranges, clipped to the default range.
+        for (default_range, _) in self.defaults.iter().filter(|(_, v)| *v == value) {
+            let mut next = default_range.first;
+            let start = self
+                .ranges
+                .partition_point(|(range, _)| range.last < default_range.first);
+            for (range, _) in &self.ranges[start..] {
+                if range.first > default_range.last {
+                    break;
+                }
+                if range.first > next {
+                    set.extend(next..range.first);
+                }
+                next = next.max(range.last.saturating_add(1));
+            }
+            if next <= default_range.last {
+                set.extend(next..=default_range.last);
+            }
+        }
+
+        set
+    }
+}
+
+/// Return the value of the range containing `code` in `ranges`, which are
+/// disjoint and in increasing order.
+fn find<T: Copy>(ranges: &[(CodePointRange, T)], code: u32) -> Option<T> {
+    let i = ranges.partition_point(|(range, _)| range.last < code);
+    ranges
+        .get(i)
+        .filter(|(range, _)| range.contains(code))
+        .map(|(_, value)| *value)
+}
+
+/// Set the value of every code point in `range` to `value` in `ranges`, which
+/// are disjoint, in increasing order, and cover every code point.
+fn overwrite<T: Copy>(ranges: &mut Vec<(CodePointRange, T)>, range: CodePointRange, value: T) {
+    let mut result = Vec::with_capacity(ranges.len() + 2);
+    for &(existing, existing_value) in ranges.iter() {
+        if existing.last < range.first || existing.first > range.last {
+            result.push((existing, existing_value));
+            continue;
+        }
+        if existing.first < range.first {
+            result.push((
+                CodePointRange {
+                    first: existing.first,
+                    last: range.first - 1,
+                },
+                existing_value,
+            ));
+        }
+        if existing.first <= range.first {
+            result.push((range, value));
+        }
+        if existing.last > range.last {
+            result.push((
+                CodePointRange {
+                    first: range.last + 1,
+                    last: existing.last,
+                },
+                existing_value,
+            ));
+        }
+    }
+    *ranges = result;
+}
+
+#[test]
+fn check_enumerated_property() {
+    let file = UcdFile::new(
+        "Example.txt",
+        "# Example-1.0.0.txt\n\
+         # @missing: 0000..10FFFF; Unknown\n\
+         # @missing: 0590..08FF; R\n\
+         # @missing: 0600..07BF; AL\n\
+         05D0..05EA ; R\n\
+         0041..005A ; L\n\
+         0600..0604 ; AN\n\
+         0620..064A ; AL\n",
+    );
+    let property =
+        EnumeratedProperty::parse(file, "a value", Some, "Default").expect("Example.txt");
+    assert_eq!(property.get('A' as u32), "L");
+    assert_eq!(property.get('a' as u32), "Unknown");
+    assert_eq!(property.get(0x05D0), "R");
+    assert_eq!(property.get(0x0590), "R");
+    assert_eq!(property.get(0x0600), "AN");
+    assert_eq!(property.get(0x0605), "AL");
+    assert_eq!(property.get(0x07C0), "R");
+    assert_eq!(property.get(0x0900), "Unknown");
+    assert_eq!(property.get(0x10FFFF), "Unknown");
+    assert_eq!(
+        property
+            .ranges()
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>(),
+        ["L", "R", "AN", "AL"]
+    );
+
+    assert_eq!(property.code_points("L").len(), 26);
+    assert_eq!(property.code_points("AN").len(), 5);
+    let al = property.code_points("AL");
+    assert_eq!(al.len(), 0x07BF - 0x0605 + 1);
+    assert!(al.contains(&0x0605) && al.contains(&0x064B) && !al.contains(&0x07C0));
+    let r = property.code_points("R");
+    assert_eq!(r.len(), (0x05FF - 0x0590 + 1) + (0x08FF - 0x07C0 + 1));
+    assert_eq!(
+        property.code_points("Unknown").len() as u32,
+        MAX_CODE_POINT + 1 - 0x0370 - 26
+    );
+    assert!(property.code_points("Default").is_empty());
+
+    // Without @missing lines, unlisted code points have the default value.
+    let property = EnumeratedProperty::parse(
+        UcdFile::new("Example.txt", "0041..005A ; L\n"),
+        "a value",
+        Some,
+        "Default",
+    )
+    .expect("Example.txt");
+    assert_eq!(property.get('a' as u32), "Default");
+    assert_eq!(
+        property.code_points("Default").len() as u32,
+        MAX_CODE_POINT + 1 - 26
+    );
+
+    let error = EnumeratedProperty::parse(
+        UcdFile::new("Example.txt", "0041..005A ; L\n0061 ; Bogus\n"),
+        "a value",
+        |name| Some(name).filter(|name| *name == "L"),
+        "L",
+    )
+    .expect_err("unknown value");
+    assert_eq!(
+        error.to_string(),
+        "Example.txt:2: expected a value, found \"Bogus\""
+    );
+}
@@ -0,0 +1,300 @@
+//! The General_Category property of code points, and the groupings of general
+//! categories (Letter, Mark, and so on) that Unicode derives from it.
+//!
+//! See <https://www.unicode.org/reports/tr44/#General_Category_Values> for
+//! details.
+
+/// The general category of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GeneralCategory {
+    UppercaseLetter,
+    LowercaseLetter,
+    TitlecaseLetter,
+    ModifierLetter,
+    OtherLetter,
+    NonspacingMark,
+    SpacingMark,
+    EnclosingMark,
+    DecimalNumber,
+    LetterNumber,
+    OtherNumber,
+    ConnectorPunctuation,
+    DashPunctuation,
+    OpenPunctuation,
+    ClosePunctuation,
+    InitialPunctuation,
+    FinalPunctuation,
+    OtherPunctuation,
+    MathSymbol,
+    CurrencySymbol,
+    ModifierSymbol,
+    OtherSymbol,
+    SpaceSeparator,
+    LineSeparator,
+    ParagraphSeparator,
+    Control,
+    Format,
+    Surrogate,
+    PrivateUse,
+    Unassigned,
+}
+
+use GeneralCategory::*;
+
+/// Every general category, in the order `PropertyValueAliases.txt` lists them,
+/// with its short and long names.
+const CATEGORIES: [(GeneralCategory, &str, &str); 30] = [
+    (UppercaseLetter, "Lu", "Uppercase_Letter"),
+    (LowercaseLetter, "Ll", "Lowercase_Letter"),
+    (TitlecaseLetter, "Lt", "Titlecase_Letter"),
+    (ModifierLetter, "Lm", "Modifier_Letter"),
+    (OtherLetter, "Lo", "Other_Letter"),
+    (NonspacingMark, "Mn", "Nonspacing_Mark"),
+    (SpacingMark, "Mc", "Spacing_Mark"),
+    (EnclosingMark, "Me", "Enclosing_Mark"),
+    (DecimalNumber, "Nd", "Decimal_Number"),
+    (LetterNumber, "Nl", "Letter_Number"),
+    (OtherNumber, "No", "Other_Number"),
+    (ConnectorPunctuation, "Pc", "Connector_Punctuation"),
+    (DashPunctuation, "Pd", "Dash_Punctuation"),
+    (OpenPunctuation, "Ps", "Open_Punctuation"),
+    (ClosePunctuation, "Pe", "Close_Punctuation"),
+    (InitialPunctuation, "Pi", "Initial_Punctuation"),
+    (FinalPunctuation, "Pf", "Final_Punctuation"),
+    (OtherPunctuation, "Po", "Other_Punctuation"),
+    (MathSymbol, "Sm", "Math_Symbol"),
+    (CurrencySymbol, "Sc", "Currency_Symbol"),
+    (ModifierSymbol, "Sk", "Modifier_Symbol"),
+    (OtherSymbol, "So", "Other_Symbol"),
+    (SpaceSeparator, "Zs", "Space_Separator"),
+    (LineSeparator, "Zl", "Line_Separator"),
+    (ParagraphSeparator, "Zp", "Paragraph_Separator"),
+    (Control, "Cc", "Control"),
+    (Format, "Cf", "Format"),
+    (Surrogate, "Cs", "Surrogate"),
+    (PrivateUse, "Co", "Private_Use"),
+    (Unassigned, "Cn", "Unassigned"),
+];
+
+impl GeneralCategory {
+    /// Return an iterator over every general category.
+    pub fn all() -> impl Iterator<Item = GeneralCategory> {
+        CATEGORIES.iter().map(|(category, _, _)| *category)
+    }
+
+    /// The abbreviated name of this category, e.g. "Zs".
+    pub fn short_name(self) -> &'static str {
+        CATEGORIES[self as usize].1
+    }
+
+    /// The long name of this category, e.g. "Space_Separator".
+    pub fn long_name(self) -> &'static str {
+        CATEGORIES[self as usize].2
+    }
+
+    /// Return the category with the abbreviated name `name`, as used in
+    /// `UnicodeData.txt`.
+    pub fn from_short_name(name: &str) -> Option<GeneralCategory> {
+        CATEGORIES
+            .iter()
+            .find(|(_, short, _)| *short == name)
+            .map(|(category, _, _)| *category)
+    }
+
+    /// Return the category with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<GeneralCategory> {
+        CATEGORIES
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(category, _, _)| *category)
+    }
+
+    /// A mask with a single bit set, unique to this category.
+    pub fn mask(self) -> u32 {
+        1 << (self as u32)
+    }
+
+    /// Whether this category is one of the categories in `group`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use unicode_info::general_category::{GeneralCategory, GeneralCategoryGroup};
+    /// assert!(GeneralCategory::TitlecaseLetter.is_in(GeneralCategoryGroup::CasedLetter));
+    /// assert!(!GeneralCategory::ModifierLetter.is_in(GeneralCategoryGroup::CasedLetter));
+    /// ```
+    pub fn is_in(self, group: GeneralCategoryGroup) -> bool {
+        group.mask() & self.mask() != 0
+    }
+}
+
+/// A grouping of general categories that Unicode defines as a General_Category
+/// value in its own right: for example, Letter (L) is the union of Lu, Ll, Lt,
+/// Lm, and Lo.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GeneralCategoryGroup {
+    CasedLetter,
+    Letter,
+    Mark,
+    Number,
+    Punctuation,
+    Symbol,
+    Separator,
+    Other,
+}
+
+use GeneralCategoryGroup::*;
+
+/// Every general category group with its short and long names and the
+/// categories it contains.
+const GROUPS: [(GeneralCategoryGroup, &str, &str, &[GeneralCategory]); 8] = [
+    (
+        CasedLetter,
+        "LC",
+        "Cased_Letter",
+        &[UppercaseLetter, LowercaseLetter, TitlecaseLetter],
+    ),
+    (
+        Letter,
+        "L",
+        "Letter",
+        &[
+            UppercaseLetter,
+            LowercaseLetter,
+            TitlecaseLetter,
+            ModifierLetter,
+            OtherLetter,
+        ],
+    ),
+    (
+        Mark,
+        "M",
+        "Mark",
+        &[NonspacingMark, SpacingMark, EnclosingMark],
+    ),
+    (
+        Number,
+        "N",
+        "Number",
+        &[DecimalNumber, LetterNumber, OtherNumber],
+    ),
+    (
+        Punctuation,
+        "P",
+        "Punctuation",
+        &[
+            ConnectorPunctuation,
+            DashPunctuation,
+            OpenPunctuation,
+            ClosePunctuation,
+            InitialPunctuation,
+            FinalPunctuation,
+            OtherPunctuation,
+        ],
+    ),
+    (
+        Symbol,
+        "S",
+        "Symbol",
+        &[MathSymbol, CurrencySymbol, ModifierSymbol, OtherSymbol],
+    ),
+    (
+        Separator,
+        "Z",
+        "Separator",
+        &[SpaceSeparator, LineSeparator, ParagraphSeparator],
+    ),
+    (
+        Other,
+        "C",
+        "Other",
+        &[Control, Format, Surrogate, PrivateUse, Unassigned],
+    ),
+];
+
+impl GeneralCategoryGroup {
+    /// Return an iterator over every general category group.
+    pub fn all() -> impl Iterator<Item = GeneralCategoryGroup> {
+        GROUPS.iter().map(|(group, _, _, _)| *group)
+    }
+
+    /// The abbreviated name of this group, e.g. "LC".
+    pub fn short_name(self) -> &'static str {
+        GROUPS[self as usize].1
+    }
+
+    /// The long name of this group, e.g. "Cased_Letter".
+    pub fn long_name(self) -> &'static str {
+        GROUPS[self as usize].2
+    }
+
+    /// Return the group with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<GeneralCategoryGroup> {
+        GROUPS
+            .iter()
+            .find(|(_, short, long, _)| *short == name || *long == name)
+            .map(|(group, _, _, _)| *group)
+    }
+
+    /// The categories in this group.
+    pub fn categories(self) -> &'static [GeneralCategory] {
+        GROUPS[self as usize].3
+    }
+
+    /// The union of the masks of the categories in this group.
+    pub fn mask(self) -> u32 {
+        self.categories()
+            .iter()
+            .fold(0, |mask, category| mask | category.mask())
+    }
+}
+
+#[test]
+fn check_category_names() {
+    for (i, category) in GeneralCategory::all().enumerate() {
+        assert_eq!(category as usize, i, "CATEGORIES is in declaration order");
+        assert_eq!(
+            GeneralCategory::from_short_name(category.short_name()),
+            Some(category)
+        );
+        assert_eq!(
+            GeneralCategory::from_name(category.long_name()),
+            Some(category)
+        );
+    }
+    assert_eq!(GeneralCategory::from_short_name("Space_Separator"), None);
+
+    for (i, group) in GeneralCategoryGroup::all().enumerate() {
+        assert_eq!(group as usize, i, "GROUPS is in declaration order");
+        assert_eq!(
+            GeneralCategoryGroup::from_name(group.short_name()),
+            Some(group)
+        );
+        assert_eq!(
+            GeneralCategoryGroup::from_name(group.long_name()),
+            Some(group)
+        );
+    }
+}
+
+#[test]
+fn check_category_groups() {
+    // Every category is in exactly one group other than Cased_Letter.
+    for category in GeneralCategory::all() {
+        assert_eq!(
+            GeneralCategoryGroup::all()
+                .filter(|group| *group != CasedLetter && category.is_in(*group))
+                .count(),
+            1,
+            "{:?} in one group",
+            category
+        );
+    }
+
+    assert!(UppercaseLetter.is_in(Letter));
+    assert!(UppercaseLetter.is_in(CasedLetter));
+    assert!(!OtherLetter.is_in(CasedLetter));
+    assert!(SpaceSeparator.is_in(Separator));
+    assert!(Unassigned.is_in(Other));
+    assert_eq!(Letter.mask() & CasedLetter.mask(), CasedLetter.mask());
+}
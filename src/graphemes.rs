@@ -0,0 +1,349 @@
+//! A reference implementation of extended grapheme cluster segmentation, per
+//! the rules in <https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundary_Rules>.
+//!
+//! This is intended as an oracle to test `Intl.Segmenter` against, so it
+//! favors following the rules as written over speed.  It's checked against
+//! `auxiliary/GraphemeBreakTest.txt` by [`check_grapheme_break_test`].
+
+use crate::derived_core_properties::{DerivedCorePropertyData, IndicConjunctBreak};
+use crate::emoji::{EmojiData, EmojiProperty};
+use crate::segmentation_properties::{check_break_test, GraphemeClusterBreak, SegmentationData};
+use crate::ucd_error::UcdError;
+use crate::ucd_source::UcdSource;
+use std::ops::Range;
+
+use GraphemeClusterBreak::*;
+
+/// The name of the grapheme cluster break conformance test file, relative to
+/// the UCD directory.
+pub(crate) const GRAPHEME_BREAK_TEST_TXT: &str = "auxiliary/GraphemeBreakTest.txt";
+
+/// How much of the emoji ZWJ sequence `\p{ExtPict} Extend* ZWJ` (rule GB11)
+/// precedes a position.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum EmojiState {
+    None,
+    /// `\p{ExtPict} Extend*`
+    Pictographic,
+    /// `\p{ExtPict} Extend* ZWJ`
+    Zwj,
+}
+
+/// How much of the Indic conjunct sequence
+/// `\p{InCB=Consonant} [\p{InCB=Extend}\p{InCB=Linker}]* \p{InCB=Linker} [\p{InCB=Extend}\p{InCB=Linker}]*`
+/// (rule GB9c) precedes a position.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ConjunctState {
+    None,
+    /// A consonant followed by extenders, but no linker yet.
+    Consonant,
+    /// The entire sequence.
+    Linker,
+}
+
+/// The state needed to apply the rules that look back past the code point
+/// immediately preceding a position.
+struct ClusterState {
+    /// The number of consecutive regional indicators preceding the position.
+    regional_indicators: usize,
+    emoji: EmojiState,
+    conjunct: ConjunctState,
+}
+
+/// Segments strings into extended grapheme clusters.
+pub struct GraphemeSegmenter<'a> {
+    breaks: &'a SegmentationData,
+    emoji: &'a EmojiData,
+    properties: &'a DerivedCorePropertyData,
+}
+
+impl<'a> GraphemeSegmenter<'a> {
+    /// Create a segmenter using the Grapheme_Cluster_Break values in `breaks`,
+    /// the Extended_Pictographic values in `emoji`, and the
+    /// Indic_Conjunct_Break values in `properties`.
+    pub fn new(
+        breaks: &'a SegmentationData,
+        emoji: &'a EmojiData,
+        properties: &'a DerivedCorePropertyData,
+    ) -> GraphemeSegmenter<'a> {
+        GraphemeSegmenter {
+            breaks,
+            emoji,
+            properties,
+        }
+    }
+
+    /// Return an iterator over the ranges of `string` occupied by each of its
+    /// extended grapheme clusters.
+    pub fn graphemes<'s>(&'s self, string: &'s [u32]) -> impl Iterator<Item = Range<usize>> + 's {
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            if start == string.len() {
+                return None;
+            }
+            let end = self.cluster_end(string, start);
+            let cluster = start..end;
+            start = end;
+            Some(cluster)
+        })
+    }
+
+    /// Return the end of the extended grapheme cluster that begins at `start`
+    /// in `string`.
+    ///
+    /// Every sequence that rules GB9c, GB11, and GB12/GB13 look back over is
+    /// itself unbroken, so the state they need can start fresh at the
+    /// beginning of each cluster.
+    fn cluster_end(&self, string: &[u32], start: usize) -> usize {
+        let mut state = ClusterState {
+            regional_indicators: 0,
+            emoji: EmojiState::None,
+            conjunct: ConjunctState::None,
+        };
+        let mut before = self.breaks.grapheme_cluster_break(string[start]);
+        self.advance(&mut state, string[start], before);
+
+        for (i, &code) in string.iter().enumerate().skip(start + 1) {
+            let after = self.breaks.grapheme_cluster_break(code);
+            if self.is_boundary(&state, before, code, after) {
+                return i;
+            }
+            self.advance(&mut state, code, after);
+            before = after;
+        }
+        string.len()
+    }
+
+    /// Update `state` to include `code`, whose Grapheme_Cluster_Break value
+    /// is `value`.
+    fn advance(&self, state: &mut ClusterState, code: u32, value: GraphemeClusterBreak) {
+        state.regional_indicators = if value == RegionalIndicator {
+            state.regional_indicators + 1
+        } else {
+            0
+        };
+
+        state.emoji = if self
+            .emoji
+            .contains(EmojiProperty::ExtendedPictographic, code)
+        {
+            EmojiState::Pictographic
+        } else {
+            match (state.emoji, value) {
+                (EmojiState::Pictographic, Extend) => EmojiState::Pictographic,
+                (EmojiState::Pictographic, ZWJ) => EmojiState::Zwj,
+                _ => EmojiState::None,
+            }
+        };
+
+        state.conjunct = match (self.properties.indic_conjunct_break(code), state.conjunct) {
+            (IndicConjunctBreak::Consonant, _) => ConjunctState::Consonant,
+            (IndicConjunctBreak::Linker, ConjunctState::None) => ConjunctState::None,
+            (IndicConjunctBreak::Linker, _) => ConjunctState::Linker,
+            (IndicConjunctBreak::Extend, conjunct) => conjunct,
+            (IndicConjunctBreak::None, _) => ConjunctState::None,
+        };
+    }
+
+    /// Whether there's a boundary between a code point whose
+    /// Grapheme_Cluster_Break value is `before` and `code`, whose value is
+    /// `after`, given the `state` preceding the boundary.
+    fn is_boundary(
+        &self,
+        state: &ClusterState,
+        before: GraphemeClusterBreak,
+        code: u32,
+        after: GraphemeClusterBreak,
+    ) -> bool {
+        match (before, after) {
+            // GB3
+            (CR, LF) => false,
+            // GB4
+            (Control, _) | (CR, _) | (LF, _) => true,
+            // GB5
+            (_, Control) | (_, CR) | (_, LF) => true,
+            // GB6
+            (L, L) | (L, V) | (L, LV) | (L, LVT) => false,
+            // GB7
+            (LV, V) | (LV, T) | (V, V) | (V, T) => false,
+            // GB8
+            (LVT, T) | (T, T) => false,
+            // GB9, GB9a
+            (_, Extend) | (_, ZWJ) | (_, SpacingMark) => false,
+            // GB9b
+            (Prepend, _) => false,
+            // GB9c
+            _ if state.conjunct == ConjunctState::Linker
+                && self.properties.indic_conjunct_break(code) == IndicConjunctBreak::Consonant =>
+            {
+                false
+            }
+            // GB11
+            _ if state.emoji == EmojiState::Zwj
+                && self
+                    .emoji
+                    .contains(EmojiProperty::ExtendedPictographic, code) =>
+            {
+                false
+            }
+            // GB12, GB13
+            (RegionalIndicator, RegionalIndicator) => state.regional_indicators.is_multiple_of(2),
+            // GB999
+            _ => true,
+        }
+    }
+}
+
+/// Check `segmenter` against the `auxiliary/GraphemeBreakTest.txt` in
+/// `source`, returning the number of test lines checked, or an error
+/// describing the first failure.
+pub fn check_grapheme_break_test(
+    source: &UcdSource,
+    segmenter: &GraphemeSegmenter,
+) -> Result<usize, UcdError> {
+    check_break_test(source, GRAPHEME_BREAK_TEST_TXT, |string| {
+        segmenter
+            .graphemes(string)
+            .map(|cluster| cluster.end)
+            .collect()
+    })
+}
+
+/// Parse snippets of the files a `GraphemeSegmenter` needs.
+#[cfg(test)]
+fn parse_segmenter_test_data() -> (SegmentationData, EmojiData, DerivedCorePropertyData) {
+    use crate::derived_core_properties::{
+        parse_derived_core_properties, DERIVED_CORE_PROPERTIES_TXT,
+    };
+    use crate::emoji::{parse_emoji_data, EMOJI_DATA_TXT};
+    use crate::segmentation_properties::{
+        parse_segmentation_properties, GRAPHEME_BREAK_PROPERTY_TXT, SENTENCE_BREAK_PROPERTY_TXT,
+        WORD_BREAK_PROPERTY_TXT,
+    };
+    use crate::ucd_file::UcdFile;
+
+    let breaks = parse_segmentation_properties(
+        UcdFile::new(
+            GRAPHEME_BREAK_PROPERTY_TXT,
+            "# GraphemeBreakProperty-15.1.0.txt\n\
+             000D          ; CR # Cc       <control-000D>\n\
+             000A          ; LF # Cc       <control-000A>\n\
+             0000..0009    ; Control # Cc  [10] <control-0000>..<control-0009>\n\
+             0300..036F    ; Extend # Mn [112] COMBINING GRAVE ACCENT..COMBINING LATIN SMALL LETTER X\n\
+             094D          ; Extend # Mn       DEVANAGARI SIGN VIRAMA\n\
+             1F3FB..1F3FF  ; Extend # Sk   [5] EMOJI MODIFIER FITZPATRICK TYPE-1-2..EMOJI MODIFIER FITZPATRICK TYPE-6\n\
+             200D          ; ZWJ # Cf       ZERO WIDTH JOINER\n\
+             1F1E6..1F1FF  ; Regional_Indicator # So  [26] REGIONAL INDICATOR SYMBOL LETTER A..REGIONAL INDICATOR SYMBOL LETTER Z\n\
+             0600..0605    ; Prepend # Cf   [6] ARABIC NUMBER SIGN..ARABIC NUMBER MARK ABOVE\n\
+             0903          ; SpacingMark # Mc       DEVANAGARI SIGN VISARGA\n\
+             1100..115F    ; L # Lo  [96] HANGUL CHOSEONG KIYEOK..HANGUL CHOSEONG FILLER\n\
+             1160..11A7    ; V # Lo  [72] HANGUL JUNGSEONG FILLER..HANGUL JUNGSEONG O-YAE\n\
+             11A8..11FF    ; T # Lo  [88] HANGUL JONGSEONG KIYEOK..HANGUL JONGSEONG SSANGNIEUN\n\
+             AC00          ; LV # Lo       HANGUL SYLLABLE GA\n\
+             AC01          ; LVT # Lo       HANGUL SYLLABLE GAG\n",
+        ),
+        UcdFile::new(WORD_BREAK_PROPERTY_TXT, ""),
+        UcdFile::new(SENTENCE_BREAK_PROPERTY_TXT, ""),
+    )
+    .expect("break properties");
+    let emoji = parse_emoji_data(UcdFile::new(
+        EMOJI_DATA_TXT,
+        "2764          ; Extended_Pictographic# E0.6   [1] (\u{2764}\u{fe0f})       red heart\n\
+         1F468         ; Extended_Pictographic# E0.6   [1] (\u{1f468})       man\n",
+    ))
+    .expect("emoji data");
+    let properties = parse_derived_core_properties(UcdFile::new(
+        DERIVED_CORE_PROPERTIES_TXT,
+        "0915..0939    ; InCB; Consonant # Lo  [37] DEVANAGARI LETTER KA..DEVANAGARI LETTER HA\n\
+         094D          ; InCB; Linker # Mn       DEVANAGARI SIGN VIRAMA\n\
+         0300..034E    ; InCB; Extend # Mn  [79] COMBINING GRAVE ACCENT..COMBINING GRAPHEME JOINER\n\
+         200D          ; InCB; Extend # Cf       ZERO WIDTH JOINER\n",
+    ))
+    .expect("derived properties");
+    (breaks, emoji, properties)
+}
+
+#[test]
+fn check_graphemes() {
+    let (breaks, emoji, properties) = parse_segmenter_test_data();
+    let segmenter = GraphemeSegmenter::new(&breaks, &emoji, &properties);
+    // The end of each cluster.
+    let graphemes = |string: &[u32]| {
+        segmenter
+            .graphemes(string)
+            .map(|cluster| cluster.end)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(graphemes(&[]), []);
+    assert_eq!(graphemes(&[0x61]), [1]);
+    // GB3, GB4, GB5
+    assert_eq!(graphemes(&[0x0D, 0x0A, 0x0A, 0x0D]), [2, 3, 4]);
+    assert_eq!(graphemes(&[0x61, 0x00, 0x0300]), [1, 2, 3]);
+    // GB6, GB7, GB8
+    assert_eq!(graphemes(&[0x1100, 0x1161, 0x11A8, 0x1100]), [3, 4]);
+    assert_eq!(graphemes(&[0xAC00, 0x11A8, 0xAC01, 0x1161]), [2, 3, 4]);
+    // GB9, GB9a, GB9b
+    assert_eq!(graphemes(&[0x61, 0x0300, 0x0903, 0x0600, 0x62]), [3, 5]);
+    // GB9c
+    assert_eq!(graphemes(&[0x0915, 0x094D, 0x0937]), [3]);
+    assert_eq!(graphemes(&[0x0915, 0x094D, 0x0300, 0x094D, 0x0937]), [5]);
+    assert_eq!(graphemes(&[0x0915, 0x0300, 0x0937]), [2, 3]);
+    assert_eq!(graphemes(&[0x61, 0x094D, 0x0937]), [2, 3]);
+    // GB11
+    assert_eq!(
+        graphemes(&[0x1F468, 0x1F3FB, 0x200D, 0x2764, 0x200D, 0x1F468]),
+        [6]
+    );
+    assert_eq!(graphemes(&[0x61, 0x200D, 0x2764]), [2, 3]);
+    // GB12, GB13
+    assert_eq!(
+        graphemes(&[0x1F1FA, 0x1F1F8, 0x1F1EC, 0x1F1E7, 0x1F1E6]),
+        [2, 4, 5]
+    );
+    assert_eq!(graphemes(&[0x61, 0x1F1FA, 0x1F1F8]), [1, 3]);
+}
+
+#[test]
+fn check_grapheme_break_test_failures() {
+    let dir = crate::ucd_source::TestDirectory::new("check_grapheme_break_test_failures");
+    dir.write(
+        GRAPHEME_BREAK_TEST_TXT,
+        "# GraphemeBreakTest-15.1.0.txt\n\
+         ÷ 0020 ÷ 0020 ÷\t#  ÷ [0.2] SPACE (Other) ÷ [999.0] SPACE (Other) ÷ [0.3]\n\
+         ÷ 000D × 000A ÷\t#  ÷ [0.2] <CARRIAGE RETURN (CR)> (CR) × [3.0] <LINE FEED (LF)> (LF) ÷ [0.3]\n",
+    );
+    let source = dir.source();
+    let (breaks, emoji, properties) = parse_segmenter_test_data();
+    let segmenter = GraphemeSegmenter::new(&breaks, &emoji, &properties);
+    assert_eq!(check_grapheme_break_test(&source, &segmenter).ok(), Some(2));
+
+    dir.write(
+        GRAPHEME_BREAK_TEST_TXT,
+        "÷ 0061 ÷ 0300 ÷\t#  a wrong expectation\n",
+    );
+    let error = check_grapheme_break_test(&source, &segmenter).expect_err("wrong boundaries");
+    assert_eq!(
+        error.to_string(),
+        "auxiliary/GraphemeBreakTest.txt:1: expected boundaries ÷ 0061 ÷ 0300 ÷, \
+         found \"÷ 0061 × 0300 ÷\""
+    );
+}
+
+/// Check against `auxiliary/GraphemeBreakTest.txt` and the other files in the
+/// UCD directory named by the `UNICODE_INFO_UCD_DIR` environment variable.
+/// Run with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs UNICODE_INFO_UCD_DIR"]
+fn check_grapheme_break_conformance() {
+    let dir = std::env::var_os("UNICODE_INFO_UCD_DIR").expect("UNICODE_INFO_UCD_DIR is set");
+    let source = UcdSource::Directory(dir.into());
+    let breaks = crate::segmentation_properties::process_segmentation_properties(&source)
+        .expect("break properties");
+    let emoji = crate::emoji::process_emoji_data(&source).expect("emoji data");
+    let properties = crate::derived_core_properties::process_derived_core_properties(&source)
+        .expect("derived properties");
+    let segmenter = GraphemeSegmenter::new(&breaks, &emoji, &properties);
+    let checked = check_grapheme_break_test(&source, &segmenter).expect("conformance");
+    assert!(checked > 0);
+}
@@ -1,13 +1,35 @@
 //! A crate that parses, slices, and dices Unicode code point description files
 //! into convenient structured representations.
 
+pub mod age;
+pub mod bidi;
+pub mod blocks;
 pub mod bmp;
 pub mod case_folding;
 pub mod code_point_table;
 pub mod constants;
 pub mod derived_core_properties;
+pub mod east_asian_width;
+pub mod emoji;
+pub mod emoji_sequences;
+pub mod enumerated_property;
+pub mod general_category;
+pub mod graphemes;
+pub mod line_break;
+pub mod line_breaker;
+pub mod name_aliases;
+pub mod names;
 pub mod non_bmp;
+pub mod normalization;
+pub mod prop_list;
+pub mod property_aliases;
+pub mod scripts;
+pub mod segmentation_properties;
 pub mod spaces;
 pub mod special_casing;
 pub mod table;
 pub mod types;
+pub mod ucd_error;
+pub mod ucd_file;
+pub mod ucd_source;
+pub mod unicode_version;
@@ -0,0 +1,287 @@
+//! Processes `LineBreak.txt` to determine the Line_Break property of code
+//! points, as used by the line breaking algorithm.
+//!
+//! `LineBreak.txt` isn't embedded in this crate, so it must be read from a
+//! [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr14/> for details.
+
+use crate::code_point_table::CodePoint;
+use crate::enumerated_property::EnumeratedProperty;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+
+/// The name of the file processed by this module.
+pub(crate) const LINE_BREAK_TXT: &str = "LineBreak.txt";
+
+/// The Line_Break value of a code point.
+///
+/// Values are named by their short names, as they are in the rules of the
+/// line breaking algorithm.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LineBreak {
+    /// Ambiguous
+    AI,
+    /// Aksara
+    AK,
+    /// Alphabetic
+    AL,
+    /// Aksara_Prebase
+    AP,
+    /// Aksara_Start
+    AS,
+    /// Break_Both
+    B2,
+    /// Break_After
+    BA,
+    /// Break_Before
+    BB,
+    /// Mandatory_Break
+    BK,
+    /// Contingent_Break
+    CB,
+    /// Conditional_Japanese_Starter
+    CJ,
+    /// Close_Punctuation
+    CL,
+    /// Combining_Mark
+    CM,
+    /// Close_Parenthesis
+    CP,
+    /// Carriage_Return
+    CR,
+    /// E_Base
+    EB,
+    /// E_Modifier
+    EM,
+    /// Exclamation
+    EX,
+    /// Glue
+    GL,
+    H2,
+    H3,
+    /// Unambiguous_Hyphen
+    HH,
+    /// Hebrew_Letter
+    HL,
+    /// Hyphen
+    HY,
+    /// Ideographic
+    ID,
+    /// Inseparable
+    IN,
+    /// Infix_Numeric
+    IS,
+    JL,
+    JT,
+    JV,
+    /// Line_Feed
+    LF,
+    /// Next_Line
+    NL,
+    /// Nonstarter
+    NS,
+    /// Numeric
+    NU,
+    /// Open_Punctuation
+    OP,
+    /// Postfix_Numeric
+    PO,
+    /// Prefix_Numeric
+    PR,
+    /// Quotation
+    QU,
+    /// Regional_Indicator
+    RI,
+    /// Complex_Context
+    SA,
+    /// Surrogate
+    SG,
+    /// Space
+    SP,
+    /// Break_Symbols
+    SY,
+    /// Virama_Final
+    VF,
+    /// Virama
+    VI,
+    /// Word_Joiner
+    WJ,
+    /// Unknown
+    XX,
+    /// ZWSpace
+    ZW,
+    ZWJ,
+}
+
+use LineBreak::*;
+
+/// Every Line_Break value, in the order `PropertyValueAliases.txt` lists them,
+/// with its short and long names.
+const LINE_BREAKS: [(LineBreak, &str, &str); 49] = [
+    (AI, "AI", "Ambiguous"),
+    (AK, "AK", "Aksara"),
+    (AL, "AL", "Alphabetic"),
+    (AP, "AP", "Aksara_Prebase"),
+    (AS, "AS", "Aksara_Start"),
+    (B2, "B2", "Break_Both"),
+    (BA, "BA", "Break_After"),
+    (BB, "BB", "Break_Before"),
+    (BK, "BK", "Mandatory_Break"),
+    (CB, "CB", "Contingent_Break"),
+    (CJ, "CJ", "Conditional_Japanese_Starter"),
+    (CL, "CL", "Close_Punctuation"),
+    (CM, "CM", "Combining_Mark"),
+    (CP, "CP", "Close_Parenthesis"),
+    (CR, "CR", "Carriage_Return"),
+    (EB, "EB", "E_Base"),
+    (EM, "EM", "E_Modifier"),
+    (EX, "EX", "Exclamation"),
+    (GL, "GL", "Glue"),
+    (H2, "H2", "H2"),
+    (H3, "H3", "H3"),
+    (HH, "HH", "Unambiguous_Hyphen"),
+    (HL, "HL", "Hebrew_Letter"),
+    (HY, "HY", "Hyphen"),
+    (ID, "ID", "Ideographic"),
+    (IN, "IN", "Inseparable"),
+    (IS, "IS", "Infix_Numeric"),
+    (JL, "JL", "JL"),
+    (JT, "JT", "JT"),
+    (JV, "JV", "JV"),
+    (LF, "LF", "Line_Feed"),
+    (NL, "NL", "Next_Line"),
+    (NS, "NS", "Nonstarter"),
+    (NU, "NU", "Numeric"),
+    (OP, "OP", "Open_Punctuation"),
+    (PO, "PO", "Postfix_Numeric"),
+    (PR, "PR", "Prefix_Numeric"),
+    (QU, "QU", "Quotation"),
+    (RI, "RI", "Regional_Indicator"),
+    (SA, "SA", "Complex_Context"),
+    (SG, "SG", "Surrogate"),
+    (SP, "SP", "Space"),
+    (SY, "SY", "Break_Symbols"),
+    (VF, "VF", "Virama_Final"),
+    (VI, "VI", "Virama"),
+    (WJ, "WJ", "Word_Joiner"),
+    (XX, "XX", "Unknown"),
+    (ZW, "ZW", "ZWSpace"),
+    (ZWJ, "ZWJ", "ZWJ"),
+];
+
+impl LineBreak {
+    /// Return an iterator over every Line_Break value.
+    pub fn all() -> impl Iterator<Item = LineBreak> {
+        LINE_BREAKS.iter().map(|(value, _, _)| *value)
+    }
+
+    /// The abbreviated name of this value, e.g. "OP".
+    pub fn short_name(self) -> &'static str {
+        LINE_BREAKS[self as usize].1
+    }
+
+    /// The long name of this value, e.g. "Open_Punctuation".
+    pub fn long_name(self) -> &'static str {
+        LINE_BREAKS[self as usize].2
+    }
+
+    /// Return the value with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<LineBreak> {
+        if name == "Inseperable" {
+            // A misspelled alias kept for stability.
+            return Some(IN);
+        }
+        LINE_BREAKS
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(value, _, _)| *value)
+    }
+}
+
+/// The Line_Break value of every code point.
+pub struct LineBreakData {
+    /// The value of every code point, from `LineBreak.txt` and its `@missing`
+    /// lines.
+    line_breaks: EnumeratedProperty<LineBreak>,
+}
+
+impl LineBreakData {
+    /// The Line_Break value of `code`.
+    pub fn line_break(&self, code: u32) -> LineBreak {
+        self.line_breaks.get(code)
+    }
+}
+
+impl CodePoint {
+    /// The Line_Break value of this code point.
+    pub fn line_break(&self, line_breaks: &LineBreakData) -> LineBreak {
+        line_breaks.line_break(self.code)
+    }
+}
+
+/// Parse the Line_Break value of every code point from the `LineBreak.txt` in
+/// `source`.
+pub fn process_line_break(source: &UcdSource) -> Result<LineBreakData, UcdError> {
+    parse_line_break(UcdFile::read(source, LINE_BREAK_TXT)?)
+}
+
+pub(crate) fn parse_line_break(file: UcdFile) -> Result<LineBreakData, UcdError> {
+    Ok(LineBreakData {
+        line_breaks: EnumeratedProperty::parse(
+            file,
+            "a Line_Break value",
+            LineBreak::from_name,
+            XX,
+        )?,
+    })
+}
+
+#[test]
+fn check_line_break() {
+    for (i, value) in LineBreak::all().enumerate() {
+        assert_eq!(value as usize, i, "LINE_BREAKS is in declaration order");
+        assert_eq!(LineBreak::from_name(value.short_name()), Some(value));
+        assert_eq!(LineBreak::from_name(value.long_name()), Some(value));
+    }
+    assert_eq!(LineBreak::from_name("Inseperable"), Some(IN));
+
+    let file = UcdFile::new(
+        LINE_BREAK_TXT,
+        "# LineBreak-16.0.0.txt\n\
+         # @missing: 0000..10FFFF; XX\n\
+         # @missing: 20A0..20CF; PR\n\
+         # @missing: 3400..4DBF; ID\n\
+         0000..0008;CM     # Cc     [9] <control-0000>..<control-0008>\n\
+         000A;LF           # Cc         <control-000A>\n\
+         0020;SP           # Zs         SPACE\n\
+         0028;OP           # Ps         LEFT PARENTHESIS\n\
+         0041..005A;AL     # Lu    [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n\
+         20AC;PR           # Sc         EURO SIGN\n\
+         3400..4DBF;ID     # Lo  [6592] CJK UNIFIED IDEOGRAPH-3400..CJK UNIFIED IDEOGRAPH-4DBF\n",
+    );
+
+    let line_breaks = parse_line_break(file).expect("Line_Break data");
+    assert_eq!(line_breaks.line_break(0x0000), CM);
+    assert_eq!(line_breaks.line_break('\n' as u32), LF);
+    assert_eq!(line_breaks.line_break('(' as u32), OP);
+    assert_eq!(line_breaks.line_break('Q' as u32), AL);
+    assert_eq!(line_breaks.line_break(0x3400), ID);
+    assert_eq!(line_breaks.line_break('a' as u32), XX);
+    // Unassigned currency symbols default to PR.
+    assert_eq!(line_breaks.line_break(0x20CF), PR);
+
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    let space = table.get(' ' as u32).expect("U+0020");
+    assert_eq!(space.line_break(&line_breaks), SP);
+
+    let error = parse_line_break(UcdFile::new(LINE_BREAK_TXT, "0020;XY # SPACE\n"))
+        .err()
+        .expect("bad Line_Break value");
+    assert_eq!(
+        error.to_string(),
+        "LineBreak.txt:1: expected a Line_Break value, found \"XY\""
+    );
+}
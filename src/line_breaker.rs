@@ -0,0 +1,614 @@
+//! A reference implementation of the line breaking algorithm, per the rules
+//! in <https://www.unicode.org/reports/tr14/#Algorithm>.
+//!
+//! This implements the default rules of Unicode 16.0 (UAX #14 revision 53),
+//! with the tailoring of rule LB25 that `auxiliary/LineBreakTest.txt` uses,
+//! and no resolution of Complex_Context (SA) characters beyond rule LB1.  Its
+//! data should come from the same version: earlier versions of `LineBreak.txt`
+//! lack the HH class, and earlier versions of `LineBreakTest.txt` expect
+//! different results from rules LB19a, LB20a, and LB21a.  (The
+//! `UnicodeData.txt` embedded in this crate is older, so conformance tests
+//! must read it from the same directory as the other files.)  Like
+//! [`graphemes`](crate::graphemes), it favors following the rules as written
+//! over speed.
+
+use crate::code_point_table::CodePointTable;
+use crate::east_asian_width::EastAsianWidthData;
+use crate::emoji::{EmojiData, EmojiProperty};
+use crate::general_category::GeneralCategory;
+use crate::line_break::{LineBreak, LineBreakData};
+use crate::segmentation_properties::check_break_test;
+use crate::ucd_error::UcdError;
+use crate::ucd_source::UcdSource;
+
+use LineBreak::*;
+
+/// The name of the line break conformance test file, relative to the UCD
+/// directory.
+pub(crate) const LINE_BREAK_TEST_TXT: &str = "auxiliary/LineBreakTest.txt";
+
+/// U+25CC DOTTED CIRCLE, which rule LB28a treats like an aksara.
+const DOTTED_CIRCLE: u32 = 0x25CC;
+
+/// A position in a string where a line may, or must, be broken.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BreakOpportunity {
+    /// The offset of the break: the line before it ends just before the code
+    /// point at this offset.
+    pub offset: usize,
+
+    /// Whether the line must be broken here, e.g. after a line feed.
+    pub mandatory: bool,
+}
+
+/// A code point and the combining marks rule LB9 attaches to it, which are
+/// treated as though they were the code point alone.
+#[derive(Copy, Clone, Debug)]
+struct Unit {
+    /// The code point.
+    code: u32,
+
+    /// Its Line_Break value, as resolved by rules LB1 and LB10.
+    class: LineBreak,
+}
+
+/// Finds line break opportunities in strings.
+pub struct LineBreaker<'a> {
+    line_breaks: &'a LineBreakData,
+    widths: &'a EastAsianWidthData,
+    emoji: &'a EmojiData,
+    table: &'a CodePointTable,
+}
+
+impl<'a> LineBreaker<'a> {
+    /// Create a line breaker using the Line_Break values in `line_breaks`,
+    /// the East_Asian_Width values in `widths`, the Extended_Pictographic
+    /// values in `emoji`, and the general categories in `table`.
+    pub fn new(
+        line_breaks: &'a LineBreakData,
+        widths: &'a EastAsianWidthData,
+        emoji: &'a EmojiData,
+        table: &'a CodePointTable,
+    ) -> LineBreaker<'a> {
+        LineBreaker {
+            line_breaks,
+            widths,
+            emoji,
+            table,
+        }
+    }
+
+    /// Return an iterator over the line break opportunities in `string`,
+    /// including the mandatory break at its end (unless it's empty).
+    pub fn break_opportunities(&self, string: &[u32]) -> impl Iterator<Item = BreakOpportunity> {
+        // LB1
+        let classes = string
+            .iter()
+            .map(|&code| match self.line_breaks.line_break(code) {
+                AI | SG | XX => AL,
+                SA => match self.general_category(code) {
+                    GeneralCategory::NonspacingMark | GeneralCategory::SpacingMark => CM,
+                    _ => AL,
+                },
+                CJ => NS,
+                class => class,
+            })
+            .collect::<Vec<_>>();
+
+        // LB9, LB10: `units` holds each code point not attached to a
+        // preceding one, and `unit_of` the index in `units` of each code
+        // point's unit.
+        let mut units = Vec::<Unit>::new();
+        let mut unit_of = Vec::with_capacity(string.len());
+        for (&code, &class) in string.iter().zip(&classes) {
+            let attaches = (class == CM || class == ZWJ)
+                && units
+                    .last()
+                    .is_some_and(|unit| !matches!(unit.class, BK | CR | LF | NL | SP | ZW));
+            if !attaches {
+                let class = if class == CM || class == ZWJ {
+                    AL
+                } else {
+                    class
+                };
+                units.push(Unit { code, class });
+            }
+            unit_of.push(units.len() - 1);
+        }
+
+        let mut opportunities = vec![];
+        for offset in 1..string.len() {
+            let mandatory = matches!(classes[offset - 1], BK | CR | LF | NL)
+                && !(classes[offset - 1] == CR && classes[offset] == LF);
+            // LB9: rules LB4 through LB8a can't apply within a unit, whose
+            // first code point isn't BK, CR, LF, NL, SP, or ZW.
+            if unit_of[offset] != unit_of[offset - 1]
+                && self.is_break(&classes, offset, &units, unit_of[offset])
+            {
+                opportunities.push(BreakOpportunity { offset, mandatory });
+            }
+        }
+        // LB3
+        if !string.is_empty() {
+            opportunities.push(BreakOpportunity {
+                offset: string.len(),
+                mandatory: true,
+            });
+        }
+        opportunities.into_iter()
+    }
+
+    /// The general category of `code`.
+    fn general_category(&self, code: u32) -> GeneralCategory {
+        self.table
+            .get(code)
+            .map_or(GeneralCategory::Unassigned, |code_point| {
+                code_point.general_category()
+            })
+    }
+
+    /// Whether `unit` is East Asian for the purposes of rules LB19a and LB30:
+    /// whether its East_Asian_Width is Fullwidth, Wide, or Halfwidth.
+    fn is_east_asian(&self, unit: &Unit) -> bool {
+        use crate::east_asian_width::EastAsianWidth::*;
+        matches!(self.widths.width(unit.code), Fullwidth | Wide | Halfwidth)
+    }
+
+    /// Whether `unit` is a quotation mark with general category `category`.
+    fn is_quotation(&self, unit: &Unit, category: GeneralCategory) -> bool {
+        unit.class == QU && self.general_category(unit.code) == category
+    }
+
+    /// Whether `unit` is an aksara for the purposes of rule LB28a.
+    fn is_aksara(unit: &Unit) -> bool {
+        matches!(unit.class, AK | AS) || unit.code == DOTTED_CIRCLE
+    }
+
+    /// Whether there's a break before `offset`, which begins `units[k]`.
+    /// `classes` holds the LB1-resolved class of every code point.
+    fn is_break(&self, classes: &[LineBreak], offset: usize, units: &[Unit], k: usize) -> bool {
+        let (before, after) = (classes[offset - 1], classes[offset]);
+
+        // LB4, LB5
+        if matches!(before, BK | CR | LF | NL) {
+            return !(before == CR && after == LF);
+        }
+        // LB6
+        if matches!(after, BK | CR | LF | NL) {
+            return false;
+        }
+        // LB7
+        if matches!(after, SP | ZW) {
+            return false;
+        }
+        // LB8: ZW SP* ÷
+        let first_non_space = classes[..offset].iter().rev().find(|class| **class != SP);
+        if first_non_space == Some(&ZW) {
+            return true;
+        }
+        // LB8a
+        if before == ZWJ {
+            return false;
+        }
+
+        // The remaining rules apply to the units that LB9 and LB10 produce.
+        let b = units[k - 1];
+        let a = units[k];
+        let b2 = k.checked_sub(2).map(|i| units[i]);
+        let a2 = units.get(k + 1).copied();
+        // The index of the last unit before `units[k]` that isn't SP, and its
+        // class.
+        let non_space = units[..k].iter().rposition(|unit| unit.class != SP);
+        let before_spaces = non_space.map(|i| units[i].class);
+
+        // LB11
+        if a.class == WJ || b.class == WJ {
+            return false;
+        }
+        // LB12
+        if b.class == GL {
+            return false;
+        }
+        // LB12a
+        if a.class == GL && !matches!(b.class, SP | BA | HY | HH) {
+            return false;
+        }
+        // LB13
+        if matches!(a.class, CL | CP | EX | SY) {
+            return false;
+        }
+        // LB14: OP SP* ×
+        if before_spaces == Some(OP) {
+            return false;
+        }
+        // LB15a: (sot | BK | CR | LF | NL | OP | QU | GL | SP | ZW) [\p{Pi}&QU] SP* ×
+        if let Some(i) = non_space {
+            let follows_opener = i == 0
+                || matches!(
+                    units[i - 1].class,
+                    BK | CR | LF | NL | OP | QU | GL | SP | ZW
+                );
+            if follows_opener && self.is_quotation(&units[i], GeneralCategory::InitialPunctuation) {
+                return false;
+            }
+        }
+        // LB15b: × [\p{Pf}&QU] ( SP | GL | WJ | CL | QU | CP | EX | IS | SY | BK | CR | LF | NL | ZW | eot )
+        if self.is_quotation(&a, GeneralCategory::FinalPunctuation)
+            && a2.is_none_or(|a2| {
+                matches!(
+                    a2.class,
+                    SP | GL | WJ | CL | QU | CP | EX | IS | SY | BK | CR | LF | NL | ZW
+                )
+            })
+        {
+            return false;
+        }
+        // LB15c
+        if b.class == SP && a.class == IS && a2.is_some_and(|a2| a2.class == NU) {
+            return true;
+        }
+        // LB15d
+        if a.class == IS {
+            return false;
+        }
+        // LB16: (CL | CP) SP* × NS
+        if a.class == NS && matches!(before_spaces, Some(CL) | Some(CP)) {
+            return false;
+        }
+        // LB17: B2 SP* × B2
+        if a.class == B2 && before_spaces == Some(B2) {
+            return false;
+        }
+        // LB18
+        if b.class == SP {
+            return true;
+        }
+        // LB19
+        if a.class == QU && !self.is_quotation(&a, GeneralCategory::InitialPunctuation) {
+            return false;
+        }
+        if b.class == QU && !self.is_quotation(&b, GeneralCategory::FinalPunctuation) {
+            return false;
+        }
+        // LB19a
+        if a.class == QU
+            && (!self.is_east_asian(&b) || a2.is_none_or(|a2| !self.is_east_asian(&a2)))
+        {
+            return false;
+        }
+        if b.class == QU
+            && (!self.is_east_asian(&a) || b2.is_none_or(|b2| !self.is_east_asian(&b2)))
+        {
+            return false;
+        }
+        // LB20
+        if a.class == CB || b.class == CB {
+            return true;
+        }
+        // LB20a: ( sot | BK | CR | LF | NL | SP | ZW | CB | GL ) ( HY | HH ) × ( AL | HL )
+        if matches!(b.class, HY | HH)
+            && matches!(a.class, AL | HL)
+            && b2.is_none_or(|b2| matches!(b2.class, BK | CR | LF | NL | SP | ZW | CB | GL))
+        {
+            return false;
+        }
+        // LB21
+        if matches!(a.class, BA | HH | HY | NS) || b.class == BB {
+            return false;
+        }
+        // LB21a
+        if matches!(b.class, HY | HH) && b2.is_some_and(|b2| b2.class == HL) && a.class != HL {
+            return false;
+        }
+        // LB21b
+        if b.class == SY && a.class == HL {
+            return false;
+        }
+        // LB22
+        if a.class == IN {
+            return false;
+        }
+        // LB23
+        match (b.class, a.class) {
+            (AL, NU) | (HL, NU) | (NU, AL) | (NU, HL) => return false,
+            _ => {}
+        }
+        // LB23a
+        match (b.class, a.class) {
+            (PR, ID) | (PR, EB) | (PR, EM) | (ID, PO) | (EB, PO) | (EM, PO) => return false,
+            _ => {}
+        }
+        // LB24
+        match (b.class, a.class) {
+            (PR, AL) | (PR, HL) | (PO, AL) | (PO, HL) => return false,
+            (AL, PR) | (AL, PO) | (HL, PR) | (HL, PO) => return false,
+            _ => {}
+        }
+        // LB25, tailored as in example 7 of section 8.2.
+        if self.is_numeric_break_prevented(units, k) {
+            return false;
+        }
+        // LB26
+        match (b.class, a.class) {
+            (JL, JL) | (JL, JV) | (JL, H2) | (JL, H3) => return false,
+            (JV, JV) | (JV, JT) | (H2, JV) | (H2, JT) => return false,
+            (JT, JT) | (H3, JT) => return false,
+            _ => {}
+        }
+        // LB27
+        if matches!(b.class, JL | JV | JT | H2 | H3) && a.class == PO {
+            return false;
+        }
+        if b.class == PR && matches!(a.class, JL | JV | JT | H2 | H3) {
+            return false;
+        }
+        // LB28
+        if matches!(b.class, AL | HL) && matches!(a.class, AL | HL) {
+            return false;
+        }
+        // LB28a
+        if b.class == AP && LineBreaker::is_aksara(&a) {
+            return false;
+        }
+        if LineBreaker::is_aksara(&b) && matches!(a.class, VF | VI) {
+            return false;
+        }
+        if b.class == VI
+            && b2.is_some_and(|b2| LineBreaker::is_aksara(&b2))
+            && (a.class == AK || a.code == DOTTED_CIRCLE)
+        {
+            return false;
+        }
+        if LineBreaker::is_aksara(&b)
+            && LineBreaker::is_aksara(&a)
+            && a2.is_some_and(|a2| a2.class == VF)
+        {
+            return false;
+        }
+        // LB29
+        if b.class == IS && matches!(a.class, AL | HL) {
+            return false;
+        }
+        // LB30
+        if matches!(b.class, AL | HL | NU) && a.class == OP && !self.is_east_asian(&a) {
+            return false;
+        }
+        if b.class == CP && !self.is_east_asian(&b) && matches!(a.class, AL | HL | NU) {
+            return false;
+        }
+        // LB30a
+        if b.class == RI && a.class == RI {
+            let count = units[..k]
+                .iter()
+                .rev()
+                .take_while(|unit| unit.class == RI)
+                .count();
+            return count.is_multiple_of(2);
+        }
+        // LB30b
+        if a.class == EM {
+            if b.class == EB {
+                return false;
+            }
+            if self
+                .emoji
+                .contains(EmojiProperty::ExtendedPictographic, b.code)
+                && self.general_category(b.code) == GeneralCategory::Unassigned
+            {
+                return false;
+            }
+        }
+        // LB31
+        true
+    }
+
+    /// Whether the tailored rule LB25 prevents a break before `units[k]`:
+    ///
+    /// ```text
+    /// ( PR | PO ) × ( OP | HY )? NU
+    /// ( OP | HY ) × NU
+    /// NU × ( NU | SY | IS )
+    /// NU ( NU | SY | IS )* × ( NU | SY | IS | CL | CP )
+    /// NU ( NU | SY | IS )* ( CL | CP )? × ( PO | PR )
+    /// ```
+    fn is_numeric_break_prevented(&self, units: &[Unit], k: usize) -> bool {
+        let class = |i: usize| units.get(i).map(|unit| unit.class);
+        let (b, a) = (units[k - 1].class, units[k].class);
+
+        if matches!(b, PR | PO) && (a == NU || (matches!(a, OP | HY) && class(k + 1) == Some(NU))) {
+            return true;
+        }
+        if matches!(b, OP | HY) && a == NU {
+            return true;
+        }
+
+        // Whether the units before `end` end with NU ( NU | SY | IS )*.
+        let follows_number = |end: usize| {
+            units[..end]
+                .iter()
+                .rev()
+                .take_while(|unit| matches!(unit.class, NU | SY | IS))
+                .any(|unit| unit.class == NU)
+        };
+        if matches!(a, NU | SY | IS | CL | CP) && follows_number(k) {
+            return true;
+        }
+        if matches!(a, PO | PR) {
+            let end = if matches!(b, CL | CP) { k - 1 } else { k };
+            if follows_number(end) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Check `breaker` against the `auxiliary/LineBreakTest.txt` in `source`,
+/// returning the number of test lines checked, or an error describing the
+/// first failure.
+pub fn check_line_break_test(source: &UcdSource, breaker: &LineBreaker) -> Result<usize, UcdError> {
+    check_break_test(source, LINE_BREAK_TEST_TXT, |string| {
+        breaker
+            .break_opportunities(string)
+            .map(|opportunity| opportunity.offset)
+            .collect()
+    })
+}
+
+/// Parse snippets of the files a `LineBreaker` needs, and the embedded
+/// `UnicodeData.txt`.
+#[cfg(test)]
+fn parse_breaker_test_data() -> (LineBreakData, EastAsianWidthData, EmojiData, CodePointTable) {
+    use crate::east_asian_width::{parse_east_asian_width, EAST_ASIAN_WIDTH_TXT};
+    use crate::emoji::{parse_emoji_data, EMOJI_DATA_TXT};
+    use crate::line_break::{parse_line_break, LINE_BREAK_TXT};
+    use crate::ucd_file::UcdFile;
+
+    let line_breaks = parse_line_break(UcdFile::new(
+        LINE_BREAK_TXT,
+        "# LineBreak-16.0.0.txt\n\
+         # @missing: 0000..10FFFF; XX\n\
+         000A;LF           # Cc         <control-000A>\n\
+         000D;CR           # Cc         <control-000D>\n\
+         0020;SP           # Zs         SPACE\n\
+         0022;QU           # Po         QUOTATION MARK\n\
+         0024;PR           # Sc         DOLLAR SIGN\n\
+         0025;PO           # Po         PERCENT SIGN\n\
+         0028;OP           # Ps         LEFT PARENTHESIS\n\
+         0029;CP           # Pe         RIGHT PARENTHESIS\n\
+         002C;IS           # Po         COMMA\n\
+         002D;HY           # Pd         HYPHEN-MINUS\n\
+         0030..0039;NU     # Nd    [10] DIGIT ZERO..DIGIT NINE\n\
+         0041..005A;AL     # Lu    [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n\
+         0061..007A;AL     # Ll    [26] LATIN SMALL LETTER A..LATIN SMALL LETTER Z\n\
+         0300..036F;CM     # Mn   [112] COMBINING GRAVE ACCENT..COMBINING LATIN SMALL LETTER X\n\
+         05D0..05EA;HL     # Lo    [27] HEBREW LETTER ALEF..HEBREW LETTER TAV\n\
+         200B;ZW           # Cf         ZERO WIDTH SPACE\n\
+         2010;HH           # Pd         HYPHEN\n\
+         201C;QU           # Pi         LEFT DOUBLE QUOTATION MARK\n\
+         4E00..9FFF;ID     # Lo [20992] CJK UNIFIED IDEOGRAPH-4E00..CJK UNIFIED IDEOGRAPH-9FFF\n\
+         1F1E6..1F1FF;RI   # So    [26] REGIONAL INDICATOR SYMBOL LETTER A..REGIONAL INDICATOR SYMBOL LETTER Z\n\
+         1F3FB..1F3FF;EM   # Sk     [5] EMOJI MODIFIER FITZPATRICK TYPE-1-2..EMOJI MODIFIER FITZPATRICK TYPE-6\n\
+         1F466;EB          # So         BOY\n",
+    ))
+    .expect("Line_Break data");
+    let widths = parse_east_asian_width(UcdFile::new(
+        EAST_ASIAN_WIDTH_TXT,
+        "# EastAsianWidth-16.0.0.txt\n\
+         # @missing: 0000..10FFFF; N\n\
+         4E00..9FFF     ; W  # Lo [20992] CJK UNIFIED IDEOGRAPH-4E00..CJK UNIFIED IDEOGRAPH-9FFF\n",
+    ))
+    .expect("East_Asian_Width");
+    let emoji = parse_emoji_data(UcdFile::new(EMOJI_DATA_TXT, "")).expect("emoji data");
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    (line_breaks, widths, emoji, table)
+}
+
+#[test]
+fn check_line_breaker() {
+    let (line_breaks, widths, emoji, table) = parse_breaker_test_data();
+    let breaker = LineBreaker::new(&line_breaks, &widths, &emoji, &table);
+    // The offset of each break opportunity.
+    let breaks = |string: &str| {
+        let string = string.chars().map(|c| c as u32).collect::<Vec<_>>();
+        breaker
+            .break_opportunities(&string)
+            .map(|opportunity| opportunity.offset)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(breaks(""), []);
+    assert_eq!(breaks("a b"), [2, 3]);
+    assert_eq!(breaks("a  b"), [3, 4]);
+    assert_eq!(
+        breaker
+            .break_opportunities(&[0x61, 0x0D, 0x0A, 0x62, 0x0A])
+            .collect::<Vec<_>>(),
+        [
+            BreakOpportunity {
+                offset: 3,
+                mandatory: true
+            },
+            BreakOpportunity {
+                offset: 5,
+                mandatory: true
+            }
+        ]
+    );
+    // LB9, LB10
+    assert_eq!(breaks("a\u{301} b"), [3, 4]);
+    assert_eq!(breaks(" \u{301}b"), [1, 3]);
+    // LB8
+    assert_eq!(breaks("a\u{200B} b"), [3, 4]);
+    // LB14, LB13
+    assert_eq!(breaks("(a) (b)"), [4, 7]);
+    // LB19
+    assert_eq!(breaks("\"a\" b"), [4, 5]);
+    // LB19a: initial quotation marks between East Asian characters.
+    assert_eq!(breaks("\u{4E00}\u{201C}\u{4E00}"), [1, 3]);
+    assert_eq!(breaks("a\u{201C}\u{4E00}"), [3]);
+    // LB21, LB20a
+    assert_eq!(breaks("a-b -c"), [2, 4, 6]);
+    assert_eq!(breaks("a\u{2010}b \u{2010}c"), [2, 4, 6]);
+    // LB21a
+    assert_eq!(breaks("\u{5D0}\u{2010}a"), [3]);
+    assert_eq!(breaks("\u{5D0}\u{2010}\u{5D1}"), [2, 3]);
+    // LB25
+    assert_eq!(breaks("$1,000% (-5)"), [8, 12]);
+    // LB31
+    assert_eq!(breaks("\u{4E00}\u{4E8C}"), [1, 2]);
+    assert_eq!(breaks("a\u{4E00}"), [1, 2]);
+    // LB30a
+    assert_eq!(
+        breaks("\u{1F1FA}\u{1F1F8}\u{1F1EC}\u{1F1E7}\u{1F1E6}"),
+        [2, 4, 5]
+    );
+    // LB30b
+    assert_eq!(breaks("\u{1F466}\u{1F3FB}\u{1F466}"), [2, 3]);
+}
+
+#[test]
+fn check_line_break_test_failures() {
+    let dir = crate::ucd_source::TestDirectory::new("check_line_break_test_failures");
+    dir.write(
+        LINE_BREAK_TEST_TXT,
+        "# LineBreakTest-16.0.0.txt\n\
+         × 0023 × 0020 ÷ 0023 ÷\t#  × [0.3] NUMBER SIGN (AL) × [7.01] SPACE (SP) ÷ [18.0] NUMBER SIGN (AL) ÷ [0.3]\n\
+         × 000A ÷ 0030 ÷\t#  × [0.3] <LINE FEED (LF)> (LF) ÷ [5.02] DIGIT ZERO (NU) ÷ [0.3]\n",
+    );
+    let source = dir.source();
+    let (line_breaks, widths, emoji, table) = parse_breaker_test_data();
+    let breaker = LineBreaker::new(&line_breaks, &widths, &emoji, &table);
+    assert_eq!(check_line_break_test(&source, &breaker).ok(), Some(2));
+
+    dir.write(
+        LINE_BREAK_TEST_TXT,
+        "× 0061 ÷ 0062 ÷\t#  a wrong expectation\n",
+    );
+    let error = check_line_break_test(&source, &breaker).expect_err("wrong breaks");
+    assert_eq!(
+        error.to_string(),
+        "auxiliary/LineBreakTest.txt:1: expected boundaries × 0061 ÷ 0062 ÷, \
+         found \"× 0061 × 0062 ÷\""
+    );
+}
+
+/// Check against `auxiliary/LineBreakTest.txt` and the other files in the
+/// Unicode 16.0 UCD directory named by the `UNICODE_INFO_UCD_DIR` environment
+/// variable.  Run with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs UNICODE_INFO_UCD_DIR"]
+fn check_line_break_conformance() {
+    let dir = std::env::var_os("UNICODE_INFO_UCD_DIR").expect("UNICODE_INFO_UCD_DIR is set");
+    let source = UcdSource::Directory(dir.into());
+    let line_breaks = crate::line_break::process_line_break(&source).expect("Line_Break data");
+    let widths =
+        crate::east_asian_width::process_east_asian_width(&source).expect("East_Asian_Width");
+    let emoji = crate::emoji::process_emoji_data(&source).expect("emoji data");
+    let table =
+        crate::code_point_table::generate_code_point_table(&source).expect("UnicodeData.txt");
+    let breaker = LineBreaker::new(&line_breaks, &widths, &emoji, &table);
+    let checked = check_line_break_test(&source, &breaker).expect("conformance");
+    assert!(checked > 0);
+}
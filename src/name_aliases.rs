@@ -0,0 +1,156 @@
+//! Processes `NameAliases.txt`, which lists formal aliases of code points:
+//! corrections of misleading names, names of control characters (which have
+//! no name in `UnicodeData.txt`), abbreviations, and so on.
+//!
+//! `NameAliases.txt` is embedded in this crate.  When a
+//! [`UcdSource::Directory`] doesn't contain it, code points have no aliases.
+//!
+//! See <https://www.unicode.org/reports/tr44/#NameAliases.txt> for details.
+
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+use std::collections::HashMap;
+
+/// The name of the file processed by this module.
+pub(crate) const NAME_ALIASES_TXT: &str = "NameAliases.txt";
+
+/// The type of a name alias.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AliasType {
+    /// A corrected name for a code point whose name contains a serious error,
+    /// e.g. PRESENTATION FORM FOR VERTICAL RIGHT WHITE LENTICULAR BRACKET for
+    /// U+FE18 (whose name misspells BRACKET as BRAKCET).
+    Correction,
+
+    /// An ISO 6429 name for a control character, e.g. CHARACTER TABULATION.
+    Control,
+
+    /// A widely used alternate name, e.g. BYTE ORDER MARK for U+FEFF.
+    Alternate,
+
+    /// A name documented but never actually standardized, e.g. HIGH OCTET
+    /// PRESET.
+    Figment,
+
+    /// A common abbreviation, e.g. NBSP.
+    Abbreviation,
+}
+
+/// Every alias type with its name in `NameAliases.txt`.
+const ALIAS_TYPES: [(AliasType, &str); 5] = [
+    (AliasType::Correction, "correction"),
+    (AliasType::Control, "control"),
+    (AliasType::Alternate, "alternate"),
+    (AliasType::Figment, "figment"),
+    (AliasType::Abbreviation, "abbreviation"),
+];
+
+impl AliasType {
+    /// Return an iterator over every alias type.
+    pub fn all() -> impl Iterator<Item = AliasType> {
+        ALIAS_TYPES.iter().map(|(alias_type, _)| *alias_type)
+    }
+
+    /// The name of this type in `NameAliases.txt`, e.g. "correction".
+    pub fn name(self) -> &'static str {
+        ALIAS_TYPES[self as usize].1
+    }
+
+    /// Return the alias type named `name` in `NameAliases.txt`.
+    pub fn from_name(name: &str) -> Option<AliasType> {
+        ALIAS_TYPES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(alias_type, _)| *alias_type)
+    }
+}
+
+/// A formal alias of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct NameAlias {
+    pub alias: &'static str,
+    pub alias_type: AliasType,
+}
+
+/// Parse the aliases of every code point from the `NameAliases.txt` in
+/// `source`, in the order the file lists them, or return no aliases if
+/// `source` has no `NameAliases.txt`.
+///
+/// Lines consist of a code point, an alias, and an alias type, e.g.
+/// `FEFF;BYTE ORDER MARK;alternate`.
+pub(crate) fn parse_name_aliases(
+    source: &UcdSource,
+) -> Result<HashMap<u32, Vec<NameAlias>>, UcdError> {
+    match source.read_optional(NAME_ALIASES_TXT)? {
+        Some(contents) => parse_aliases(UcdFile::new(NAME_ALIASES_TXT, contents)),
+        None => Ok(HashMap::new()),
+    }
+}
+
+fn parse_aliases(file: UcdFile) -> Result<HashMap<u32, Vec<NameAlias>>, UcdError> {
+    let mut aliases = HashMap::<u32, Vec<NameAlias>>::new();
+    for record in file.records() {
+        let record = record?;
+        let alias = record.field(0, "an alias")?;
+        let name = record.field(1, "an alias type")?;
+        let alias_type =
+            AliasType::from_name(name).ok_or_else(|| record.error(name, "an alias type"))?;
+        for code in record.range.iter() {
+            aliases
+                .entry(code)
+                .or_default()
+                .push(NameAlias { alias, alias_type });
+        }
+    }
+    Ok(aliases)
+}
+
+#[test]
+fn check_name_aliases() {
+    for (i, alias_type) in AliasType::all().enumerate() {
+        assert_eq!(
+            alias_type as usize, i,
+            "ALIAS_TYPES is in declaration order"
+        );
+        assert_eq!(AliasType::from_name(alias_type.name()), Some(alias_type));
+    }
+
+    let embedded = parse_name_aliases(&UcdSource::Embedded).expect("NameAliases.txt");
+    assert_eq!(
+        embedded[&0x0009][0],
+        NameAlias {
+            alias: "CHARACTER TABULATION",
+            alias_type: AliasType::Control
+        }
+    );
+    let dir = crate::ucd_source::TestDirectory::new("check_name_aliases");
+    assert!(parse_name_aliases(&dir.source())
+        .expect("no NameAliases.txt")
+        .is_empty());
+
+    let file = UcdFile::new(
+        NAME_ALIASES_TXT,
+        "# NameAliases-13.0.0.txt\n\
+         0009;CHARACTER TABULATION;control\n\
+         0009;HORIZONTAL TABULATION;control\n\
+         0009;HT;abbreviation\n\
+         0009;TAB;abbreviation\n",
+    );
+    let aliases = parse_aliases(file).expect("NameAliases.txt");
+    assert_eq!(aliases[&0x0009].len(), 4);
+    assert_eq!(
+        aliases[&0x0009][3],
+        NameAlias {
+            alias: "TAB",
+            alias_type: AliasType::Abbreviation
+        }
+    );
+
+    let error = parse_aliases(UcdFile::new(NAME_ALIASES_TXT, "0009;TAB;nickname\n"))
+        .expect_err("bad alias type");
+    assert_eq!(
+        error.to_string(),
+        "NameAliases.txt:1: expected an alias type, found \"nickname\""
+    );
+}
@@ -0,0 +1,293 @@
+//! Character names that Unicode derives algorithmically rather than listing in
+//! `UnicodeData.txt` (Hangul syllables, and ideographs named by their code
+//! point), named character sequences from `NamedSequences.txt`, and the index
+//! by which [`CodePointTable::lookup_name`] finds the code point with a name.
+//!
+//! `UnicodeData.txt` lists algorithmically named code points as ranges (e.g.
+//! `<CJK Ideograph, First>` through `<CJK Ideograph, Last>`), whose labels
+//! aren't names.  See section 4.8 of the Unicode Standard, and in particular
+//! its table 4-8, "Name Derivation Rule Prefix Strings".
+
+use crate::code_point_table::CodePointTable;
+use crate::normalization::{self, HANGUL_L_BASE, HANGUL_T_BASE, HANGUL_V_BASE};
+use crate::ucd_error::UcdError;
+use crate::ucd_file::DataLines;
+use crate::ucd_source::UcdSource;
+use std::collections::HashMap;
+
+/// The name of the file listing named character sequences.
+pub(crate) const NAMED_SEQUENCES_TXT: &str = "NamedSequences.txt";
+
+/// The Jamo_Short_Name of each leading consonant jamo, from U+1100 HANGUL
+/// CHOSEONG KIYEOK.
+const JAMO_L_NAMES: [&str; 19] = [
+    "G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T", "P",
+    "H",
+];
+
+/// The Jamo_Short_Name of each vowel jamo, from U+1161 HANGUL JUNGSEONG A.
+const JAMO_V_NAMES: [&str; 21] = [
+    "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WEO", "WE",
+    "WI", "YU", "EU", "YI", "I",
+];
+
+/// The Jamo_Short_Name of each trailing consonant jamo, from U+11A7 (which
+/// isn't a jamo: it stands for the absence of a trailing consonant).
+const JAMO_T_NAMES: [&str; 28] = [
+    "", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP", "LH", "M",
+    "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H",
+];
+
+/// The labels of `UnicodeData.txt` ranges whose code points are named by a
+/// prefix followed by their code point in hexadecimal, and that prefix.  A
+/// label matches a range if it begins the range's label, e.g. "CJK
+/// Ideograph" matches "CJK Ideograph Extension A".
+///
+/// Khitan small script characters and Nushu characters are named the same
+/// way, but `UnicodeData.txt` lists them individually, names included, so
+/// they need no entry here.
+const CODE_POINT_NAMED_RANGES: [(&str, &str); 2] = [
+    ("CJK Ideograph", "CJK UNIFIED IDEOGRAPH-"),
+    ("Tangut Ideograph", "TANGUT IDEOGRAPH-"),
+];
+
+/// The label of the `UnicodeData.txt` range of Hangul syllables.
+const HANGUL_SYLLABLE_RANGE: &str = "Hangul Syllable";
+
+/// The name of the Hangul syllable `code`, e.g. "HANGUL SYLLABLE GAG" for
+/// U+AC01, or `None` if it's not a Hangul syllable.
+pub fn hangul_syllable_name(code: u32) -> Option<String> {
+    let jamo = normalization::decompose_hangul(code)?;
+    let mut name = String::from("HANGUL SYLLABLE ");
+    name.push_str(JAMO_L_NAMES[(jamo[0] - HANGUL_L_BASE) as usize]);
+    name.push_str(JAMO_V_NAMES[(jamo[1] - HANGUL_V_BASE) as usize]);
+    if let Some(t) = jamo.get(2) {
+        name.push_str(JAMO_T_NAMES[(t - HANGUL_T_BASE) as usize]);
+    }
+    Some(name)
+}
+
+/// The algorithmically derived name of `code`, whose `UnicodeData.txt` range
+/// has the label `range_label` (e.g. "CJK Ideograph Extension A"), or `None`
+/// if code points in that range aren't named algorithmically.  (Code points
+/// in surrogate and private use ranges have no names.)
+pub(crate) fn algorithmic_name(code: u32, range_label: &str) -> Option<String> {
+    if range_label == HANGUL_SYLLABLE_RANGE {
+        return hangul_syllable_name(code);
+    }
+    CODE_POINT_NAMED_RANGES
+        .iter()
+        .find(|(label, _)| range_label.starts_with(label))
+        .map(|(_, prefix)| format!("{}{:04X}", prefix, code))
+}
+
+/// Return the key by which `name` is compared to other character names under
+/// UAX44-LM2 loose matching: `name` uppercased, without whitespace,
+/// underscores, or medial hyphens (hyphens between two letters or digits).
+///
+/// The hyphen between the O and the E of U+1180 HANGUL JUNGSEONG O-E is
+/// kept, to distinguish it from U+116C HANGUL JUNGSEONG OE.
+///
+/// # Example
+///
+/// ```
+/// # use unicode_info::names::name_matching_key;
+/// assert_eq!(name_matching_key("Zero-width space"), "ZEROWIDTHSPACE");
+/// assert_eq!(name_matching_key("TIBETAN LETTER -A"), "TIBETANLETTER-A");
+/// assert_eq!(name_matching_key("hangul jungseong o-e"), "HANGULJUNGSEONGO-E");
+/// assert_eq!(name_matching_key("hangul-jungseong oe"), "HANGULJUNGSEONGOE");
+/// ```
+pub fn name_matching_key(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut key = String::with_capacity(name.len());
+    // The length of `key` when each medial hyphen was removed.
+    let mut removed_hyphens = vec![];
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() || c == '_' {
+            continue;
+        }
+        if c == '-'
+            && i > 0
+            && chars[i - 1].is_alphanumeric()
+            && chars.get(i + 1).is_some_and(|next| next.is_alphanumeric())
+        {
+            removed_hyphens.push(key.len());
+            continue;
+        }
+        key.extend(c.to_uppercase());
+    }
+    if key == "HANGULJUNGSEONGOE" && removed_hyphens.contains(&(key.len() - 1)) {
+        key.insert(key.len() - 1, '-');
+    }
+    key
+}
+
+/// Parse the named character sequences in the `NamedSequences.txt` in
+/// `source`, or return none if `source` has no `NamedSequences.txt`.
+///
+/// Lines consist of a name and a space-separated code point sequence, e.g.
+/// `LATIN CAPITAL LETTER A WITH MACRON AND GRAVE;0100 0300`.
+pub(crate) fn parse_named_sequences(
+    source: &UcdSource,
+) -> Result<Vec<(&'static str, Vec<u32>)>, UcdError> {
+    match source.read_optional(NAMED_SEQUENCES_TXT)? {
+        Some(contents) => parse_named_sequence_lines(DataLines::new(contents)),
+        None => Ok(vec![]),
+    }
+}
+
+fn parse_named_sequence_lines(lines: DataLines) -> Result<Vec<(&'static str, Vec<u32>)>, UcdError> {
+    let mut sequences = vec![];
+    for line in lines {
+        let error =
+            |text: &str, expected| UcdError::parse(NAMED_SEQUENCES_TXT, line.line, text, expected);
+        if line.fields.len() != 2 {
+            return Err(error(
+                &line.fields.join(";"),
+                "a name and a code point sequence",
+            ));
+        }
+        let codes = line.fields[1];
+        let sequence = codes
+            .split_whitespace()
+            .map(|code| u32::from_str_radix(code, 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| error(codes, "a sequence of hexadecimal code points"))?;
+        if sequence.len() < 2 {
+            return Err(error(codes, "a sequence of hexadecimal code points"));
+        }
+        sequences.push((line.fields[0], sequence));
+    }
+    Ok(sequences)
+}
+
+/// An index of every name and alias of every code point, and of every named
+/// character sequence, by name matching key.
+pub(crate) struct NameIndex {
+    code_points: HashMap<String, u32>,
+    sequences: HashMap<String, Vec<u32>>,
+}
+
+impl NameIndex {
+    /// Index the names in `table`: the names in `UnicodeData.txt`,
+    /// algorithmically derived names, aliases, and named sequences.
+    pub(crate) fn new(table: &CodePointTable) -> NameIndex {
+        let mut code_points = HashMap::new();
+        for code_point in table.iter() {
+            let code = code_point.code;
+            let name = code_point.name();
+            // Character names are uppercase, unlike the labels of ranges
+            // (e.g. "Private Use") and of code points with no name (e.g.
+            // "<control>").
+            if let Some(name) = algorithmic_name(code, name) {
+                code_points.insert(name_matching_key(&name), code);
+            } else if !name.starts_with('<') && !name.contains(|c: char| c.is_ascii_lowercase()) {
+                code_points.insert(name_matching_key(name), code);
+            }
+            for alias in table.aliases(code) {
+                code_points
+                    .entry(name_matching_key(alias.alias))
+                    .or_insert(code);
+            }
+        }
+
+        let sequences = table
+            .named_sequences()
+            .iter()
+            .map(|(name, sequence)| (name_matching_key(name), sequence.clone()))
+            .collect();
+
+        NameIndex {
+            code_points,
+            sequences,
+        }
+    }
+
+    /// The code point named `name`, compared by name matching key.
+    pub(crate) fn code_point(&self, name: &str) -> Option<u32> {
+        self.code_points.get(&name_matching_key(name)).copied()
+    }
+
+    /// The named sequence named `name`, compared by name matching key.
+    pub(crate) fn sequence(&self, name: &str) -> Option<&[u32]> {
+        self.sequences
+            .get(&name_matching_key(name))
+            .map(|sequence| sequence.as_slice())
+    }
+}
+
+#[test]
+fn check_algorithmic_names() {
+    assert_eq!(
+        hangul_syllable_name(0xAC00).as_deref(),
+        Some("HANGUL SYLLABLE GA")
+    );
+    assert_eq!(
+        hangul_syllable_name(0xAC01).as_deref(),
+        Some("HANGUL SYLLABLE GAG")
+    );
+    // U+C544 has the empty leading consonant name.
+    assert_eq!(
+        hangul_syllable_name(0xC544).as_deref(),
+        Some("HANGUL SYLLABLE A")
+    );
+    assert_eq!(
+        hangul_syllable_name(0xD7A3).as_deref(),
+        Some("HANGUL SYLLABLE HIH")
+    );
+    assert_eq!(hangul_syllable_name(0xD7A4), None);
+    assert_eq!(hangul_syllable_name(0x1100), None);
+
+    assert_eq!(
+        algorithmic_name(0x3400, "CJK Ideograph Extension A").as_deref(),
+        Some("CJK UNIFIED IDEOGRAPH-3400")
+    );
+    assert_eq!(
+        algorithmic_name(0x20000, "CJK Ideograph Extension B").as_deref(),
+        Some("CJK UNIFIED IDEOGRAPH-20000")
+    );
+    assert_eq!(
+        algorithmic_name(0x18D00, "Tangut Ideograph Supplement").as_deref(),
+        Some("TANGUT IDEOGRAPH-18D00")
+    );
+    assert_eq!(
+        algorithmic_name(0xAC01, HANGUL_SYLLABLE_RANGE).as_deref(),
+        Some("HANGUL SYLLABLE GAG")
+    );
+    assert_eq!(
+        algorithmic_name(0xD800, "Non Private Use High Surrogate"),
+        None
+    );
+    assert_eq!(algorithmic_name(0xE000, "Private Use"), None);
+}
+
+#[test]
+fn check_named_sequences() {
+    let lines = DataLines::new(
+        "# NamedSequences-13.0.0.txt\n\
+         LATIN CAPITAL LETTER A WITH MACRON AND GRAVE;0100 0300\n\
+         KEYCAP NUMBER SIGN;0023 FE0F 20E3\n",
+    );
+    let sequences = parse_named_sequence_lines(lines).expect("named sequences");
+    assert_eq!(
+        sequences,
+        [
+            (
+                "LATIN CAPITAL LETTER A WITH MACRON AND GRAVE",
+                vec![0x0100, 0x0300]
+            ),
+            ("KEYCAP NUMBER SIGN", vec![0x0023, 0xFE0F, 0x20E3]),
+        ]
+    );
+    assert_eq!(
+        parse_named_sequences(&UcdSource::Embedded).ok(),
+        Some(vec![])
+    );
+
+    let error = parse_named_sequence_lines(DataLines::new("KEYCAP NUMBER SIGN;0023\n"))
+        .expect_err("single code point");
+    assert_eq!(
+        error.to_string(),
+        "NamedSequences.txt:1: expected a sequence of hexadecimal code points, found \"0023\""
+    );
+}
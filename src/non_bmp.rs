@@ -4,9 +4,12 @@
 use crate::code_point_table;
 use crate::constants::MAX_BMP;
 use crate::derived_core_properties;
+use crate::general_category::GeneralCategory;
 use crate::types::{CaseMap, CodePointSet};
+use crate::ucd_error::UcdError;
+use crate::ucd_source::UcdSource;
 
-use derived_core_properties::DerivedCorePropertyData;
+use derived_core_properties::DerivedProperty;
 
 /// Information about various categories and mappings of non-BMP code point.
 ///
@@ -36,11 +39,11 @@ pub struct NonBMPInfo {
 
 /// Generate various information about code points outside the base multilingual
 /// plane: code points that can't be represented in a single UTF-16 code unit.
-pub fn generate_non_bmp_info(code_point_table: &code_point_table::CodePointTable) -> NonBMPInfo {
-    let DerivedCorePropertyData {
-        id_start: derived_id_start,
-        id_continue: derived_id_continue,
-    } = derived_core_properties::process_derived_core_properties();
+pub fn generate_non_bmp_info(
+    source: &UcdSource,
+    code_point_table: &code_point_table::CodePointTable,
+) -> Result<NonBMPInfo, UcdError> {
+    let dcp = derived_core_properties::process_derived_core_properties(source)?;
 
     let mut lowercase_map = CaseMap::new();
     let mut uppercase_map = CaseMap::new();
@@ -73,30 +76,31 @@ pub fn generate_non_bmp_info(code_point_table: &code_point_table::CodePointTable
             );
             uppercase_map.insert(code, upper);
         }
-        if code_point.category() == "Zs" {
+        if code_point.general_category() == GeneralCategory::SpaceSeparator {
             space_set.insert(code);
         }
-        if derived_id_start.contains(&code) {
+        if dcp.contains(DerivedProperty::IdStart, code) {
             id_start_set.insert(code);
         }
-        if derived_id_continue.contains(&code) {
+        if dcp.contains(DerivedProperty::IdContinue, code) {
             id_continue_set.insert(code);
         }
     }
 
-    NonBMPInfo {
+    Ok(NonBMPInfo {
         lowercase_map,
         uppercase_map,
         space_set,
         id_start_set,
         id_continue_set,
-    }
+    })
 }
 
 #[test]
 fn non_bmp_lowercase_map() {
-    let table = code_point_table::generate_code_point_table();
-    let non_bmp_info = generate_non_bmp_info(&table);
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
+    let non_bmp_info = generate_non_bmp_info(&UcdSource::Embedded, &table).expect("embedded data");
 
     assert_eq!(
         table.full_name(0x10403),
@@ -118,7 +122,8 @@ const OLD_PERSIAN_SIGN_AURAMAZDAA: u32 = 0x103C8;
 
 #[test]
 fn check_constant_code_point_values() {
-    let table = code_point_table::generate_code_point_table();
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
     assert_eq!(
         table.full_name(DESERET_SMALL_LETTER_LONG_AH),
         "U+1042B DESERET SMALL LETTER LONG AH"
@@ -135,8 +140,9 @@ fn check_constant_code_point_values() {
 
 #[test]
 fn non_bmp_uppercase_map() {
-    let table = code_point_table::generate_code_point_table();
-    let non_bmp_info = generate_non_bmp_info(&table);
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
+    let non_bmp_info = generate_non_bmp_info(&UcdSource::Embedded, &table).expect("embedded data");
 
     assert_eq!(
         non_bmp_info
@@ -148,8 +154,9 @@ fn non_bmp_uppercase_map() {
 
 #[test]
 fn non_bmp_space_set_is_empty() {
-    let table = code_point_table::generate_code_point_table();
-    let non_bmp_info = generate_non_bmp_info(&table);
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
+    let non_bmp_info = generate_non_bmp_info(&UcdSource::Embedded, &table).expect("embedded data");
     assert!(
         non_bmp_info.space_set.is_empty(),
         "js::unicode::IsSpace(char32) is defined assuming there are no non-BMP \
@@ -159,9 +166,10 @@ fn non_bmp_space_set_is_empty() {
 
 #[test]
 fn non_bmp_identifier_start() {
-    let table = code_point_table::generate_code_point_table();
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
 
-    let non_bmp_info = generate_non_bmp_info(&table);
+    let non_bmp_info = generate_non_bmp_info(&UcdSource::Embedded, &table).expect("embedded data");
 
     assert!(
         non_bmp_info
@@ -173,14 +181,15 @@ fn non_bmp_identifier_start() {
 
 #[test]
 fn non_bmp_identifier_continue() {
-    let table = code_point_table::generate_code_point_table();
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
 
     assert_eq!(
         table.name(OLD_PERSIAN_SIGN_AURAMAZDAA),
         "OLD PERSIAN SIGN AURAMAZDAA"
     );
 
-    let non_bmp_info = generate_non_bmp_info(&table);
+    let non_bmp_info = generate_non_bmp_info(&UcdSource::Embedded, &table).expect("embedded data");
 
     assert!(
         non_bmp_info
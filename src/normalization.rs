@@ -0,0 +1,661 @@
+//! Unicode normalization: decomposition and composition tables built from
+//! `UnicodeData.txt` and `CompositionExclusions.txt`, quick-check values from
+//! `DerivedNormalizationProps.txt`, and a reference implementation of the
+//! NFC, NFD, NFKC, and NFKD normalization forms, as needed by
+//! `String.prototype.normalize`.
+//!
+//! `CompositionExclusions.txt` and `DerivedNormalizationProps.txt` aren't
+//! embedded in this crate, so they must be read from a
+//! [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr15/> for details.
+
+use crate::code_point_table::CodePointTable;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{DataLines, Entry, UcdFile};
+use crate::ucd_source::UcdSource;
+use std::collections::HashMap;
+
+/// The name of the file listing code points excluded from composition.
+pub(crate) const COMPOSITION_EXCLUSIONS_TXT: &str = "CompositionExclusions.txt";
+
+/// The name of the file listing derived normalization properties.
+pub(crate) const DERIVED_NORMALIZATION_PROPS_TXT: &str = "DerivedNormalizationProps.txt";
+
+/// The name of the normalization conformance test file.
+pub(crate) const NORMALIZATION_TEST_TXT: &str = "NormalizationTest.txt";
+
+// Constants for the algorithmic decomposition and composition of Hangul
+// syllables.  See section 3.12 of the Unicode Standard.
+const HANGUL_S_BASE: u32 = 0xAC00;
+pub(crate) const HANGUL_L_BASE: u32 = 0x1100;
+pub(crate) const HANGUL_V_BASE: u32 = 0x1161;
+pub(crate) const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_L_COUNT: u32 = 19;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = HANGUL_L_COUNT * HANGUL_N_COUNT;
+
+/// A Unicode normalization form.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Form {
+    /// Canonical decomposition followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Form {
+    /// Return an iterator over every normalization form.
+    pub fn all() -> impl Iterator<Item = Form> {
+        [Form::Nfc, Form::Nfd, Form::Nfkc, Form::Nfkd]
+            .iter()
+            .copied()
+    }
+
+    /// The name of this form, e.g. "NFKC".
+    pub fn name(self) -> &'static str {
+        match self {
+            Form::Nfc => "NFC",
+            Form::Nfd => "NFD",
+            Form::Nfkc => "NFKC",
+            Form::Nfkd => "NFKD",
+        }
+    }
+
+    fn is_compatibility(self) -> bool {
+        matches!(self, Form::Nfkc | Form::Nfkd)
+    }
+
+    fn is_composed(self) -> bool {
+        matches!(self, Form::Nfc | Form::Nfkc)
+    }
+}
+
+/// A normalization quick-check value, e.g. of the NFC_Quick_Check property.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum QuickCheck {
+    /// The code point can occur in the normalization form.
+    Yes,
+    /// The code point can't occur in the normalization form.
+    No,
+    /// Whether the code point can occur in the normalization form depends on
+    /// its context.  (Only the composed forms have this value.)
+    Maybe,
+}
+
+/// Decomposition and composition tables and quick-check values.
+pub struct NormalizationData {
+    /// The canonical combining class of every code point with a non-zero
+    /// class.
+    combining_classes: HashMap<u32, u8>,
+
+    /// The full (recursively applied) canonical decomposition of every code
+    /// point with one, excluding Hangul syllables.
+    canonical: HashMap<u32, Vec<u32>>,
+
+    /// The full compatibility decomposition of every code point whose
+    /// compatibility decomposition differs from its canonical decomposition.
+    compatibility: HashMap<u32, Vec<u32>>,
+
+    /// The primary composite of each pair of code points that canonically
+    /// compose, excluding Hangul syllables.
+    compositions: HashMap<(u32, u32), u32>,
+
+    /// Every code point with the Full_Composition_Exclusion property.
+    full_composition_exclusions: CodePointSet,
+
+    /// The quick-check value of every code point whose value isn't `Yes`,
+    /// indexed by `Form`.
+    quick_checks: Vec<HashMap<u32, QuickCheck>>,
+}
+
+impl NormalizationData {
+    /// The canonical combining class of `code`.
+    pub fn combining_class(&self, code: u32) -> u8 {
+        self.combining_classes.get(&code).copied().unwrap_or(0)
+    }
+
+    /// The full canonical decomposition of `code`, or `None` if it has no
+    /// canonical decomposition.  Hangul syllables decompose algorithmically.
+    pub fn canonical_decomposition(&self, code: u32) -> Option<Vec<u32>> {
+        if let Some(jamo) = decompose_hangul(code) {
+            return Some(jamo);
+        }
+        self.canonical.get(&code).cloned()
+    }
+
+    /// The full compatibility decomposition of `code`, or `None` if it has
+    /// neither a compatibility nor a canonical decomposition.
+    pub fn compatibility_decomposition(&self, code: u32) -> Option<Vec<u32>> {
+        match self.compatibility.get(&code) {
+            Some(decomposition) => Some(decomposition.clone()),
+            None => self.canonical_decomposition(code),
+        }
+    }
+
+    /// The primary composite that `first` followed by `second` canonically
+    /// composes to, if any.
+    pub fn composition(&self, first: u32, second: u32) -> Option<u32> {
+        if let Some(syllable) = compose_hangul(first, second) {
+            return Some(syllable);
+        }
+        self.compositions.get(&(first, second)).copied()
+    }
+
+    /// Whether `code` has the Full_Composition_Exclusion property: it's never
+    /// the result of composition, despite having a canonical decomposition.
+    pub fn is_composition_excluded(&self, code: u32) -> bool {
+        self.full_composition_exclusions.contains(&code)
+    }
+
+    /// The value of the quick-check property for `form` (e.g. NFC_QC) of
+    /// `code`.
+    pub fn quick_check(&self, form: Form, code: u32) -> QuickCheck {
+        self.quick_checks[form as usize]
+            .get(&code)
+            .copied()
+            .unwrap_or(QuickCheck::Yes)
+    }
+
+    /// Return the normalization of `codes` to `form`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use unicode_info::code_point_table::generate_code_point_table;
+    /// # use unicode_info::normalization::*;
+    /// # use unicode_info::ucd_source::UcdSource;
+    /// let source = UcdSource::Directory("ucd".into());
+    /// let table = generate_code_point_table(&source).expect("UnicodeData.txt");
+    /// let data = process_normalization(&source, &table).expect("normalization data");
+    /// // U+212B ANGSTROM SIGN
+    /// assert_eq!(data.normalize(&[0x212B], Form::Nfd), [0x0041, 0x030A]);
+    /// assert_eq!(data.normalize(&[0x212B], Form::Nfc), [0x00C5]);
+    /// ```
+    pub fn normalize(&self, codes: &[u32], form: Form) -> Vec<u32> {
+        let decomposed = self.decompose(codes, form.is_compatibility());
+        if form.is_composed() {
+            self.compose(&decomposed)
+        } else {
+            decomposed
+        }
+    }
+
+    /// Fully decompose `codes` and put the result in canonical order.
+    fn decompose(&self, codes: &[u32], compatibility: bool) -> Vec<u32> {
+        let mut decomposed = Vec::with_capacity(codes.len());
+        for code in codes {
+            let decomposition = if compatibility {
+                self.compatibility_decomposition(*code)
+            } else {
+                self.canonical_decomposition(*code)
+            };
+            match decomposition {
+                Some(decomposition) => decomposed.extend(decomposition),
+                None => decomposed.push(*code),
+            }
+        }
+
+        // The Canonical Ordering Algorithm: stably sort every run of
+        // non-starters by combining class.
+        let mut start = 0;
+        while start < decomposed.len() {
+            if self.combining_class(decomposed[start]) == 0 {
+                start += 1;
+                continue;
+            }
+            let end = decomposed[start..]
+                .iter()
+                .position(|code| self.combining_class(*code) == 0)
+                .map_or(decomposed.len(), |i| start + i);
+            decomposed[start..end].sort_by_key(|code| self.combining_class(*code));
+            start = end;
+        }
+
+        decomposed
+    }
+
+    /// Apply the Canonical Composition Algorithm to `decomposed`, a fully
+    /// decomposed and canonically ordered sequence.
+    fn compose(&self, decomposed: &[u32]) -> Vec<u32> {
+        let mut composed = Vec::with_capacity(decomposed.len());
+
+        // The index in `composed` of the last starter, and the combining
+        // class of the last code point appended after it.
+        let mut starter: Option<usize> = None;
+        let mut last_class = 0;
+
+        for code in decomposed {
+            let class = self.combining_class(*code);
+            if let Some(starter) = starter {
+                // A code point is blocked from the starter by any intervening
+                // code point whose combining class is zero or at least its
+                // own.
+                let adjacent = composed.len() == starter + 1;
+                if adjacent || last_class < class {
+                    if let Some(composite) = self.composition(composed[starter], *code) {
+                        composed[starter] = composite;
+                        continue;
+                    }
+                }
+            }
+
+            if class == 0 {
+                starter = Some(composed.len());
+            }
+            last_class = class;
+            composed.push(*code);
+        }
+
+        composed
+    }
+}
+
+/// The decomposition of the Hangul syllable `code` into jamo, or `None` if
+/// it's not a Hangul syllable.
+pub(crate) fn decompose_hangul(code: u32) -> Option<Vec<u32>> {
+    let index = code.checked_sub(HANGUL_S_BASE)?;
+    if index >= HANGUL_S_COUNT {
+        return None;
+    }
+    let l = HANGUL_L_BASE + index / HANGUL_N_COUNT;
+    let v = HANGUL_V_BASE + (index % HANGUL_N_COUNT) / HANGUL_T_COUNT;
+    let t = HANGUL_T_BASE + index % HANGUL_T_COUNT;
+    if t == HANGUL_T_BASE {
+        Some(vec![l, v])
+    } else {
+        Some(vec![l, v, t])
+    }
+}
+
+/// The Hangul syllable that `first` followed by `second` composes to, if
+/// they're a leading and vowel jamo or an LV syllable and trailing jamo.
+fn compose_hangul(first: u32, second: u32) -> Option<u32> {
+    let l_index = first.wrapping_sub(HANGUL_L_BASE);
+    let v_index = second.wrapping_sub(HANGUL_V_BASE);
+    if l_index < HANGUL_L_COUNT && v_index < HANGUL_V_COUNT {
+        return Some(HANGUL_S_BASE + (l_index * HANGUL_V_COUNT + v_index) * HANGUL_T_COUNT);
+    }
+
+    let s_index = first.wrapping_sub(HANGUL_S_BASE);
+    let t_index = second.wrapping_sub(HANGUL_T_BASE);
+    if s_index < HANGUL_S_COUNT
+        && s_index.is_multiple_of(HANGUL_T_COUNT)
+        && 0 < t_index
+        && t_index < HANGUL_T_COUNT
+    {
+        return Some(first + t_index);
+    }
+
+    None
+}
+
+/// Build the normalization tables from the decompositions and combining
+/// classes in `table` (from the `UnicodeData.txt` in `source`) and the
+/// `CompositionExclusions.txt` and `DerivedNormalizationProps.txt` in
+/// `source`.
+pub fn process_normalization(
+    source: &UcdSource,
+    table: &CodePointTable,
+) -> Result<NormalizationData, UcdError> {
+    parse_normalization(
+        table,
+        UcdFile::read(source, COMPOSITION_EXCLUSIONS_TXT)?,
+        UcdFile::read(source, DERIVED_NORMALIZATION_PROPS_TXT)?,
+    )
+}
+
+fn parse_normalization(
+    table: &CodePointTable,
+    exclusions_file: UcdFile,
+    properties_file: UcdFile,
+) -> Result<NormalizationData, UcdError> {
+    let mut combining_classes = HashMap::new();
+    let mut single_canonical = HashMap::new();
+    let mut single_compatibility = HashMap::new();
+    for code_point in table.iter() {
+        if code_point.combining_class() != 0 {
+            combining_classes.insert(code_point.code, code_point.combining_class());
+        }
+        if let Some(decomposition) = code_point.decomposition() {
+            if decomposition.is_canonical() {
+                single_canonical.insert(code_point.code, decomposition.mapping);
+            } else {
+                single_compatibility.insert(code_point.code, decomposition.mapping);
+            }
+        }
+    }
+
+    // Apply decompositions recursively, so that each table maps to a fully
+    // decomposed sequence.
+    fn full_decomposition(code: u32, maps: &[&HashMap<u32, Vec<u32>>], decomposed: &mut Vec<u32>) {
+        match maps.iter().find_map(|map| map.get(&code)) {
+            Some(mapping) => {
+                for code in mapping {
+                    full_decomposition(*code, maps, decomposed);
+                }
+            }
+            None => match decompose_hangul(code) {
+                Some(jamo) => decomposed.extend(jamo),
+                None => decomposed.push(code),
+            },
+        }
+    }
+
+    let canonical = single_canonical
+        .keys()
+        .map(|code| {
+            let mut decomposed = vec![];
+            full_decomposition(*code, &[&single_canonical], &mut decomposed);
+            (*code, decomposed)
+        })
+        .collect::<HashMap<_, _>>();
+    let compatibility = single_compatibility
+        .keys()
+        .chain(single_canonical.keys())
+        .filter_map(|code| {
+            let mut decomposed = vec![];
+            full_decomposition(
+                *code,
+                &[&single_compatibility, &single_canonical],
+                &mut decomposed,
+            );
+            if canonical.get(code) == Some(&decomposed) {
+                None
+            } else {
+                Some((*code, decomposed))
+            }
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Full_Composition_Exclusion consists of the code points listed in
+    // `CompositionExclusions.txt`, singleton decompositions, and
+    // decompositions of or starting with a non-starter.
+    let mut full_composition_exclusions = CodePointSet::new();
+    for record in exclusions_file.records() {
+        full_composition_exclusions.extend(record?.range);
+    }
+    let class = |code: &u32| combining_classes.get(code).copied().unwrap_or(0);
+    for (code, mapping) in &single_canonical {
+        if mapping.len() == 1 || class(code) != 0 || class(&mapping[0]) != 0 {
+            full_composition_exclusions.insert(*code);
+        }
+    }
+
+    let compositions = single_canonical
+        .iter()
+        .filter(|(code, mapping)| {
+            mapping.len() == 2 && !full_composition_exclusions.contains(*code)
+        })
+        .map(|(code, mapping)| ((mapping[0], mapping[1]), *code))
+        .collect::<HashMap<_, _>>();
+
+    let mut quick_checks = vec![HashMap::new(); 4];
+    for entry in properties_file {
+        // `@missing` lines only specify the default, `Yes`.
+        let record = match entry? {
+            Entry::Record(record) => record,
+            Entry::Missing(_) => continue,
+        };
+        let form = match record.field(0, "a property name")? {
+            "NFC_QC" => Form::Nfc,
+            "NFD_QC" => Form::Nfd,
+            "NFKC_QC" => Form::Nfkc,
+            "NFKD_QC" => Form::Nfkd,
+            _ => continue,
+        };
+        let value = match record.field(1, "a quick-check value")? {
+            "Y" => QuickCheck::Yes,
+            "N" => QuickCheck::No,
+            "M" => QuickCheck::Maybe,
+            value => return Err(record.error(value, "Y, N, or M")),
+        };
+        quick_checks[form as usize].extend(record.range.iter().map(|code| (code, value)));
+    }
+
+    Ok(NormalizationData {
+        combining_classes,
+        canonical,
+        compatibility,
+        compositions,
+        full_composition_exclusions,
+        quick_checks,
+    })
+}
+
+/// Check `data` against the `NormalizationTest.txt` in `source`, returning
+/// the number of test lines checked, or an error describing the first
+/// failure.
+///
+/// Each test line lists five sequences `c1` through `c5`, such that `c2` is
+/// the NFC form of `c1`, `c3` its NFD form, `c4` its NFKC form, and `c5` its
+/// NFKD form.  Additionally, every code point not listed in part 1 of the
+/// file must be unchanged by every normalization form.
+pub fn check_normalization_test(
+    source: &UcdSource,
+    data: &NormalizationData,
+) -> Result<usize, UcdError> {
+    check_normalization_test_lines(DataLines::new(source.read(NORMALIZATION_TEST_TXT)?), data)
+}
+
+fn check_normalization_test_lines(
+    lines: DataLines,
+    data: &NormalizationData,
+) -> Result<usize, UcdError> {
+    let parse = |line: usize, field: &str| -> Result<Vec<u32>, UcdError> {
+        field
+            .split_whitespace()
+            .map(|code| u32::from_str_radix(code, 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                UcdError::parse(
+                    NORMALIZATION_TEST_TXT,
+                    line,
+                    field,
+                    "a sequence of hexadecimal code points",
+                )
+            })
+    };
+    let to_hex = |codes: &[u32]| {
+        codes
+            .iter()
+            .map(|code| format!("{:04X}", code))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut checked = 0;
+    let mut part = "";
+    let mut part1_codes = CodePointSet::new();
+    for line in lines {
+        if line.fields[0].starts_with('@') {
+            part = line.fields[0];
+            continue;
+        }
+        if line.fields.len() != 5 {
+            return Err(UcdError::parse(
+                NORMALIZATION_TEST_TXT,
+                line.line,
+                &line.fields.join(";"),
+                "five semicolon-separated fields",
+            ));
+        }
+        let c = line
+            .fields
+            .iter()
+            .map(|field| parse(line.line, field))
+            .collect::<Result<Vec<_>, _>>()?;
+        if part == "@Part1" {
+            part1_codes.extend(c[0].iter().copied());
+        }
+
+        // For each form, the column holding the expected result of
+        // normalizing each column.
+        let expectations = [
+            (Form::Nfc, [1, 1, 1, 3, 3]),
+            (Form::Nfd, [2, 2, 2, 4, 4]),
+            (Form::Nfkc, [3, 3, 3, 3, 3]),
+            (Form::Nfkd, [4, 4, 4, 4, 4]),
+        ];
+        for (form, expected) in expectations.iter() {
+            for (column, expected) in expected.iter().enumerate() {
+                let normalized = data.normalize(&c[column], *form);
+                if normalized != c[*expected] {
+                    return Err(UcdError::parse(
+                        NORMALIZATION_TEST_TXT,
+                        line.line,
+                        &to_hex(&normalized),
+                        format!(
+                            "{}(c{}) = c{} = {}",
+                            form.name(),
+                            column + 1,
+                            expected + 1,
+                            to_hex(&c[*expected])
+                        ),
+                    ));
+                }
+            }
+        }
+        checked += 1;
+    }
+
+    for code in 0..=crate::constants::MAX_CODE_POINT {
+        if part1_codes.contains(&code) || (0xD800..=0xDFFF).contains(&code) {
+            continue;
+        }
+        for form in Form::all() {
+            let normalized = data.normalize(&[code], form);
+            if normalized != [code] {
+                return Err(UcdError::parse(
+                    NORMALIZATION_TEST_TXT,
+                    1,
+                    &to_hex(&normalized),
+                    format!(
+                        "{}({:04X}) = {:04X}, as it's not listed in part 1",
+                        form.name(),
+                        code,
+                        code
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(checked)
+}
+
+#[cfg(test)]
+fn test_normalization_data() -> NormalizationData {
+    let exclusions_file = UcdFile::new(
+        COMPOSITION_EXCLUSIONS_TXT,
+        "# CompositionExclusions-13.0.0.txt\n\
+         0958    #  DEVANAGARI LETTER QA\n\
+         2ADC    #  FORKING\n",
+    );
+    let properties_file = UcdFile::new(
+        DERIVED_NORMALIZATION_PROPS_TXT,
+        "# DerivedNormalizationProps-13.0.0.txt\n\
+         # @missing: 0000..10FFFF; NFC_QC; Yes\n\
+         0340..0341    ; NFC_QC; N # Mn   [2] COMBINING GRAVE TONE MARK..COMBINING ACUTE TONE MARK\n\
+         0300..0304    ; NFC_QC; M # Mn   [5] COMBINING GRAVE ACCENT..COMBINING MACRON\n\
+         00C0..00C5    ; NFD_QC; N # L&   [6] LATIN CAPITAL LETTER A WITH GRAVE..\n\
+         00A0          ; NFKD_QC; N # Zs       NO-BREAK SPACE\n\
+         00A0          ; NFKC_CF; 0020 # Zs       NO-BREAK SPACE\n",
+    );
+
+    let table = crate::code_point_table::generate_code_point_table(&UcdSource::Embedded)
+        .expect("UnicodeData.txt");
+    parse_normalization(&table, exclusions_file, properties_file).expect("normalization data")
+}
+
+#[test]
+fn check_normalization() {
+    let data = test_normalization_data();
+    let normalize = |codes: &[u32], form| data.normalize(codes, form);
+
+    // The example from UAX #15: U+1E9B LATIN SMALL LETTER LONG S WITH DOT
+    // ABOVE followed by U+0323 COMBINING DOT BELOW.
+    let long_s = [0x1E9B, 0x0323];
+    assert_eq!(normalize(&long_s, Form::Nfc), [0x1E9B, 0x0323]);
+    assert_eq!(normalize(&long_s, Form::Nfd), [0x017F, 0x0323, 0x0307]);
+    assert_eq!(normalize(&long_s, Form::Nfkc), [0x1E69]);
+    assert_eq!(normalize(&long_s, Form::Nfkd), [0x0073, 0x0323, 0x0307]);
+
+    // Singletons and composition exclusions don't recompose.
+    assert_eq!(normalize(&[0x212B], Form::Nfc), [0x00C5]);
+    assert!(data.is_composition_excluded(0x212B));
+    assert_eq!(normalize(&[0x0958], Form::Nfc), [0x0915, 0x093C]);
+    assert!(data.is_composition_excluded(0x0958));
+    assert_eq!(data.composition(0x0915, 0x093C), None);
+    assert_eq!(data.composition(0x0041, 0x030A), Some(0x00C5));
+
+    // Canonical ordering, and composition past a non-blocking mark.
+    assert_eq!(
+        normalize(&[0x0061, 0x0301, 0x0323], Form::Nfd),
+        [0x0061, 0x0323, 0x0301]
+    );
+    assert_eq!(normalize(&[0x0061, 0x0323, 0x0302], Form::Nfc), [0x1EAD]);
+    assert_eq!(
+        normalize(&[0x0061, 0x0302, 0x0302], Form::Nfc),
+        [0x00E2, 0x0302]
+    );
+
+    // Hangul syllables.
+    assert_eq!(normalize(&[0xAC01], Form::Nfd), [0x1100, 0x1161, 0x11A8]);
+    assert_eq!(normalize(&[0x1100, 0x1161, 0x11A8], Form::Nfc), [0xAC01]);
+    assert_eq!(normalize(&[0xAC00, 0x11A8], Form::Nfc), [0xAC01]);
+    assert_eq!(normalize(&[0xAC01, 0x11A8], Form::Nfc), [0xAC01, 0x11A8]);
+
+    // Compatibility decompositions that include canonical decompositions.
+    assert_eq!(normalize(&[0x01C4], Form::Nfkd), [0x0044, 0x005A, 0x030C]);
+    assert_eq!(normalize(&[0x01C4], Form::Nfkc), [0x0044, 0x017D]);
+    assert_eq!(normalize(&[0x01C4], Form::Nfc), [0x01C4]);
+
+    assert_eq!(data.combining_class(0x0301), 230);
+    assert_eq!(data.combining_class('a' as u32), 0);
+    assert_eq!(data.quick_check(Form::Nfc, 0x0341), QuickCheck::No);
+    assert_eq!(data.quick_check(Form::Nfc, 0x0301), QuickCheck::Maybe);
+    assert_eq!(data.quick_check(Form::Nfc, 0x00C5), QuickCheck::Yes);
+    assert_eq!(data.quick_check(Form::Nfd, 0x00C5), QuickCheck::No);
+    assert_eq!(data.quick_check(Form::Nfkd, 0x00A0), QuickCheck::No);
+    assert_eq!(data.quick_check(Form::Nfkc, 0x00A0), QuickCheck::Yes);
+}
+
+#[test]
+fn check_normalization_test_failures() {
+    let data = test_normalization_data();
+    let lines = DataLines::new(
+        "# NormalizationTest-13.0.0.txt\n\
+         @Part0 # Specific cases\n\
+         1E0A;1E0A;0044 0307;1E0A;0044 0307; # (Ḋ; Ḋ; D◌̇; Ḋ; D◌̇; ) LATIN CAPITAL LETTER D WITH DOT ABOVE\n\
+         1E0A 0323;1E0A 0323;0044 0307 0323;1E0C 0307;0044 0323 0307; # (deliberately wrong)\n",
+    );
+
+    let error = check_normalization_test_lines(lines, &data).expect_err("incorrect test line");
+    assert_eq!(
+        error.to_string(),
+        "NormalizationTest.txt:4: expected NFC(c1) = c2 = 1E0A 0323, found \"1E0C 0307\""
+    );
+}
+
+/// Check against `NormalizationTest.txt` and the other files in the UCD
+/// directory named by the `UNICODE_INFO_UCD_DIR` environment variable.  Run
+/// with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs UNICODE_INFO_UCD_DIR"]
+fn check_normalization_conformance() {
+    let dir = std::env::var_os("UNICODE_INFO_UCD_DIR").expect("UNICODE_INFO_UCD_DIR is set");
+    let source = UcdSource::Directory(dir.into());
+    let table =
+        crate::code_point_table::generate_code_point_table(&source).expect("UnicodeData.txt");
+    let data = process_normalization(&source, &table).expect("normalization data");
+    let checked = check_normalization_test(&source, &data).expect("conformance");
+    assert!(checked > 0);
+}
@@ -0,0 +1,126 @@
+//! Processes `PropList.txt` to extract the code points having each of the
+//! binary properties it lists: White_Space, Pattern_Syntax, Other_ID_Start,
+//! Soft_Dotted, Noncharacter_Code_Point, and so on.
+//!
+//! `PropList.txt` is embedded in this crate, as case mapping needs its
+//! Soft_Dotted property: see [`process_soft_dotted`].
+
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+use std::collections::BTreeMap;
+
+/// The name of the file processed by this module.
+pub(crate) const PROP_LIST_TXT: &str = "PropList.txt";
+
+/// The code points having each binary property listed in `PropList.txt`.
+///
+/// Properties are looked up by their long names as they appear in the file,
+/// e.g. "White_Space" or "Soft_Dotted".  The set of properties isn't fixed:
+/// new Unicode versions occasionally add one, and whatever the file lists is
+/// available here.
+pub struct PropListData {
+    properties: BTreeMap<&'static str, CodePointSet>,
+}
+
+impl PropListData {
+    /// Return the set of code points having the property `name`, or `None` if
+    /// `PropList.txt` doesn't list that property.
+    pub fn get(&self, name: &str) -> Option<&CodePointSet> {
+        self.properties.get(name)
+    }
+
+    /// Whether `code` has the property `name`.  (Code points never have a
+    /// property not listed in `PropList.txt`.)
+    pub fn contains(&self, name: &str, code: u32) -> bool {
+        self.get(name).is_some_and(|set| set.contains(&code))
+    }
+
+    /// Return an iterator over the names of every property in `PropList.txt`,
+    /// in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.properties.keys().copied()
+    }
+}
+
+/// Collect the code points listed for each property in `file`, a file whose
+/// records consist of a code point range and a binary property name.
+pub(crate) fn parse_binary_properties(
+    file: UcdFile,
+) -> Result<BTreeMap<&'static str, CodePointSet>, UcdError> {
+    let mut properties = BTreeMap::<&'static str, CodePointSet>::new();
+    for record in file.records() {
+        let record = record?;
+        let name = record.field(0, "a property name")?;
+        properties.entry(name).or_default().extend(record.range);
+    }
+    Ok(properties)
+}
+
+/// Generate the set of code points having each property in the
+/// `PropList.txt` in `source`.
+pub fn process_prop_list(source: &UcdSource) -> Result<PropListData, UcdError> {
+    let properties = parse_binary_properties(UcdFile::read(source, PROP_LIST_TXT)?)?;
+    Ok(PropListData { properties })
+}
+
+/// Generate the set of code points having the Soft_Dotted property, which the
+/// After_Soft_Dotted casing context depends on, from the `PropList.txt` in
+/// `source`.
+pub fn process_soft_dotted(source: &UcdSource) -> Result<CodePointSet, UcdError> {
+    Ok(process_prop_list(source)?
+        .get("Soft_Dotted")
+        .cloned()
+        .unwrap_or_default())
+}
+
+#[test]
+fn check_prop_list() {
+    let file = UcdFile::new(
+        PROP_LIST_TXT,
+        "# PropList-13.0.0.txt\n\
+         0009..000D    ; White_Space # Cc   [5] <control-0009>..<control-000D>\n\
+         0020          ; White_Space # Zs       SPACE\n\
+         \n\
+         0069..006A    ; Soft_Dotted # L&   [2] LATIN SMALL LETTER I..LATIN SMALL LETTER J\n\
+         FDD0..FDEF    ; Noncharacter_Code_Point # Cn  [32] <noncharacter-FDD0>..\n",
+    );
+
+    let prop_list = PropListData {
+        properties: parse_binary_properties(file).expect("PropList.txt"),
+    };
+    assert_eq!(
+        prop_list.names().collect::<Vec<_>>(),
+        ["Noncharacter_Code_Point", "Soft_Dotted", "White_Space"]
+    );
+    assert_eq!(prop_list.get("White_Space").map(|set| set.len()), Some(6));
+    assert!(prop_list.contains("Soft_Dotted", 'j' as u32));
+    assert!(!prop_list.contains("Soft_Dotted", 'k' as u32));
+    assert!(prop_list.contains("Noncharacter_Code_Point", 0xFDEF));
+    assert!(prop_list.get("Pattern_Syntax").is_none());
+    assert!(!prop_list.contains("Pattern_Syntax", '#' as u32));
+
+    let embedded = process_prop_list(&UcdSource::Embedded).expect("PropList.txt");
+    assert_eq!(embedded.get("White_Space").map(|set| set.len()), Some(25));
+    assert!(embedded.contains("Pattern_Syntax", '#' as u32));
+
+    let soft_dotted = process_soft_dotted(&UcdSource::Embedded).expect("Soft_Dotted");
+    assert_eq!(soft_dotted.len(), 46);
+    assert!(soft_dotted.contains(&0x0456) && soft_dotted.contains(&0x1D693));
+    assert!(!soft_dotted.contains(&0x0131));
+
+    let dir = crate::ucd_source::TestDirectory::new("check_soft_dotted");
+    assert!(
+        process_soft_dotted(&dir.source()).is_err_and(|error| error.is_not_found()),
+        "a directory must have PropList.txt"
+    );
+    dir.write(
+        PROP_LIST_TXT,
+        "0069..006A    ; Soft_Dotted # L&   [2] LATIN SMALL LETTER I..LATIN SMALL LETTER J\n",
+    );
+    assert_eq!(
+        process_soft_dotted(&dir.source()).ok(),
+        Some(CodePointSet::from([0x0069, 0x006A]))
+    );
+}
@@ -0,0 +1,542 @@
+//! Processes `PropertyAliases.txt` and `PropertyValueAliases.txt` to resolve
+//! user-written property names and values, e.g. `gc=Lu`,
+//! `General_Category=Uppercase_Letter`, or `sc=Grek`, to the properties and
+//! values they name.
+//!
+//! Names can be matched strictly, as ECMAScript requires for `\p{...}`, or
+//! loosely per [UAX44-LM3](https://www.unicode.org/reports/tr44/#UAX44-LM3),
+//! ignoring case, whitespace, underscores, hyphens, and an initial "is".
+//!
+//! Neither file is embedded in this crate, so both must be read from a
+//! [`UcdSource::Directory`].
+
+use crate::ucd_error::UcdError;
+use crate::ucd_file::DataLines;
+use crate::ucd_source::UcdSource;
+use std::iter;
+
+/// The name of the file listing property names.
+pub(crate) const PROPERTY_ALIASES_TXT: &str = "PropertyAliases.txt";
+
+/// The name of the file listing property value names.
+pub(crate) const PROPERTY_VALUE_ALIASES_TXT: &str = "PropertyValueAliases.txt";
+
+/// How to compare a user-written name to the names of properties and values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NameMatching {
+    /// Names must match exactly, as in ECMAScript `\p{...}`.
+    Strict,
+
+    /// Names are compared per UAX44-LM3: see [`loose_matching_key`].
+    Loose,
+}
+
+impl NameMatching {
+    /// Whether `name` matches `alias` under this kind of matching.
+    fn matches(self, name: &str, alias: &str) -> bool {
+        match self {
+            NameMatching::Strict => name == alias,
+            NameMatching::Loose => loose_matching_key(name) == loose_matching_key(alias),
+        }
+    }
+}
+
+/// Return the key by which `name` is compared to other names under UAX44-LM3
+/// loose matching: `name` lowercased, without whitespace, underscores, or
+/// hyphens, and without any initial "is".
+///
+/// # Example
+///
+/// ```
+/// # use unicode_info::property_aliases::loose_matching_key;
+/// assert_eq!(loose_matching_key("Line_Break"), "linebreak");
+/// assert_eq!(loose_matching_key("is-Uppercase Letter"), "uppercaseletter");
+/// ```
+pub fn loose_matching_key(name: &str) -> String {
+    let key = name
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect::<String>();
+    match key.strip_prefix("is") {
+        Some(rest) => rest.to_string(),
+        None => key,
+    }
+}
+
+/// Return an iterator over `short_name`, `long_name`, and `other_names`, the
+/// names of a property or property value.
+fn names<'a>(
+    short_name: &'static str,
+    long_name: &'static str,
+    other_names: &'a [&'static str],
+) -> impl Iterator<Item = &'static str> + 'a {
+    iter::once(short_name)
+        .chain(iter::once(long_name))
+        .chain(other_names.iter().copied())
+}
+
+/// A value of a property, e.g. Uppercase_Letter (Lu) of General_Category.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyValue {
+    /// The abbreviated name of the value, e.g. "Lu".
+    pub short_name: &'static str,
+
+    /// The long name of the value, e.g. "Uppercase_Letter".
+    pub long_name: &'static str,
+
+    /// Any other names of the value, e.g. "Combining_Mark" for Mark, or the
+    /// number of a Canonical_Combining_Class value.
+    pub other_names: Vec<&'static str>,
+}
+
+impl PropertyValue {
+    /// Return an iterator over every name of this value.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        names(self.short_name, self.long_name, &self.other_names)
+    }
+}
+
+/// A property, e.g. General_Category (gc), and its values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Property {
+    /// The abbreviated name of the property, e.g. "gc".
+    pub short_name: &'static str,
+
+    /// The long name of the property, e.g. "General_Category".
+    pub long_name: &'static str,
+
+    /// Any other names of the property.
+    pub other_names: Vec<&'static str>,
+
+    /// The values of the property listed in `PropertyValueAliases.txt`.
+    /// Properties that aren't enumerated (e.g. Name) have no listed values.
+    pub values: Vec<PropertyValue>,
+}
+
+impl Property {
+    /// Return an iterator over every name of this property.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        names(self.short_name, self.long_name, &self.other_names)
+    }
+
+    /// Return the value of this property named `name`.
+    pub fn value(&self, name: &str, matching: NameMatching) -> Option<&PropertyValue> {
+        self.values
+            .iter()
+            .find(|value| value.names().any(|alias| matching.matches(name, alias)))
+    }
+}
+
+/// A property escape `\p{...}` that ECMA-262 supports, resolved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EcmaPropertyEscape<'a> {
+    /// A binary property, e.g. `\p{Alpha}`, by its canonical name
+    /// ("Alphabetic").  This includes the ECMAScript-specific properties Any,
+    /// ASCII, and Assigned.
+    Binary(&'static str),
+
+    /// A value of General_Category, Script, or Script_Extensions, e.g.
+    /// `\p{Lu}` or `\p{scx=Grek}`, with the canonical name of the property.
+    Value {
+        property: &'static str,
+        value: &'a PropertyValue,
+    },
+
+    /// A property of strings, e.g. `\p{RGI_Emoji}`, only valid with the `v`
+    /// flag.
+    Strings(&'static str),
+}
+
+/// The binary properties ECMA-262 supports, by canonical name and alias.
+const ECMA_BINARY_PROPERTIES: [(&str, Option<&str>); 53] = [
+    ("ASCII", None),
+    ("ASCII_Hex_Digit", Some("AHex")),
+    ("Alphabetic", Some("Alpha")),
+    ("Any", None),
+    ("Assigned", None),
+    ("Bidi_Control", Some("Bidi_C")),
+    ("Bidi_Mirrored", Some("Bidi_M")),
+    ("Case_Ignorable", Some("CI")),
+    ("Cased", None),
+    ("Changes_When_Casefolded", Some("CWCF")),
+    ("Changes_When_Casemapped", Some("CWCM")),
+    ("Changes_When_Lowercased", Some("CWL")),
+    ("Changes_When_NFKC_Casefolded", Some("CWKCF")),
+    ("Changes_When_Titlecased", Some("CWT")),
+    ("Changes_When_Uppercased", Some("CWU")),
+    ("Dash", None),
+    ("Default_Ignorable_Code_Point", Some("DI")),
+    ("Deprecated", Some("Dep")),
+    ("Diacritic", Some("Dia")),
+    ("Emoji", None),
+    ("Emoji_Component", Some("EComp")),
+    ("Emoji_Modifier", Some("EMod")),
+    ("Emoji_Modifier_Base", Some("EBase")),
+    ("Emoji_Presentation", Some("EPres")),
+    ("Extended_Pictographic", Some("ExtPict")),
+    ("Extender", Some("Ext")),
+    ("Grapheme_Base", Some("Gr_Base")),
+    ("Grapheme_Extend", Some("Gr_Ext")),
+    ("Hex_Digit", Some("Hex")),
+    ("IDS_Binary_Operator", Some("IDSB")),
+    ("IDS_Trinary_Operator", Some("IDST")),
+    ("ID_Continue", Some("IDC")),
+    ("ID_Start", Some("IDS")),
+    ("Ideographic", Some("Ideo")),
+    ("Join_Control", Some("Join_C")),
+    ("Logical_Order_Exception", Some("LOE")),
+    ("Lowercase", Some("Lower")),
+    ("Math", None),
+    ("Noncharacter_Code_Point", Some("NChar")),
+    ("Pattern_Syntax", Some("Pat_Syn")),
+    ("Pattern_White_Space", Some("Pat_WS")),
+    ("Quotation_Mark", Some("QMark")),
+    ("Radical", None),
+    ("Regional_Indicator", Some("RI")),
+    ("Sentence_Terminal", Some("STerm")),
+    ("Soft_Dotted", Some("SD")),
+    ("Terminal_Punctuation", Some("Term")),
+    ("Unified_Ideograph", Some("UIdeo")),
+    ("Uppercase", Some("Upper")),
+    ("Variation_Selector", Some("VS")),
+    ("White_Space", Some("space")),
+    ("XID_Continue", Some("XIDC")),
+    ("XID_Start", Some("XIDS")),
+];
+
+/// The non-binary properties ECMA-262 supports, by canonical name and alias,
+/// with the property in `PropertyValueAliases.txt` whose values they take.
+/// (Script_Extensions takes the values of Script.)
+const ECMA_NON_BINARY_PROPERTIES: [(&str, &str, &str); 3] = [
+    ("General_Category", "gc", "gc"),
+    ("Script", "sc", "sc"),
+    ("Script_Extensions", "scx", "sc"),
+];
+
+/// The properties of strings ECMA-262 supports with the `v` flag.
+const ECMA_STRING_PROPERTIES: [&str; 7] = [
+    "Basic_Emoji",
+    "Emoji_Keycap_Sequence",
+    "RGI_Emoji_Modifier_Sequence",
+    "RGI_Emoji_Flag_Sequence",
+    "RGI_Emoji_Tag_Sequence",
+    "RGI_Emoji_ZWJ_Sequence",
+    "RGI_Emoji",
+];
+
+/// Every property listed in `PropertyAliases.txt`, with its values.
+pub struct PropertyAliases {
+    properties: Vec<Property>,
+}
+
+impl PropertyAliases {
+    /// Every property, in the order `PropertyAliases.txt` lists them.
+    pub fn properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    /// Return the property named `name`.
+    pub fn property(&self, name: &str, matching: NameMatching) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.names().any(|alias| matching.matches(name, alias)))
+    }
+
+    /// Resolve `text`, either `property=value` or a lone name, to a property
+    /// and (if a value is named) a value of it.
+    ///
+    /// A lone name is resolved first as a General_Category value, then as a
+    /// Script value, then as a property, so that `Lu` and `Greek` resolve as
+    /// `gc=Lu` and `sc=Greek`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use unicode_info::property_aliases::*;
+    /// # use unicode_info::ucd_source::UcdSource;
+    /// let source = UcdSource::Directory("ucd".into());
+    /// let aliases = process_property_aliases(&source).expect("alias data");
+    /// let (property, value) = aliases.resolve("gc=Lu", NameMatching::Strict).expect("gc=Lu");
+    /// assert_eq!(property.long_name, "General_Category");
+    /// assert_eq!(value.expect("value").long_name, "Uppercase_Letter");
+    /// ```
+    pub fn resolve(
+        &self,
+        text: &str,
+        matching: NameMatching,
+    ) -> Option<(&Property, Option<&PropertyValue>)> {
+        if let Some(i) = text.find('=') {
+            let property = self.property(&text[..i], matching)?;
+            let value = property.value(&text[i + 1..], matching)?;
+            return Some((property, Some(value)));
+        }
+
+        for lone_value_property in &["gc", "sc"] {
+            if let Some(property) = self.property(lone_value_property, NameMatching::Strict) {
+                if let Some(value) = property.value(text, matching) {
+                    return Some((property, Some(value)));
+                }
+            }
+        }
+        self.property(text, matching)
+            .map(|property| (property, None))
+    }
+
+    /// Resolve `text`, the contents of an ECMAScript `\p{...}` property
+    /// escape, if ECMA-262 supports it.
+    ///
+    /// ECMAScript requires strict matching, and accepts only General_Category
+    /// values and the binary properties and properties of strings ECMA-262
+    /// lists on their own, and only General_Category, Script, and
+    /// Script_Extensions as the property in `property=value`.
+    pub fn resolve_ecma(&self, text: &str) -> Option<EcmaPropertyEscape<'_>> {
+        if let Some(i) = text.find('=') {
+            let (name, value) = (&text[..i], &text[i + 1..]);
+            let (property, _, values_of) = ECMA_NON_BINARY_PROPERTIES
+                .iter()
+                .find(|(long, short, _)| name == *long || name == *short)?;
+            let value = self
+                .property(values_of, NameMatching::Strict)?
+                .value(value, NameMatching::Strict)?;
+            return Some(EcmaPropertyEscape::Value { property, value });
+        }
+
+        if let Some(value) = self
+            .property("gc", NameMatching::Strict)
+            .and_then(|gc| gc.value(text, NameMatching::Strict))
+        {
+            return Some(EcmaPropertyEscape::Value {
+                property: "General_Category",
+                value,
+            });
+        }
+
+        if let Some((name, _)) = ECMA_BINARY_PROPERTIES
+            .iter()
+            .find(|(name, alias)| text == *name || Some(text) == *alias)
+        {
+            return Some(EcmaPropertyEscape::Binary(name));
+        }
+
+        ECMA_STRING_PROPERTIES
+            .iter()
+            .find(|name| text == **name)
+            .map(|name| EcmaPropertyEscape::Strings(name))
+    }
+}
+
+/// Parse every property and property value name from the
+/// `PropertyAliases.txt` and `PropertyValueAliases.txt` in `source`.
+pub fn process_property_aliases(source: &UcdSource) -> Result<PropertyAliases, UcdError> {
+    parse_property_aliases(
+        DataLines::new(source.read(PROPERTY_ALIASES_TXT)?),
+        DataLines::new(source.read(PROPERTY_VALUE_ALIASES_TXT)?),
+    )
+}
+
+/// Parse every property and property value name from `property_lines` and
+/// `value_lines`, the data lines of `PropertyAliases.txt` and
+/// `PropertyValueAliases.txt`.
+fn parse_property_aliases(
+    property_lines: DataLines,
+    value_lines: DataLines,
+) -> Result<PropertyAliases, UcdError> {
+    // PropertyAliases.txt lines consist of a short name, a long name, and
+    // possibly other names, e.g. `scf ; Simple_Case_Folding ; sfc`.
+    let mut properties = vec![];
+    for line in property_lines {
+        if line.fields.len() < 2 {
+            return Err(UcdError::parse(
+                PROPERTY_ALIASES_TXT,
+                line.line,
+                &line.fields.join("; "),
+                "a short and a long property name",
+            ));
+        }
+        properties.push(Property {
+            short_name: line.fields[0],
+            long_name: line.fields[1],
+            other_names: line.fields[2..].to_vec(),
+            values: vec![],
+        });
+    }
+
+    // PropertyValueAliases.txt lines consist of a property's short name, then
+    // a value's short name, long name, and possibly other names, e.g.
+    // `gc ; M ; Mark ; Combining_Mark`.  Canonical_Combining_Class lines have
+    // the numeric value before the names, e.g. `ccc; 230; A ; Above`.
+    for line in value_lines {
+        if line.missing {
+            continue;
+        }
+
+        let error = |expected| {
+            UcdError::parse(
+                PROPERTY_VALUE_ALIASES_TXT,
+                line.line,
+                &line.fields.join("; "),
+                expected,
+            )
+        };
+
+        let property_name = line.fields[0];
+        let property = properties
+            .iter_mut()
+            .find(|property| property.short_name == property_name)
+            .ok_or_else(|| error("a property listed in PropertyAliases.txt"))?;
+
+        let mut names = line.fields[1..].to_vec();
+        if property_name == "ccc" && !names.is_empty() {
+            let number = names.remove(0);
+            names.push(number);
+        }
+        if names.len() < 2 {
+            return Err(error("a short and a long property value name"));
+        }
+        property.values.push(PropertyValue {
+            short_name: names[0],
+            long_name: names[1],
+            other_names: names[2..].to_vec(),
+        });
+    }
+
+    Ok(PropertyAliases { properties })
+}
+
+/// The contents of a `PropertyAliases.txt` and `PropertyValueAliases.txt`
+/// listing a few properties, for tests.
+#[cfg(test)]
+const TEST_PROPERTY_ALIASES: [&str; 2] = [
+    "# PropertyAliases-13.0.0.txt\n\
+     ccc                      ; Canonical_Combining_Class\n\
+     gc                       ; General_Category\n\
+     sc                       ; Script\n\
+     scx                      ; Script_Extensions\n\
+     Alpha                    ; Alphabetic\n\
+     WSpace                   ; White_Space                 ; space\n",
+    "# PropertyValueAliases-13.0.0.txt\n\
+     # @missing: 0000..10FFFF; Canonical_Combining_Class; 0\n\
+     ccc;   0; NR                         ; Not_Reordered\n\
+     ccc; 230; A                          ; Above\n\
+     gc ; L                                ; Letter\n\
+     gc ; Lu                               ; Uppercase_Letter\n\
+     gc ; M                                ; Mark                             ; Combining_Mark\n\
+     sc ; Grek                             ; Greek\n\
+     sc ; Zinh                             ; Inherited                        ; Qaai\n\
+     Alpha; N                              ; No                               ; F ; False\n\
+     Alpha; Y                              ; Yes                              ; T ; True\n",
+];
+
+#[cfg(test)]
+fn parse_test_aliases() -> PropertyAliases {
+    parse_property_aliases(
+        DataLines::new(TEST_PROPERTY_ALIASES[0]),
+        DataLines::new(TEST_PROPERTY_ALIASES[1]),
+    )
+    .expect("alias data")
+}
+
+#[test]
+fn check_property_aliases() {
+    use NameMatching::*;
+
+    let aliases = parse_test_aliases();
+    assert_eq!(aliases.properties().len(), 6);
+
+    let white_space = aliases.property("space", Strict).expect("space");
+    assert_eq!(white_space.long_name, "White_Space");
+    assert!(white_space.values.is_empty());
+
+    let ccc = aliases.property("ccc", Strict).expect("ccc");
+    let above = ccc.value("230", Strict).expect("ccc=230");
+    assert_eq!((above.short_name, above.long_name), ("A", "Above"));
+
+    let (property, value) = aliases.resolve("gc=Lu", Strict).expect("gc=Lu");
+    assert_eq!(property.long_name, "General_Category");
+    assert_eq!(value.map(|value| value.long_name), Some("Uppercase_Letter"));
+    let (_, value) = aliases
+        .resolve("General_Category=Uppercase_Letter", Strict)
+        .expect("General_Category=Uppercase_Letter");
+    assert_eq!(value.map(|value| value.short_name), Some("Lu"));
+    let (property, value) = aliases.resolve("Combining_Mark", Strict).expect("M");
+    assert_eq!(property.short_name, "gc");
+    assert_eq!(value.map(|value| value.short_name), Some("M"));
+    let (property, value) = aliases.resolve("Greek", Strict).expect("Greek");
+    assert_eq!(property.short_name, "sc");
+    assert_eq!(value.map(|value| value.short_name), Some("Grek"));
+    let (property, value) = aliases.resolve("Alphabetic", Strict).expect("Alpha");
+    assert_eq!(property.short_name, "Alpha");
+    assert_eq!(value, None);
+
+    assert!(aliases
+        .resolve("general category=uppercase letter", Strict)
+        .is_none());
+    let (property, value) = aliases
+        .resolve("general category=uppercase letter", Loose)
+        .expect("loose");
+    assert_eq!(property.short_name, "gc");
+    assert_eq!(value.map(|value| value.short_name), Some("Lu"));
+    assert!(aliases.resolve("is-Greek", Loose).is_some());
+    assert!(aliases.resolve("GC=lu", Loose).is_some());
+    assert!(aliases.resolve("gc=Greek", Loose).is_none());
+}
+
+#[test]
+fn check_ecma_property_escapes() {
+    let aliases = parse_test_aliases();
+
+    let value = |text| match aliases.resolve_ecma(text) {
+        Some(EcmaPropertyEscape::Value { property, value }) => Some((property, value.long_name)),
+        _ => None,
+    };
+    assert_eq!(value("Lu"), Some(("General_Category", "Uppercase_Letter")));
+    assert_eq!(
+        value("gc=Lu"),
+        Some(("General_Category", "Uppercase_Letter"))
+    );
+    assert_eq!(value("sc=Grek"), Some(("Script", "Greek")));
+    assert_eq!(
+        value("Script_Extensions=Qaai"),
+        Some(("Script_Extensions", "Inherited"))
+    );
+
+    assert_eq!(
+        aliases.resolve_ecma("space"),
+        Some(EcmaPropertyEscape::Binary("White_Space"))
+    );
+    assert_eq!(
+        aliases.resolve_ecma("Any"),
+        Some(EcmaPropertyEscape::Binary("Any"))
+    );
+    assert_eq!(
+        aliases.resolve_ecma("RGI_Emoji"),
+        Some(EcmaPropertyEscape::Strings("RGI_Emoji"))
+    );
+
+    // Strict matching only, no lone Script values, and only gc/sc/scx with a
+    // value.
+    assert_eq!(aliases.resolve_ecma("lu"), None);
+    assert_eq!(aliases.resolve_ecma("Greek"), None);
+    assert_eq!(aliases.resolve_ecma("Alpha=Y"), None);
+    assert_eq!(aliases.resolve_ecma("ccc=230"), None);
+    assert_eq!(aliases.resolve_ecma("gc=Greek"), None);
+}
+
+#[test]
+fn check_property_aliases_errors() {
+    let error = parse_property_aliases(
+        DataLines::new(TEST_PROPERTY_ALIASES[0]),
+        DataLines::new(
+            "gc ; Lu ; Uppercase_Letter\n\
+             blk; ASCII; Basic_Latin\n",
+        ),
+    )
+    .err()
+    .expect("unknown property");
+    assert_eq!(
+        error.to_string(),
+        "PropertyValueAliases.txt:2: expected a property listed in PropertyAliases.txt, \
+         found \"blk; ASCII; Basic_Latin\""
+    );
+}
@@ -0,0 +1,588 @@
+//! Processes `Scripts.txt` and `ScriptExtensions.txt` to determine the Script
+//! and Script_Extensions properties of code points, as used by RegExp
+//! `\p{Script=...}` and `\p{Script_Extensions=...}` property escapes.
+//!
+//! Neither file is embedded in this crate, so both must be read from a
+//! [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr24/> for details.
+
+use crate::code_point_table::CodePoint;
+use crate::enumerated_property::EnumeratedProperty;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::UcdFile;
+use crate::ucd_source::UcdSource;
+use std::collections::HashMap;
+
+/// The name of the file listing the Script of code points.
+pub(crate) const SCRIPTS_TXT: &str = "Scripts.txt";
+
+/// The name of the file listing the Script_Extensions of code points.
+pub(crate) const SCRIPT_EXTENSIONS_TXT: &str = "ScriptExtensions.txt";
+
+/// A script, the value of the Script property of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Script {
+    Adlam,
+    CaucasianAlbanian,
+    Ahom,
+    Arabic,
+    ImperialAramaic,
+    Armenian,
+    Avestan,
+    Balinese,
+    Bamum,
+    BassaVah,
+    Batak,
+    Bengali,
+    BeriaErfe,
+    Bhaiksuki,
+    Bopomofo,
+    Brahmi,
+    Braille,
+    Buginese,
+    Buhid,
+    Chakma,
+    CanadianAboriginal,
+    Carian,
+    Cham,
+    Cherokee,
+    Chisoi,
+    Chorasmian,
+    Coptic,
+    CyproMinoan,
+    Cypriot,
+    Cyrillic,
+    Devanagari,
+    DivesAkuru,
+    Dogra,
+    Deseret,
+    Duployan,
+    EgyptianHieroglyphs,
+    Elbasan,
+    Elymaic,
+    Ethiopic,
+    Garay,
+    Georgian,
+    Glagolitic,
+    GunjalaGondi,
+    MasaramGondi,
+    Gothic,
+    Grantha,
+    Greek,
+    Gujarati,
+    GurungKhema,
+    Gurmukhi,
+    Hangul,
+    Han,
+    Hanunoo,
+    Hatran,
+    Hebrew,
+    Hiragana,
+    AnatolianHieroglyphs,
+    PahawhHmong,
+    NyiakengPuachueHmong,
+    KatakanaOrHiragana,
+    OldHungarian,
+    OldItalic,
+    Javanese,
+    KayahLi,
+    Katakana,
+    Kawi,
+    Kharoshthi,
+    Khmer,
+    Khojki,
+    KhitanSmallScript,
+    Kannada,
+    KiratRai,
+    Kaithi,
+    TaiTham,
+    Lao,
+    Latin,
+    Lepcha,
+    Limbu,
+    LinearA,
+    LinearB,
+    Lisu,
+    Lycian,
+    Lydian,
+    Mahajani,
+    Makasar,
+    Mandaic,
+    Manichaean,
+    Marchen,
+    Medefaidrin,
+    MendeKikakui,
+    MeroiticCursive,
+    MeroiticHieroglyphs,
+    Malayalam,
+    Modi,
+    Mongolian,
+    Mro,
+    MeeteiMayek,
+    Multani,
+    Myanmar,
+    NagMundari,
+    Nandinagari,
+    OldNorthArabian,
+    Nabataean,
+    Newa,
+    Nko,
+    Nushu,
+    Ogham,
+    OlChiki,
+    OlOnal,
+    OldTurkic,
+    Oriya,
+    Osage,
+    Osmanya,
+    OldUyghur,
+    Palmyrene,
+    PauCinHau,
+    OldPermic,
+    PhagsPa,
+    InscriptionalPahlavi,
+    PsalterPahlavi,
+    Phoenician,
+    Miao,
+    InscriptionalParthian,
+    Rejang,
+    HanifiRohingya,
+    Runic,
+    Samaritan,
+    OldSouthArabian,
+    Saurashtra,
+    SignWriting,
+    Shavian,
+    Sharada,
+    Siddham,
+    Sidetic,
+    Khudawadi,
+    Sinhala,
+    Sogdian,
+    OldSogdian,
+    SoraSompeng,
+    Soyombo,
+    Sundanese,
+    Sunuwar,
+    SylotiNagri,
+    Syriac,
+    Tagbanwa,
+    Takri,
+    TaiLe,
+    NewTaiLue,
+    Tamil,
+    Tangut,
+    TaiViet,
+    TaiYo,
+    Telugu,
+    Tifinagh,
+    Tagalog,
+    Thaana,
+    Thai,
+    Tibetan,
+    Tirhuta,
+    Tangsa,
+    Todhri,
+    TolongSiki,
+    Toto,
+    TuluTigalari,
+    Ugaritic,
+    Vai,
+    Vithkuqi,
+    WarangCiti,
+    Wancho,
+    OldPersian,
+    Cuneiform,
+    Yezidi,
+    Yi,
+    ZanabazarSquare,
+    Inherited,
+    Common,
+    Unknown,
+}
+
+use Script::*;
+
+/// Every script, in the order `PropertyValueAliases.txt` lists them (sorted by
+/// short name), with its short and long names.
+const SCRIPTS: [(Script, &str, &str); 177] = [
+    (Adlam, "Adlm", "Adlam"),
+    (CaucasianAlbanian, "Aghb", "Caucasian_Albanian"),
+    (Ahom, "Ahom", "Ahom"),
+    (Arabic, "Arab", "Arabic"),
+    (ImperialAramaic, "Armi", "Imperial_Aramaic"),
+    (Armenian, "Armn", "Armenian"),
+    (Avestan, "Avst", "Avestan"),
+    (Balinese, "Bali", "Balinese"),
+    (Bamum, "Bamu", "Bamum"),
+    (BassaVah, "Bass", "Bassa_Vah"),
+    (Batak, "Batk", "Batak"),
+    (Bengali, "Beng", "Bengali"),
+    (BeriaErfe, "Berf", "Beria_Erfe"),
+    (Bhaiksuki, "Bhks", "Bhaiksuki"),
+    (Bopomofo, "Bopo", "Bopomofo"),
+    (Brahmi, "Brah", "Brahmi"),
+    (Braille, "Brai", "Braille"),
+    (Buginese, "Bugi", "Buginese"),
+    (Buhid, "Buhd", "Buhid"),
+    (Chakma, "Cakm", "Chakma"),
+    (CanadianAboriginal, "Cans", "Canadian_Aboriginal"),
+    (Carian, "Cari", "Carian"),
+    (Cham, "Cham", "Cham"),
+    (Cherokee, "Cher", "Cherokee"),
+    (Chisoi, "Chis", "Chisoi"),
+    (Chorasmian, "Chrs", "Chorasmian"),
+    (Coptic, "Copt", "Coptic"),
+    (CyproMinoan, "Cpmn", "Cypro_Minoan"),
+    (Cypriot, "Cprt", "Cypriot"),
+    (Cyrillic, "Cyrl", "Cyrillic"),
+    (Devanagari, "Deva", "Devanagari"),
+    (DivesAkuru, "Diak", "Dives_Akuru"),
+    (Dogra, "Dogr", "Dogra"),
+    (Deseret, "Dsrt", "Deseret"),
+    (Duployan, "Dupl", "Duployan"),
+    (EgyptianHieroglyphs, "Egyp", "Egyptian_Hieroglyphs"),
+    (Elbasan, "Elba", "Elbasan"),
+    (Elymaic, "Elym", "Elymaic"),
+    (Ethiopic, "Ethi", "Ethiopic"),
+    (Garay, "Gara", "Garay"),
+    (Georgian, "Geor", "Georgian"),
+    (Glagolitic, "Glag", "Glagolitic"),
+    (GunjalaGondi, "Gong", "Gunjala_Gondi"),
+    (MasaramGondi, "Gonm", "Masaram_Gondi"),
+    (Gothic, "Goth", "Gothic"),
+    (Grantha, "Gran", "Grantha"),
+    (Greek, "Grek", "Greek"),
+    (Gujarati, "Gujr", "Gujarati"),
+    (GurungKhema, "Gukh", "Gurung_Khema"),
+    (Gurmukhi, "Guru", "Gurmukhi"),
+    (Hangul, "Hang", "Hangul"),
+    (Han, "Hani", "Han"),
+    (Hanunoo, "Hano", "Hanunoo"),
+    (Hatran, "Hatr", "Hatran"),
+    (Hebrew, "Hebr", "Hebrew"),
+    (Hiragana, "Hira", "Hiragana"),
+    (AnatolianHieroglyphs, "Hluw", "Anatolian_Hieroglyphs"),
+    (PahawhHmong, "Hmng", "Pahawh_Hmong"),
+    (NyiakengPuachueHmong, "Hmnp", "Nyiakeng_Puachue_Hmong"),
+    (KatakanaOrHiragana, "Hrkt", "Katakana_Or_Hiragana"),
+    (OldHungarian, "Hung", "Old_Hungarian"),
+    (OldItalic, "Ital", "Old_Italic"),
+    (Javanese, "Java", "Javanese"),
+    (KayahLi, "Kali", "Kayah_Li"),
+    (Katakana, "Kana", "Katakana"),
+    (Kawi, "Kawi", "Kawi"),
+    (Kharoshthi, "Khar", "Kharoshthi"),
+    (Khmer, "Khmr", "Khmer"),
+    (Khojki, "Khoj", "Khojki"),
+    (KhitanSmallScript, "Kits", "Khitan_Small_Script"),
+    (Kannada, "Knda", "Kannada"),
+    (KiratRai, "Krai", "Kirat_Rai"),
+    (Kaithi, "Kthi", "Kaithi"),
+    (TaiTham, "Lana", "Tai_Tham"),
+    (Lao, "Laoo", "Lao"),
+    (Latin, "Latn", "Latin"),
+    (Lepcha, "Lepc", "Lepcha"),
+    (Limbu, "Limb", "Limbu"),
+    (LinearA, "Lina", "Linear_A"),
+    (LinearB, "Linb", "Linear_B"),
+    (Lisu, "Lisu", "Lisu"),
+    (Lycian, "Lyci", "Lycian"),
+    (Lydian, "Lydi", "Lydian"),
+    (Mahajani, "Mahj", "Mahajani"),
+    (Makasar, "Maka", "Makasar"),
+    (Mandaic, "Mand", "Mandaic"),
+    (Manichaean, "Mani", "Manichaean"),
+    (Marchen, "Marc", "Marchen"),
+    (Medefaidrin, "Medf", "Medefaidrin"),
+    (MendeKikakui, "Mend", "Mende_Kikakui"),
+    (MeroiticCursive, "Merc", "Meroitic_Cursive"),
+    (MeroiticHieroglyphs, "Mero", "Meroitic_Hieroglyphs"),
+    (Malayalam, "Mlym", "Malayalam"),
+    (Modi, "Modi", "Modi"),
+    (Mongolian, "Mong", "Mongolian"),
+    (Mro, "Mroo", "Mro"),
+    (MeeteiMayek, "Mtei", "Meetei_Mayek"),
+    (Multani, "Mult", "Multani"),
+    (Myanmar, "Mymr", "Myanmar"),
+    (NagMundari, "Nagm", "Nag_Mundari"),
+    (Nandinagari, "Nand", "Nandinagari"),
+    (OldNorthArabian, "Narb", "Old_North_Arabian"),
+    (Nabataean, "Nbat", "Nabataean"),
+    (Newa, "Newa", "Newa"),
+    (Nko, "Nkoo", "Nko"),
+    (Nushu, "Nshu", "Nushu"),
+    (Ogham, "Ogam", "Ogham"),
+    (OlChiki, "Olck", "Ol_Chiki"),
+    (OlOnal, "Onao", "Ol_Onal"),
+    (OldTurkic, "Orkh", "Old_Turkic"),
+    (Oriya, "Orya", "Oriya"),
+    (Osage, "Osge", "Osage"),
+    (Osmanya, "Osma", "Osmanya"),
+    (OldUyghur, "Ougr", "Old_Uyghur"),
+    (Palmyrene, "Palm", "Palmyrene"),
+    (PauCinHau, "Pauc", "Pau_Cin_Hau"),
+    (OldPermic, "Perm", "Old_Permic"),
+    (PhagsPa, "Phag", "Phags_Pa"),
+    (InscriptionalPahlavi, "Phli", "Inscriptional_Pahlavi"),
+    (PsalterPahlavi, "Phlp", "Psalter_Pahlavi"),
+    (Phoenician, "Phnx", "Phoenician"),
+    (Miao, "Plrd", "Miao"),
+    (InscriptionalParthian, "Prti", "Inscriptional_Parthian"),
+    (Rejang, "Rjng", "Rejang"),
+    (HanifiRohingya, "Rohg", "Hanifi_Rohingya"),
+    (Runic, "Runr", "Runic"),
+    (Samaritan, "Samr", "Samaritan"),
+    (OldSouthArabian, "Sarb", "Old_South_Arabian"),
+    (Saurashtra, "Saur", "Saurashtra"),
+    (SignWriting, "Sgnw", "SignWriting"),
+    (Shavian, "Shaw", "Shavian"),
+    (Sharada, "Shrd", "Sharada"),
+    (Siddham, "Sidd", "Siddham"),
+    (Sidetic, "Sidt", "Sidetic"),
+    (Khudawadi, "Sind", "Khudawadi"),
+    (Sinhala, "Sinh", "Sinhala"),
+    (Sogdian, "Sogd", "Sogdian"),
+    (OldSogdian, "Sogo", "Old_Sogdian"),
+    (SoraSompeng, "Sora", "Sora_Sompeng"),
+    (Soyombo, "Soyo", "Soyombo"),
+    (Sundanese, "Sund", "Sundanese"),
+    (Sunuwar, "Sunu", "Sunuwar"),
+    (SylotiNagri, "Sylo", "Syloti_Nagri"),
+    (Syriac, "Syrc", "Syriac"),
+    (Tagbanwa, "Tagb", "Tagbanwa"),
+    (Takri, "Takr", "Takri"),
+    (TaiLe, "Tale", "Tai_Le"),
+    (NewTaiLue, "Talu", "New_Tai_Lue"),
+    (Tamil, "Taml", "Tamil"),
+    (Tangut, "Tang", "Tangut"),
+    (TaiViet, "Tavt", "Tai_Viet"),
+    (TaiYo, "Tayo", "Tai_Yo"),
+    (Telugu, "Telu", "Telugu"),
+    (Tifinagh, "Tfng", "Tifinagh"),
+    (Tagalog, "Tglg", "Tagalog"),
+    (Thaana, "Thaa", "Thaana"),
+    (Thai, "Thai", "Thai"),
+    (Tibetan, "Tibt", "Tibetan"),
+    (Tirhuta, "Tirh", "Tirhuta"),
+    (Tangsa, "Tnsa", "Tangsa"),
+    (Todhri, "Todr", "Todhri"),
+    (TolongSiki, "Tols", "Tolong_Siki"),
+    (Toto, "Toto", "Toto"),
+    (TuluTigalari, "Tutg", "Tulu_Tigalari"),
+    (Ugaritic, "Ugar", "Ugaritic"),
+    (Vai, "Vaii", "Vai"),
+    (Vithkuqi, "Vith", "Vithkuqi"),
+    (WarangCiti, "Wara", "Warang_Citi"),
+    (Wancho, "Wcho", "Wancho"),
+    (OldPersian, "Xpeo", "Old_Persian"),
+    (Cuneiform, "Xsux", "Cuneiform"),
+    (Yezidi, "Yezi", "Yezidi"),
+    (Yi, "Yiii", "Yi"),
+    (ZanabazarSquare, "Zanb", "Zanabazar_Square"),
+    (Inherited, "Zinh", "Inherited"),
+    (Common, "Zyyy", "Common"),
+    (Unknown, "Zzzz", "Unknown"),
+];
+
+impl Script {
+    /// Return an iterator over every script.
+    pub fn all() -> impl Iterator<Item = Script> {
+        SCRIPTS.iter().map(|(script, _, _)| *script)
+    }
+
+    /// The four-letter ISO 15924 code of this script, e.g. "Grek".
+    pub fn short_name(self) -> &'static str {
+        SCRIPTS[self as usize].1
+    }
+
+    /// The long name of this script, e.g. "Greek".
+    pub fn long_name(self) -> &'static str {
+        SCRIPTS[self as usize].2
+    }
+
+    /// Return the script with the short or long name `name`.  The alternative
+    /// codes "Qaac" (Coptic) and "Qaai" (Inherited) are also accepted.
+    pub fn from_name(name: &str) -> Option<Script> {
+        match name {
+            "Qaac" => return Some(Coptic),
+            "Qaai" => return Some(Inherited),
+            _ => {}
+        }
+        SCRIPTS
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(script, _, _)| *script)
+    }
+}
+
+/// The Script and Script_Extensions of every code point.
+pub struct ScriptData {
+    /// The script of every code point, from `Scripts.txt`.
+    scripts: EnumeratedProperty<Script>,
+
+    /// The script extensions of every code point listed in
+    /// `ScriptExtensions.txt`.  Every other code point's script extensions
+    /// consist of its script alone.
+    extensions: HashMap<u32, Vec<Script>>,
+}
+
+impl ScriptData {
+    /// The Script of `code`.
+    pub fn script(&self, code: u32) -> Script {
+        self.scripts.get(code)
+    }
+
+    /// The Script_Extensions of `code`: the scripts it's used with.
+    ///
+    /// Code points used with multiple scripts (e.g. U+3001 IDEOGRAPHIC COMMA)
+    /// have Script Common or Inherited but several script extensions; every
+    /// other code point's sole script extension is its script.
+    pub fn script_extensions(&self, code: u32) -> Vec<Script> {
+        match self.extensions.get(&code) {
+            Some(extensions) => extensions.clone(),
+            None => vec![self.script(code)],
+        }
+    }
+
+    /// Return the set of all code points whose Script is `script`.
+    pub fn script_set(&self, script: Script) -> CodePointSet {
+        self.scripts.code_points(script)
+    }
+
+    /// Return the set of all code points whose Script_Extensions include
+    /// `script`.
+    pub fn script_extensions_set(&self, script: Script) -> CodePointSet {
+        let mut set = self.script_set(script);
+        for (code, extensions) in &self.extensions {
+            if extensions.contains(&script) {
+                set.insert(*code);
+            } else {
+                set.remove(code);
+            }
+        }
+        set
+    }
+}
+
+impl CodePoint {
+    /// The Script of this code point.
+    pub fn script(&self, scripts: &ScriptData) -> Script {
+        scripts.script(self.code)
+    }
+
+    /// The Script_Extensions of this code point.
+    pub fn script_extensions(&self, scripts: &ScriptData) -> Vec<Script> {
+        scripts.script_extensions(self.code)
+    }
+}
+
+/// Parse the Script and Script_Extensions of every code point from the
+/// `Scripts.txt` and `ScriptExtensions.txt` in `source`.
+pub fn process_scripts(source: &UcdSource) -> Result<ScriptData, UcdError> {
+    parse_scripts(
+        UcdFile::read(source, SCRIPTS_TXT)?,
+        UcdFile::read(source, SCRIPT_EXTENSIONS_TXT)?,
+    )
+}
+
+/// Parse the Script and Script_Extensions of every code point from
+/// `scripts_file` and `extensions_file`, the tokenized `Scripts.txt` and
+/// `ScriptExtensions.txt`.
+fn parse_scripts(scripts_file: UcdFile, extensions_file: UcdFile) -> Result<ScriptData, UcdError> {
+    let scripts =
+        EnumeratedProperty::parse(scripts_file, "a script name", Script::from_name, Unknown)?;
+
+    // `ScriptExtensions.txt` lists space-separated short script names, e.g.
+    // `3001 ; Bopo Hang Hani Hira Kana Yiii # Po IDEOGRAPHIC COMMA`.  Its
+    // `@missing` lines just say that unlisted code points default to their
+    // Script, which `ScriptData::script_extensions` does anyway.
+    let mut extensions = HashMap::new();
+    for record in extensions_file.records() {
+        let record = record?;
+        let names = record.field(0, "a list of script names")?;
+        let list = names
+            .split_whitespace()
+            .map(|name| Script::from_name(name).ok_or_else(|| record.error(name, "a script name")))
+            .collect::<Result<Vec<_>, _>>()?;
+        if list.is_empty() {
+            return Err(record.error(names, "a list of script names"));
+        }
+        extensions.extend(record.range.iter().map(|code| (code, list.clone())));
+    }
+
+    Ok(ScriptData {
+        scripts,
+        extensions,
+    })
+}
+
+#[test]
+fn check_script_names() {
+    for (i, script) in Script::all().enumerate() {
+        assert_eq!(script as usize, i, "SCRIPTS is in declaration order");
+        assert_eq!(Script::from_name(script.short_name()), Some(script));
+        assert_eq!(Script::from_name(script.long_name()), Some(script));
+    }
+    assert_eq!(Script::from_name("Grek"), Some(Greek));
+    assert_eq!(Script::from_name("Zinh"), Some(Inherited));
+    assert_eq!(Script::from_name("Qaai"), Some(Inherited));
+    assert_eq!(Script::from_name("Klingon"), None);
+}
+
+#[test]
+fn check_scripts() {
+    let scripts_file = UcdFile::new(
+        SCRIPTS_TXT,
+        "# Scripts-13.0.0.txt\n\
+         # @missing: 0000..10FFFF; Unknown\n\
+         0000..0040    ; Common # Cc  [32] <control-0000>..<control-001F>\n\
+         0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n\
+         0300..036F    ; Inherited # Mn [112] COMBINING GRAVE ACCENT..\n\
+         0391..03A1    ; Greek # L&  [17] GREEK CAPITAL LETTER ALPHA..GREEK CAPITAL LETTER RHO\n\
+         3001          ; Common # Po       IDEOGRAPHIC COMMA\n",
+    );
+    let extensions_file = UcdFile::new(
+        SCRIPT_EXTENSIONS_TXT,
+        "# ScriptExtensions-13.0.0.txt\n\
+         # @missing: 0000..10FFFF; <script>\n\
+         0342          ; Grek # Mn       COMBINING GREEK PERISPOMENI\n\
+         3001          ; Bopo Hang Hani Hira Kana Yiii # Po IDEOGRAPHIC COMMA\n",
+    );
+
+    let scripts = parse_scripts(scripts_file, extensions_file).expect("script data");
+    assert_eq!(scripts.script('A' as u32), Latin);
+    assert_eq!(scripts.script(0x0391), Greek);
+    assert_eq!(scripts.script(0x0342), Inherited);
+    assert_eq!(scripts.script(0x3001), Common);
+    assert_eq!(scripts.script(0x10FFFF), Unknown);
+
+    assert_eq!(scripts.script_extensions('A' as u32), [Latin]);
+    assert_eq!(scripts.script_extensions(0x0342), [Greek]);
+    assert_eq!(
+        scripts.script_extensions(0x3001),
+        [Bopomofo, Hangul, Han, Hiragana, Katakana, Yi]
+    );
+
+    assert_eq!(scripts.script_set(Greek).len(), 17);
+    assert_eq!(scripts.script_extensions_set(Greek).len(), 18);
+    assert_eq!(scripts.script_set(Inherited).len(), 112);
+    assert_eq!(scripts.script_extensions_set(Inherited).len(), 111);
+    assert!(scripts.script_extensions_set(Han).contains(&0x3001));
+
+    let error = parse_scripts(
+        UcdFile::new(SCRIPTS_TXT, ""),
+        UcdFile::new(
+            SCRIPT_EXTENSIONS_TXT,
+            "0342 ; Grek Klng # Mn COMBINING GREEK PERISPOMENI\n",
+        ),
+    )
+    .err()
+    .expect("unknown script");
+    assert_eq!(
+        error.to_string(),
+        "ScriptExtensions.txt:1: expected a script name, found \"Klng\""
+    );
+}
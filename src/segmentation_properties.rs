@@ -0,0 +1,535 @@
+//! Processes the auxiliary break property files `GraphemeBreakProperty.txt`,
+//! `WordBreakProperty.txt`, and `SentenceBreakProperty.txt` to determine the
+//! Grapheme_Cluster_Break, Word_Break, and Sentence_Break values of every code
+//! point, as needed by `Intl.Segmenter`.
+//!
+//! These files aren't embedded in this crate, so they must be read from the
+//! `auxiliary` subdirectory of a [`UcdSource::Directory`].
+//!
+//! See <https://www.unicode.org/reports/tr29/> for details.
+
+use crate::constants::MAX_CODE_POINT;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{DataLines, UcdFile};
+use crate::ucd_source::UcdSource;
+use std::collections::HashMap;
+
+/// The name of the file listing Grapheme_Cluster_Break values, relative to
+/// the UCD directory.
+pub(crate) const GRAPHEME_BREAK_PROPERTY_TXT: &str = "auxiliary/GraphemeBreakProperty.txt";
+
+/// The name of the file listing Word_Break values, relative to the UCD
+/// directory.
+pub(crate) const WORD_BREAK_PROPERTY_TXT: &str = "auxiliary/WordBreakProperty.txt";
+
+/// The name of the file listing Sentence_Break values, relative to the UCD
+/// directory.
+pub(crate) const SENTENCE_BREAK_PROPERTY_TXT: &str = "auxiliary/SentenceBreakProperty.txt";
+
+/// The Grapheme_Cluster_Break value of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphemeClusterBreak {
+    Other,
+    CR,
+    LF,
+    Control,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    LV,
+    LVT,
+}
+
+/// Every Grapheme_Cluster_Break value with its short and long names.
+const GRAPHEME_CLUSTER_BREAKS: [(GraphemeClusterBreak, &str, &str); 14] = [
+    (GraphemeClusterBreak::Other, "XX", "Other"),
+    (GraphemeClusterBreak::CR, "CR", "CR"),
+    (GraphemeClusterBreak::LF, "LF", "LF"),
+    (GraphemeClusterBreak::Control, "CN", "Control"),
+    (GraphemeClusterBreak::Extend, "EX", "Extend"),
+    (GraphemeClusterBreak::ZWJ, "ZWJ", "ZWJ"),
+    (
+        GraphemeClusterBreak::RegionalIndicator,
+        "RI",
+        "Regional_Indicator",
+    ),
+    (GraphemeClusterBreak::Prepend, "PP", "Prepend"),
+    (GraphemeClusterBreak::SpacingMark, "SM", "SpacingMark"),
+    (GraphemeClusterBreak::L, "L", "L"),
+    (GraphemeClusterBreak::V, "V", "V"),
+    (GraphemeClusterBreak::T, "T", "T"),
+    (GraphemeClusterBreak::LV, "LV", "LV"),
+    (GraphemeClusterBreak::LVT, "LVT", "LVT"),
+];
+
+impl GraphemeClusterBreak {
+    /// Return an iterator over every Grapheme_Cluster_Break value.
+    pub fn all() -> impl Iterator<Item = GraphemeClusterBreak> {
+        GRAPHEME_CLUSTER_BREAKS.iter().map(|(value, _, _)| *value)
+    }
+
+    /// The abbreviated name of this value, e.g. "RI".
+    pub fn short_name(self) -> &'static str {
+        GRAPHEME_CLUSTER_BREAKS[self as usize].1
+    }
+
+    /// The long name of this value, e.g. "Regional_Indicator".
+    pub fn long_name(self) -> &'static str {
+        GRAPHEME_CLUSTER_BREAKS[self as usize].2
+    }
+
+    /// Return the value with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<GraphemeClusterBreak> {
+        GRAPHEME_CLUSTER_BREAKS
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(value, _, _)| *value)
+    }
+}
+
+/// The Word_Break value of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WordBreak {
+    Other,
+    CR,
+    LF,
+    Newline,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Format,
+    Katakana,
+    HebrewLetter,
+    ALetter,
+    SingleQuote,
+    DoubleQuote,
+    MidNumLet,
+    MidLetter,
+    MidNum,
+    Numeric,
+    ExtendNumLet,
+    WSegSpace,
+}
+
+/// Every Word_Break value with its short and long names.
+const WORD_BREAKS: [(WordBreak, &str, &str); 19] = [
+    (WordBreak::Other, "XX", "Other"),
+    (WordBreak::CR, "CR", "CR"),
+    (WordBreak::LF, "LF", "LF"),
+    (WordBreak::Newline, "NL", "Newline"),
+    (WordBreak::Extend, "Extend", "Extend"),
+    (WordBreak::ZWJ, "ZWJ", "ZWJ"),
+    (WordBreak::RegionalIndicator, "RI", "Regional_Indicator"),
+    (WordBreak::Format, "FO", "Format"),
+    (WordBreak::Katakana, "KA", "Katakana"),
+    (WordBreak::HebrewLetter, "HL", "Hebrew_Letter"),
+    (WordBreak::ALetter, "LE", "ALetter"),
+    (WordBreak::SingleQuote, "SQ", "Single_Quote"),
+    (WordBreak::DoubleQuote, "DQ", "Double_Quote"),
+    (WordBreak::MidNumLet, "MB", "MidNumLet"),
+    (WordBreak::MidLetter, "ML", "MidLetter"),
+    (WordBreak::MidNum, "MN", "MidNum"),
+    (WordBreak::Numeric, "NU", "Numeric"),
+    (WordBreak::ExtendNumLet, "EX", "ExtendNumLet"),
+    (WordBreak::WSegSpace, "WSegSpace", "WSegSpace"),
+];
+
+impl WordBreak {
+    /// Return an iterator over every Word_Break value.
+    pub fn all() -> impl Iterator<Item = WordBreak> {
+        WORD_BREAKS.iter().map(|(value, _, _)| *value)
+    }
+
+    /// The abbreviated name of this value, e.g. "LE".
+    pub fn short_name(self) -> &'static str {
+        WORD_BREAKS[self as usize].1
+    }
+
+    /// The long name of this value, e.g. "ALetter".
+    pub fn long_name(self) -> &'static str {
+        WORD_BREAKS[self as usize].2
+    }
+
+    /// Return the value with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<WordBreak> {
+        WORD_BREAKS
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(value, _, _)| *value)
+    }
+}
+
+/// The Sentence_Break value of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SentenceBreak {
+    Other,
+    CR,
+    LF,
+    Extend,
+    Sep,
+    Format,
+    Sp,
+    Lower,
+    Upper,
+    OLetter,
+    Numeric,
+    ATerm,
+    SContinue,
+    STerm,
+    Close,
+}
+
+/// Every Sentence_Break value with its short and long names.
+const SENTENCE_BREAKS: [(SentenceBreak, &str, &str); 15] = [
+    (SentenceBreak::Other, "XX", "Other"),
+    (SentenceBreak::CR, "CR", "CR"),
+    (SentenceBreak::LF, "LF", "LF"),
+    (SentenceBreak::Extend, "EX", "Extend"),
+    (SentenceBreak::Sep, "SE", "Sep"),
+    (SentenceBreak::Format, "FO", "Format"),
+    (SentenceBreak::Sp, "SP", "Sp"),
+    (SentenceBreak::Lower, "LO", "Lower"),
+    (SentenceBreak::Upper, "UP", "Upper"),
+    (SentenceBreak::OLetter, "LE", "OLetter"),
+    (SentenceBreak::Numeric, "NU", "Numeric"),
+    (SentenceBreak::ATerm, "AT", "ATerm"),
+    (SentenceBreak::SContinue, "SC", "SContinue"),
+    (SentenceBreak::STerm, "ST", "STerm"),
+    (SentenceBreak::Close, "CL", "Close"),
+];
+
+impl SentenceBreak {
+    /// Return an iterator over every Sentence_Break value.
+    pub fn all() -> impl Iterator<Item = SentenceBreak> {
+        SENTENCE_BREAKS.iter().map(|(value, _, _)| *value)
+    }
+
+    /// The abbreviated name of this value, e.g. "ST".
+    pub fn short_name(self) -> &'static str {
+        SENTENCE_BREAKS[self as usize].1
+    }
+
+    /// The long name of this value, e.g. "STerm".
+    pub fn long_name(self) -> &'static str {
+        SENTENCE_BREAKS[self as usize].2
+    }
+
+    /// Return the value with the short or long name `name`.
+    pub fn from_name(name: &str) -> Option<SentenceBreak> {
+        SENTENCE_BREAKS
+            .iter()
+            .find(|(_, short, long)| *short == name || *long == name)
+            .map(|(value, _, _)| *value)
+    }
+}
+
+/// The break property values of a code point.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BreakValues {
+    pub grapheme_cluster_break: GraphemeClusterBreak,
+    pub word_break: WordBreak,
+    pub sentence_break: SentenceBreak,
+}
+
+/// The break property values of every code point, in the form of
+/// [`bmp::BMPInfo`](crate::bmp::BMPInfo): a table of unique values and an
+/// index into it for every code point.  `index` can be compressed using
+/// [`table::split_table`](crate::table::split_table).
+pub struct SegmentationInfo {
+    /// A list of unique `BreakValues`.  The first element is the values of
+    /// code points not listed in any break property file (all `Other`).
+    pub table: Vec<BreakValues>,
+
+    /// The index in `table` of the values of each code point, indexed by code
+    /// point (up to and including `MAX_CODE_POINT`).
+    pub index: Vec<u32>,
+}
+
+/// The Grapheme_Cluster_Break, Word_Break, and Sentence_Break values of every
+/// code point.
+pub struct SegmentationData {
+    grapheme_cluster_breaks: HashMap<u32, GraphemeClusterBreak>,
+    word_breaks: HashMap<u32, WordBreak>,
+    sentence_breaks: HashMap<u32, SentenceBreak>,
+}
+
+impl SegmentationData {
+    /// The Grapheme_Cluster_Break value of `code`.
+    pub fn grapheme_cluster_break(&self, code: u32) -> GraphemeClusterBreak {
+        self.grapheme_cluster_breaks
+            .get(&code)
+            .copied()
+            .unwrap_or(GraphemeClusterBreak::Other)
+    }
+
+    /// The Word_Break value of `code`.
+    pub fn word_break(&self, code: u32) -> WordBreak {
+        self.word_breaks
+            .get(&code)
+            .copied()
+            .unwrap_or(WordBreak::Other)
+    }
+
+    /// The Sentence_Break value of `code`.
+    pub fn sentence_break(&self, code: u32) -> SentenceBreak {
+        self.sentence_breaks
+            .get(&code)
+            .copied()
+            .unwrap_or(SentenceBreak::Other)
+    }
+
+    /// The break property values of `code`.
+    pub fn break_values(&self, code: u32) -> BreakValues {
+        BreakValues {
+            grapheme_cluster_break: self.grapheme_cluster_break(code),
+            word_break: self.word_break(code),
+            sentence_break: self.sentence_break(code),
+        }
+    }
+
+    /// Generate the break property values of every code point as a table of
+    /// unique values and a per-code-point index into it.
+    pub fn generate_segmentation_info(&self) -> SegmentationInfo {
+        let mut table = vec![BreakValues {
+            grapheme_cluster_break: GraphemeClusterBreak::Other,
+            word_break: WordBreak::Other,
+            sentence_break: SentenceBreak::Other,
+        }];
+        let mut cache = HashMap::<BreakValues, u32>::new();
+        cache.insert(table[0], 0);
+
+        let index = (0..=MAX_CODE_POINT)
+            .map(|code| {
+                let values = self.break_values(code);
+                *cache.entry(values).or_insert_with(|| {
+                    table.push(values);
+                    (table.len() - 1) as u32
+                })
+            })
+            .collect();
+
+        SegmentationInfo { table, index }
+    }
+}
+
+/// Parse the values of a break property from `file`, whose records consist
+/// of a code point range and a value name.
+fn parse_break_property<T: Copy>(
+    file: UcdFile,
+    from_name: fn(&str) -> Option<T>,
+) -> Result<HashMap<u32, T>, UcdError> {
+    let mut values = HashMap::new();
+    for record in file.records() {
+        let record = record?;
+        let name = record.field(0, "a property value")?;
+        let value = from_name(name).ok_or_else(|| record.error(name, "a property value"))?;
+        values.extend(record.range.iter().map(|code| (code, value)));
+    }
+    Ok(values)
+}
+
+/// Parse the break property values of every code point from the
+/// `auxiliary/GraphemeBreakProperty.txt`, `auxiliary/WordBreakProperty.txt`,
+/// and `auxiliary/SentenceBreakProperty.txt` in `source`.
+pub fn process_segmentation_properties(source: &UcdSource) -> Result<SegmentationData, UcdError> {
+    parse_segmentation_properties(
+        UcdFile::read(source, GRAPHEME_BREAK_PROPERTY_TXT)?,
+        UcdFile::read(source, WORD_BREAK_PROPERTY_TXT)?,
+        UcdFile::read(source, SENTENCE_BREAK_PROPERTY_TXT)?,
+    )
+}
+
+pub(crate) fn parse_segmentation_properties(
+    grapheme_file: UcdFile,
+    word_file: UcdFile,
+    sentence_file: UcdFile,
+) -> Result<SegmentationData, UcdError> {
+    Ok(SegmentationData {
+        grapheme_cluster_breaks: parse_break_property(
+            grapheme_file,
+            GraphemeClusterBreak::from_name,
+        )?,
+        word_breaks: parse_break_property(word_file, WordBreak::from_name)?,
+        sentence_breaks: parse_break_property(sentence_file, SentenceBreak::from_name)?,
+    })
+}
+
+/// Check the boundaries computed by `boundaries` against the break test file
+/// `file` in `source` (e.g. `auxiliary/GraphemeBreakTest.txt`), returning the
+/// number of test lines checked, or an error describing the first failure.
+///
+/// Each test line is a sequence of hexadecimal code points, separated and
+/// surrounded by `÷` where there's a boundary and `×` where there isn't, e.g.
+/// `÷ 0020 × 0308 ÷ 0020 ÷`.  (The mark at the start of the line is ignored:
+/// it's `÷` in segmentation tests but `×` in line break tests.)  `boundaries`
+/// must return the offset of every boundary in a string except its start.
+pub(crate) fn check_break_test(
+    source: &UcdSource,
+    file: &'static str,
+    boundaries: impl Fn(&[u32]) -> Vec<usize>,
+) -> Result<usize, UcdError> {
+    check_break_test_lines(file, DataLines::new(source.read(file)?), boundaries)
+}
+
+/// Check the boundaries computed by `boundaries` against the lines of the
+/// break test file `file`, as [`check_break_test`] does.
+pub(crate) fn check_break_test_lines(
+    file: &'static str,
+    lines: DataLines,
+    boundaries: impl Fn(&[u32]) -> Vec<usize>,
+) -> Result<usize, UcdError> {
+    let mut checked = 0;
+    for line in lines {
+        let test = line.fields[0];
+        let error = |expected: &str| UcdError::parse(file, line.line, test, expected);
+
+        let mut string = vec![];
+        let mut expected = vec![];
+        for (i, token) in test.split_whitespace().enumerate().skip(1) {
+            if i % 2 == 1 {
+                let code = u32::from_str_radix(token, 16)
+                    .map_err(|_| error("a hexadecimal code point"))?;
+                string.push(code);
+            } else if token == "÷" {
+                expected.push(string.len());
+            } else if token != "×" {
+                return Err(error("÷ or ×"));
+            }
+        }
+
+        let found = boundaries(&string);
+        if string.is_empty() || found != expected {
+            let mut actual = String::from(test.split_whitespace().next().unwrap_or(""));
+            for (i, code) in string.iter().enumerate() {
+                let mark = if found.contains(&(i + 1)) { '÷' } else { '×' };
+                actual.push_str(&format!(" {:04X} {}", code, mark));
+            }
+            return Err(UcdError::parse(
+                file,
+                line.line,
+                &actual,
+                format!("boundaries {}", test),
+            ));
+        }
+        checked += 1;
+    }
+    Ok(checked)
+}
+
+#[test]
+fn check_break_value_names() {
+    for (i, value) in GraphemeClusterBreak::all().enumerate() {
+        assert_eq!(
+            value as usize, i,
+            "GRAPHEME_CLUSTER_BREAKS is in declaration order"
+        );
+        assert_eq!(
+            GraphemeClusterBreak::from_name(value.short_name()),
+            Some(value)
+        );
+        assert_eq!(
+            GraphemeClusterBreak::from_name(value.long_name()),
+            Some(value)
+        );
+    }
+    for (i, value) in WordBreak::all().enumerate() {
+        assert_eq!(value as usize, i, "WORD_BREAKS is in declaration order");
+        assert_eq!(WordBreak::from_name(value.short_name()), Some(value));
+        assert_eq!(WordBreak::from_name(value.long_name()), Some(value));
+    }
+    for (i, value) in SentenceBreak::all().enumerate() {
+        assert_eq!(value as usize, i, "SENTENCE_BREAKS is in declaration order");
+        assert_eq!(SentenceBreak::from_name(value.short_name()), Some(value));
+        assert_eq!(SentenceBreak::from_name(value.long_name()), Some(value));
+    }
+}
+
+#[test]
+fn check_segmentation_properties() {
+    let grapheme_file = || {
+        UcdFile::new(
+            GRAPHEME_BREAK_PROPERTY_TXT,
+            "# GraphemeBreakProperty-13.0.0.txt\n\
+             000D          ; CR # Cc       <control-000D>\n\
+             000A          ; LF # Cc       <control-000A>\n\
+             0300..036F    ; Extend # Mn [112] COMBINING GRAVE ACCENT..COMBINING LATIN SMALL LETTER X\n\
+             1F1E6..1F1FF  ; Regional_Indicator # So  [26] REGIONAL INDICATOR SYMBOL LETTER A..\n\
+             AC00          ; LV # Lo       HANGUL SYLLABLE GA\n",
+        )
+    };
+    let word_file = || {
+        UcdFile::new(
+            WORD_BREAK_PROPERTY_TXT,
+            "# WordBreakProperty-13.0.0.txt\n\
+             000D          ; CR # Cc       <control-000D>\n\
+             0027          ; Single_Quote # Po       APOSTROPHE\n\
+             0041..005A    ; ALetter # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n",
+        )
+    };
+    let sentence_file = || {
+        UcdFile::new(
+            SENTENCE_BREAK_PROPERTY_TXT,
+            "# SentenceBreakProperty-13.0.0.txt\n\
+             000D          ; CR # Cc       <control-000D>\n\
+             002E          ; ATerm # Po       FULL STOP\n\
+             0041..005A    ; Upper # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n",
+        )
+    };
+
+    let data = parse_segmentation_properties(grapheme_file(), word_file(), sentence_file())
+        .expect("break properties");
+    assert_eq!(
+        data.grapheme_cluster_break(0x000D),
+        GraphemeClusterBreak::CR
+    );
+    assert_eq!(
+        data.grapheme_cluster_break(0x0301),
+        GraphemeClusterBreak::Extend
+    );
+    assert_eq!(
+        data.grapheme_cluster_break(0x1F1FF),
+        GraphemeClusterBreak::RegionalIndicator
+    );
+    assert_eq!(
+        data.grapheme_cluster_break('A' as u32),
+        GraphemeClusterBreak::Other
+    );
+    assert_eq!(data.word_break('\'' as u32), WordBreak::SingleQuote);
+    assert_eq!(data.word_break('Q' as u32), WordBreak::ALetter);
+    assert_eq!(data.sentence_break('.' as u32), SentenceBreak::ATerm);
+    assert_eq!(data.sentence_break('a' as u32), SentenceBreak::Other);
+
+    let info = data.generate_segmentation_info();
+    assert_eq!(info.index.len(), MAX_CODE_POINT as usize + 1);
+    // Other; CR; LF; Extend; RI; LV; Single_Quote; ALetter/Upper; ATerm.
+    assert_eq!(info.table.len(), 9);
+    assert_eq!(info.index[0x10FFFF], 0);
+    assert_eq!(
+        info.table[info.index[0x000D] as usize],
+        data.break_values(0x000D)
+    );
+    assert_eq!(info.index['A' as usize], info.index['Z' as usize]);
+
+    let split = crate::table::split_table(&info.index);
+    assert!(split.index1.len() + split.index2.len() < info.index.len());
+
+    let error = parse_segmentation_properties(
+        grapheme_file(),
+        word_file(),
+        UcdFile::new(
+            SENTENCE_BREAK_PROPERTY_TXT,
+            "002E ; Period # Po FULL STOP\n",
+        ),
+    )
+    .err()
+    .expect("unknown value");
+    assert_eq!(
+        error.to_string(),
+        "auxiliary/SentenceBreakProperty.txt:1: expected a property value, found \"Period\""
+    );
+}
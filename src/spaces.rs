@@ -7,6 +7,9 @@ use crate::constants::{
     LINE_TABULATION, NO_BREAK_SPACE, PARAGRAPH_SEPARATOR, ZERO_WIDTH_NO_BREAK_SPACE,
 };
 use crate::constants::{LINE_TERMINATOR, MAX_BMP, WHITE_SPACE};
+use crate::general_category::GeneralCategory;
+#[cfg(test)]
+use crate::ucd_source::UcdSource;
 
 use crate::types::CodePointSet;
 
@@ -15,11 +18,16 @@ use crate::types::CodePointSet;
 ///
 /// Note that `WhiteSpace` includes all code points in the Unicode "Space
 /// Separator", i.e. "Zs", category.
+///
+/// This isn't the Unicode White_Space property listed in `PropList.txt` (see
+/// [`prop_list`](crate::prop_list)): that property includes U+0085 NEXT LINE
+/// but not U+FEFF ZERO WIDTH NO-BREAK SPACE, while JavaScript's productions do
+/// the reverse.
 pub fn compute_white_space(code_point_table: &code_point_table::CodePointTable) -> CodePointSet {
     let mut space_set = CodePointSet::new();
     for code_point in code_point_table.iter() {
         let code = code_point.code;
-        if code_point.category() == "Zs"
+        if code_point.general_category() == GeneralCategory::SpaceSeparator
             || WHITE_SPACE.contains(&code)
             || LINE_TERMINATOR.contains(&code)
         {
@@ -36,7 +44,8 @@ pub fn compute_white_space(code_point_table: &code_point_table::CodePointTable)
 
 #[test]
 fn space_set_contains() {
-    let table = code_point_table::generate_code_point_table();
+    let table =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
     let spaces = compute_white_space(&table);
     assert!(spaces.contains(&CHARACTER_TABULATION));
     assert!(spaces.contains(&LINE_TABULATION));
@@ -1,101 +1,207 @@
 //! Processes `SpecialCasing.txt` to extract all special casing information.
 
-use crate::bmp;
-use crate::constants::MAX_BMP;
-use crate::types::MappedCodePoint;
+use crate::bmp::{self, BMPInfo};
+use crate::code_point_table::{self, CodePointTable};
+use crate::constants::{COMBINING_DOT_ABOVE, LATIN_CAPITAL_LETTER_I, MAX_BMP, MAX_CODE_POINT};
 #[cfg(test)]
-use crate::{
-    code_point_table,
-    constants::{
-        COMBINING_DOT_ABOVE, GREEK_CAPITAL_LETTER_SIGMA, GREEK_SMALL_LETTER_FINAL_SIGMA,
-        GREEK_SMALL_LETTER_SIGMA, LATIN_CAPITAL_LETTER_I_WITH_DOT_ABOVE, LATIN_CAPITAL_LETTER_S,
-        LATIN_SMALL_LETTER_I, LATIN_SMALL_LETTER_SHARP_S,
-    },
-    derived_core_properties,
+use crate::constants::{
+    GREEK_CAPITAL_LETTER_SIGMA, GREEK_SMALL_LETTER_FINAL_SIGMA, GREEK_SMALL_LETTER_SIGMA,
+    LATIN_CAPITAL_LETTER_I_WITH_DOT_ABOVE, LATIN_CAPITAL_LETTER_S, LATIN_SMALL_LETTER_I,
+    LATIN_SMALL_LETTER_SHARP_S,
 };
+use crate::derived_core_properties::{self, DerivedCorePropertyData, DerivedProperty};
+use crate::non_bmp::{self, NonBMPInfo};
+use crate::prop_list;
+use crate::types::CodePointSet;
+use crate::ucd_error::UcdError;
+use crate::ucd_file::{Entry, Record, UcdFile};
+use crate::ucd_source::UcdSource;
 use std::collections::BTreeMap;
+use std::sync::OnceLock;
 #[cfg(test)]
 use std::{collections::HashSet, iter::FromIterator};
 
-static SPECIAL_CASING_TXT: &str = include_str!("data/SpecialCasing.txt");
+/// A condition on the context surrounding a code point, under which a casing
+/// mapping applies.  See table 3-17, "Context Specification for Casing", in
+/// section 3.13 of the Unicode Standard.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CasingContext {
+    /// The code point is preceded by a cased letter (ignoring case-ignorable
+    /// code points), and isn't followed by one.
+    FinalSigma,
+
+    /// There's a Soft_Dotted code point before the code point, with no
+    /// intervening code point of combining class 0 or 230.
+    AfterSoftDotted,
+
+    /// The code point is followed by a combining mark of combining class 230,
+    /// with no intervening code point of combining class 0 or 230.
+    MoreAbove,
+
+    /// The code point is followed by U+0307 COMBINING DOT ABOVE, with no
+    /// intervening code point of combining class 0 or 230.
+    BeforeDot,
+
+    /// The negation of `BeforeDot`.
+    NotBeforeDot,
+
+    /// There's an uppercase I before the code point, with no intervening code
+    /// point of combining class 0 or 230.
+    AfterI,
+}
+
+/// Every casing context with its name in `SpecialCasing.txt`.
+const CASING_CONTEXTS: [(CasingContext, &str); 6] = [
+    (CasingContext::FinalSigma, "Final_Sigma"),
+    (CasingContext::AfterSoftDotted, "After_Soft_Dotted"),
+    (CasingContext::MoreAbove, "More_Above"),
+    (CasingContext::BeforeDot, "Before_Dot"),
+    (CasingContext::NotBeforeDot, "Not_Before_Dot"),
+    (CasingContext::AfterI, "After_I"),
+];
+
+impl CasingContext {
+    /// Return an iterator over every casing context.
+    pub fn all() -> impl Iterator<Item = CasingContext> {
+        CASING_CONTEXTS.iter().map(|(context, _)| *context)
+    }
+
+    /// The name of this context in `SpecialCasing.txt`, e.g. "Final_Sigma".
+    pub fn name(self) -> &'static str {
+        CASING_CONTEXTS[self as usize].1
+    }
+
+    /// Return the context named `name` in `SpecialCasing.txt`.
+    pub fn from_name(name: &str) -> Option<CasingContext> {
+        CASING_CONTEXTS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(context, _)| *context)
+    }
+}
+
+/// A language with language-specific casing mappings.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Language {
+    Azeri,
+    Lithuanian,
+    Turkish,
+}
+
+/// Every language with its language code, as used in `SpecialCasing.txt`.
+const LANGUAGES: [(Language, &str); 3] = [
+    (Language::Azeri, "az"),
+    (Language::Lithuanian, "lt"),
+    (Language::Turkish, "tr"),
+];
+
+impl Language {
+    /// Return an iterator over every language.
+    pub fn all() -> impl Iterator<Item = Language> {
+        LANGUAGES.iter().map(|(language, _)| *language)
+    }
+
+    /// The language code of this language, e.g. "tr".
+    pub fn code(self) -> &'static str {
+        LANGUAGES[self as usize].1
+    }
+
+    /// Return the language with the language code `code`.
+    pub fn from_code(code: &str) -> Option<Language> {
+        LANGUAGES
+            .iter()
+            .find(|(_, c)| *c == code)
+            .map(|(language, _)| *language)
+    }
+}
 
 pub struct SpecialCase {
     code: u32,
     lower: Vec<u32>,
+    title: Vec<u32>,
     upper: Vec<u32>,
-    languages: Vec<&'static str>,
-    contexts: Vec<&'static str>,
+    languages: Vec<Language>,
+    contexts: Vec<CasingContext>,
 }
 
+/// The name of the file parsed by `SpecialCasing`.
+const SPECIAL_CASING_TXT: &str = "SpecialCasing.txt";
+
 struct SpecialCasing {
-    lines: std::str::Lines<'static>,
+    file: UcdFile,
 }
 
-impl SpecialCasing {
-    fn read() -> SpecialCasing {
-        SpecialCasing {
-            lines: SPECIAL_CASING_TXT.lines(),
+/// Convert a `SpecialCasing.txt` record into a `SpecialCase`.
+fn parse_special_case(record: &Record) -> Result<SpecialCase, UcdError> {
+    if record.range.first != record.range.last {
+        return Err(record.error(
+            &format!("{:04X}..{:04X}", record.range.first, record.range.last),
+            "a single code point",
+        ));
+    }
+    let code = record.range.first;
+
+    let parse_codes = |index: usize, expected: &str| {
+        let field = record.field(index, expected)?;
+        if field.is_empty() {
+            Ok(vec![]) // a code point can be replaced with nothing
+        } else {
+            field
+                .split(' ')
+                .map(|code| {
+                    u32::from_str_radix(code, 16)
+                        .ok()
+                        .filter(|code| *code <= MAX_CODE_POINT)
+                        .ok_or_else(|| record.error(code, "a hexadecimal code in a mapping"))
+                })
+                .collect::<Result<Vec<u32>, UcdError>>()
+        }
+    };
+
+    let lower = parse_codes(0, "a lowercase mapping")?;
+    let title = parse_codes(1, "a titlecase mapping")?;
+    let upper = parse_codes(2, "an uppercase mapping")?;
+
+    let mut languages = vec![];
+    let mut contexts = vec![];
+    if let Some(conditions) = record.fields.get(3) {
+        for cond in conditions.split(' ') {
+            if cond.chars().next().expect("condition").is_lowercase() {
+                languages.push(
+                    Language::from_code(cond)
+                        .ok_or_else(|| record.error(cond, "a language code"))?,
+                );
+            } else {
+                contexts.push(
+                    CasingContext::from_name(cond)
+                        .ok_or_else(|| record.error(cond, "a casing context"))?,
+                );
+            }
         }
     }
+
+    Ok(SpecialCase {
+        code,
+        lower,
+        title,
+        upper,
+        languages,
+        contexts,
+    })
 }
 
 impl Iterator for SpecialCasing {
-    type Item = SpecialCase;
+    type Item = Result<SpecialCase, UcdError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             // Format:
             // <code>; <lower>; <title>; <upper>; (<condition_list>;)? # <comment>
-            let line_with_comment = self.lines.next()?;
-            let line = line_with_comment
-                .split('#')
-                .nth(0)
-                .expect("splitting returns at least one string");
-            if line.is_empty() {
-                continue;
-            }
-
-            let mut fields = line.split(';');
-
-            let code =
-                u32::from_str_radix(fields.next().expect("code field").trim(), 16).expect("code");
-
-            let mut parse_next_codes = || {
-                let field = fields.next().expect("codes field").trim();
-                if field.is_empty() {
-                    vec![] // a code point can be replaced with nothing
-                } else {
-                    field
-                        .split(' ')
-                        .map(|code| u16::from_str_radix(code, 16).expect("bad code in list") as u32)
-                        .collect::<Vec<u32>>()
-                }
+            return match self.file.next()? {
+                Ok(Entry::Record(record)) => Some(parse_special_case(&record)),
+                Ok(Entry::Missing(_)) => continue,
+                Err(error) => Some(Err(error)),
             };
-
-            let lower = parse_next_codes();
-            let _title = parse_next_codes();
-            let upper = parse_next_codes();
-
-            let mut languages = vec![];
-            let mut contexts = vec![];
-            let conditions = fields.next().expect("condition_list").trim();
-            if !conditions.is_empty() {
-                for cond in conditions.split(' ') {
-                    if cond.chars().nth(0).expect("condition").is_lowercase() {
-                        languages.push(cond);
-                    } else {
-                        contexts.push(cond);
-                    }
-                }
-            }
-
-            return Some(SpecialCase {
-                code,
-                lower,
-                upper,
-                languages,
-                contexts,
-            });
         }
     }
 }
@@ -113,13 +219,6 @@ pub type UnconditionalMapping = BTreeMap<u32, Vec<u32>>; // BTreeMap offers sort
 pub type ContextualMapping<Context> = BTreeMap<u32, (Vec<u32>, Context)>;
 
 /// Casing mappings computed from `SpecialCasing.txt`.
-///
-/// Some case mappings are applied only in contexts where ICU will handle things
-/// for us, or where we can simply inline the relevant handling and not depend
-/// on a fully generalized system.  If this changes, the `#[cfg(test)]`
-/// attributes on some of these fields may have to be removed and the fields
-/// made public.  Assertions in a test function far below hopefully will be able
-/// to detect this.
 pub struct SpecialCasingData {
     /// Unconditional mappings, performed for all languages and contexts, when
     /// lowercasing.
@@ -129,67 +228,95 @@ pub struct SpecialCasingData {
     /// uppercasing.
     pub unconditional_toupper: UnconditionalMapping,
 
+    /// Unconditional mappings, performed for all languages and contexts, when
+    /// titlecasing.
+    pub unconditional_totitle: UnconditionalMapping,
+
     /// Lowercasings that apply in particular contexts but independent of
     /// language.
-    #[cfg(test)]
-    conditional_tolower: ContextualMapping<&'static str>,
+    pub conditional_tolower: ContextualMapping<CasingContext>,
 
     /// Uppercasings that apply in particular contexts but independent of
     /// language.
-    #[cfg(test)]
-    conditional_toupper: ContextualMapping<&'static str>,
+    pub conditional_toupper: ContextualMapping<CasingContext>,
+
+    /// Titlecasings that apply in particular contexts but independent of
+    /// language.
+    pub conditional_totitle: ContextualMapping<CasingContext>,
 
     /// Language-dependent lowercasings, that potentially only apply in a
     /// particular context.
-    #[cfg(test)]
-    lang_conditional_tolower: BTreeMap<&'static str, ContextualMapping<Option<&'static str>>>,
+    pub lang_conditional_tolower: BTreeMap<Language, ContextualMapping<Option<CasingContext>>>,
 
     /// Language-dependent uppercasings, that potentially only apply in a
     /// particular context.
-    #[cfg(test)]
-    lang_conditional_toupper: BTreeMap<&'static str, ContextualMapping<Option<&'static str>>>,
+    pub lang_conditional_toupper: BTreeMap<Language, ContextualMapping<Option<CasingContext>>>,
+
+    /// Language-dependent titlecasings, that potentially only apply in a
+    /// particular context.
+    pub lang_conditional_totitle: BTreeMap<Language, ContextualMapping<Option<CasingContext>>>,
+}
+
+/// Generate special casing mappings from the `SpecialCasing.txt` in `source`.
+///
+/// Only mappings that differ from the simple mappings in `table` are recorded.
+pub fn process_special_casing(
+    source: &UcdSource,
+    table: &CodePointTable,
+) -> Result<SpecialCasingData, UcdError> {
+    parse_special_casing(UcdFile::read(source, SPECIAL_CASING_TXT)?, table)
 }
 
-/// Generate sets containing code points within salient categories.
-pub fn process_special_casing(bmp: &bmp::BMPInfo) -> SpecialCasingData {
+fn parse_special_casing(
+    file: UcdFile,
+    table: &CodePointTable,
+) -> Result<SpecialCasingData, UcdError> {
     // Use BTreeMap for all these maps for naturally sorted keys ordering.
 
     // Unconditional special casing.
     let mut unconditional_tolower = UnconditionalMapping::new();
     let mut unconditional_toupper = UnconditionalMapping::new();
+    let mut unconditional_totitle = UnconditionalMapping::new();
 
     // Conditional special casing: applicable in context yet
     // language-independent.
-    let mut conditional_tolower = ContextualMapping::<&'static str>::new();
-    let mut conditional_toupper = ContextualMapping::<&'static str>::new();
+    let mut conditional_tolower = ContextualMapping::<CasingContext>::new();
+    let mut conditional_toupper = ContextualMapping::<CasingContext>::new();
+    let mut conditional_totitle = ContextualMapping::<CasingContext>::new();
 
     // Conditional special casing: language-dependent, possibly only applicable
     // in context.
-    type LangToMapping = BTreeMap<&'static str, ContextualMapping<Option<&'static str>>>;
+    type LangToMapping = BTreeMap<Language, ContextualMapping<Option<CasingContext>>>;
     let mut lang_conditional_tolower = LangToMapping::new();
     let mut lang_conditional_toupper = LangToMapping::new();
-
-    let case_info = |code: u32| bmp.table[bmp.index[code as usize] as usize].apply(code);
-
-    for SpecialCase {
-        code,
-        upper,
-        lower,
-        languages,
-        contexts,
-    } in SpecialCasing::read()
-    {
-        assert!(code <= MAX_BMP, "non-BMP special not handled yet");
+    let mut lang_conditional_totitle = LangToMapping::new();
+
+    // The simple case mappings of `code`, as (lower, title, upper).
+    let simple_mappings = |code: u32| match table.get(code) {
+        Some(code_point) => (
+            code_point.lowercase(),
+            code_point.titlecase(),
+            code_point.uppercase(),
+        ),
+        None => (code, code, code),
+    };
+
+    for special_case in (SpecialCasing { file }) {
+        let SpecialCase {
+            code,
+            upper,
+            title,
+            lower,
+            languages,
+            contexts,
+        } = special_case?;
         assert!(languages.len() <= 1, "only 0/1 languages handled");
         assert!(contexts.len() <= 1, "only 0/1 casing contexts handled");
 
-        let MappedCodePoint {
-            lower: default_lower,
-            upper: default_upper,
-            ..
-        } = case_info(code);
+        let (default_lower, default_title, default_upper) = simple_mappings(code);
 
         let has_special_lower = lower.len() != 1 || lower[0] != default_lower;
+        let has_special_title = title.len() != 1 || title[0] != default_title;
         let has_special_upper = upper.len() != 1 || upper[0] != default_upper;
 
         // Invariant: If |code| has casing per UnicodeData.txt, then it also has
@@ -197,14 +324,8 @@ pub fn process_special_casing(bmp: &bmp::BMPInfo) -> SpecialCasingData {
         assert!(code == default_lower || lower.len() != 1 || code != lower[0]);
         assert!(code == default_upper || upper.len() != 1 || code != upper[0]);
 
-        let language: Option<&'static str> = match languages.get(0) {
-            Some(language) => Some(*language),
-            None => None,
-        };
-        let context = match contexts.get(0) {
-            Some(context) => Some(*context),
-            None => None,
-        };
+        let language = languages.first().copied();
+        let context = contexts.first().copied();
 
         match (language, context) {
             (None, None) => {
@@ -214,6 +335,9 @@ pub fn process_special_casing(bmp: &bmp::BMPInfo) -> SpecialCasingData {
                 if has_special_upper {
                     unconditional_toupper.insert(code, upper);
                 }
+                if has_special_title {
+                    unconditional_totitle.insert(code, title);
+                }
             }
             (None, Some(context)) => {
                 if has_special_lower {
@@ -222,42 +346,302 @@ pub fn process_special_casing(bmp: &bmp::BMPInfo) -> SpecialCasingData {
                 if has_special_upper {
                     conditional_toupper.insert(code, (upper, context));
                 }
+                if has_special_title {
+                    conditional_totitle.insert(code, (title, context));
+                }
             }
+            // Language-dependent mappings are kept even when they're the
+            // same as the simple mappings, because they take precedence over
+            // unconditional mappings: in Turkish, U+0130 LATIN CAPITAL LETTER
+            // I WITH DOT ABOVE lowercases to its simple mapping i, not to the
+            // unconditional mapping i followed by U+0307 COMBINING DOT ABOVE.
             (Some(language), context) => {
-                if has_special_lower {
-                    lang_conditional_tolower
-                        .entry(language)
-                        .or_insert_with(|| ContextualMapping::new())
-                        .insert(code, (lower, context));
-                }
-                if has_special_upper {
-                    lang_conditional_toupper
-                        .entry(language)
-                        .or_insert_with(|| ContextualMapping::new())
-                        .insert(code, (upper, context));
-                }
+                lang_conditional_tolower
+                    .entry(language)
+                    .or_default()
+                    .insert(code, (lower, context));
+                lang_conditional_toupper
+                    .entry(language)
+                    .or_default()
+                    .insert(code, (upper, context));
+                lang_conditional_totitle
+                    .entry(language)
+                    .or_default()
+                    .insert(code, (title, context));
             }
         };
     }
 
-    SpecialCasingData {
+    Ok(SpecialCasingData {
         unconditional_tolower,
         unconditional_toupper,
-        #[cfg(test)]
+        unconditional_totitle,
         conditional_tolower,
-        #[cfg(test)]
         conditional_toupper,
-        #[cfg(test)]
+        conditional_totitle,
         lang_conditional_tolower,
-        #[cfg(test)]
         lang_conditional_toupper,
+        lang_conditional_totitle,
+    })
+}
+
+/// Which case a `CaseMapper` maps strings to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Case {
+    Lower,
+    Upper,
+}
+
+/// A reference implementation of the full case mapping of strings, as
+/// specified by the Default Case Algorithms in section 3.13 of the Unicode
+/// Standard: special casings, including those that apply only in certain
+/// contexts or languages, and otherwise the simple case mappings.
+pub struct CaseMapper<'a> {
+    special_casing: &'a SpecialCasingData,
+    table: &'a CodePointTable,
+    bmp: &'a BMPInfo,
+    non_bmp: &'a NonBMPInfo,
+    derived_properties: &'a DerivedCorePropertyData,
+    soft_dotted: &'a CodePointSet,
+}
+
+impl<'a> CaseMapper<'a> {
+    /// Create a case mapper from special casings, simple case mappings, and
+    /// the properties used to evaluate casing contexts.
+    ///
+    /// `soft_dotted` is the set of code points with the Soft_Dotted property,
+    /// from `PropList.txt`.  It's only used to evaluate After_Soft_Dotted, in
+    /// Lithuanian.
+    pub fn new(
+        special_casing: &'a SpecialCasingData,
+        table: &'a CodePointTable,
+        bmp: &'a BMPInfo,
+        non_bmp: &'a NonBMPInfo,
+        derived_properties: &'a DerivedCorePropertyData,
+        soft_dotted: &'a CodePointSet,
+    ) -> CaseMapper<'a> {
+        CaseMapper {
+            special_casing,
+            table,
+            bmp,
+            non_bmp,
+            derived_properties,
+            soft_dotted,
+        }
     }
+
+    /// Return the full lowercase mapping of `codes`, in `language` if it's
+    /// specified.
+    ///
+    /// This is `String.prototype.toLowerCase` when `language` is `None`, and
+    /// `String.prototype.toLocaleLowerCase` otherwise.
+    pub fn to_lower(&self, codes: &[u32], language: Option<Language>) -> Vec<u32> {
+        self.map(codes, language, Case::Lower)
+    }
+
+    /// Return the full uppercase mapping of `codes`, in `language` if it's
+    /// specified.
+    ///
+    /// This is `String.prototype.toUpperCase` when `language` is `None`, and
+    /// `String.prototype.toLocaleUpperCase` otherwise.
+    pub fn to_upper(&self, codes: &[u32], language: Option<Language>) -> Vec<u32> {
+        self.map(codes, language, Case::Upper)
+    }
+
+    fn map(&self, codes: &[u32], language: Option<Language>, case: Case) -> Vec<u32> {
+        let SpecialCasingData {
+            unconditional_tolower,
+            unconditional_toupper,
+            conditional_tolower,
+            conditional_toupper,
+            lang_conditional_tolower,
+            lang_conditional_toupper,
+            ..
+        } = self.special_casing;
+        let (unconditional, conditional, lang_conditional) = match case {
+            Case::Lower => (
+                unconditional_tolower,
+                conditional_tolower,
+                lang_conditional_tolower,
+            ),
+            Case::Upper => (
+                unconditional_toupper,
+                conditional_toupper,
+                lang_conditional_toupper,
+            ),
+        };
+
+        let mut mapped = Vec::with_capacity(codes.len());
+        for (i, code) in codes.iter().enumerate() {
+            let language_mapping = language
+                .and_then(|language| lang_conditional.get(&language))
+                .and_then(|mappings| mappings.get(code))
+                .filter(|(_, context)| {
+                    context.is_none_or(|context| self.in_context(codes, i, context))
+                })
+                .map(|(mapping, _)| mapping);
+            let mapping = language_mapping
+                .or_else(|| {
+                    conditional
+                        .get(code)
+                        .filter(|(_, context)| self.in_context(codes, i, *context))
+                        .map(|(mapping, _)| mapping)
+                })
+                .or_else(|| unconditional.get(code));
+            match mapping {
+                Some(mapping) => mapped.extend(mapping),
+                None => mapped.push(self.simple_mapping(*code, case)),
+            }
+        }
+        mapped
+    }
+
+    /// The simple case mapping of `code`.
+    fn simple_mapping(&self, code: u32, case: Case) -> u32 {
+        if code <= MAX_BMP {
+            let mapped = self.bmp.table[self.bmp.index[code as usize] as usize].apply(code);
+            match case {
+                Case::Lower => mapped.lower,
+                Case::Upper => mapped.upper,
+            }
+        } else {
+            let map = match case {
+                Case::Lower => &self.non_bmp.lowercase_map,
+                Case::Upper => &self.non_bmp.uppercase_map,
+            };
+            *map.get(&code).unwrap_or(&code)
+        }
+    }
+
+    /// The canonical combining class of `code`, which is 0 if it's
+    /// unassigned.
+    fn combining_class(&self, code: u32) -> u8 {
+        self.table
+            .get(code)
+            .map_or(0, |code_point| code_point.combining_class())
+    }
+
+    /// Whether the code point at `codes[i]` is in `context`, as specified by
+    /// table 3-17, "Context Specification for Casing".
+    fn in_context(&self, codes: &[u32], i: usize, context: CasingContext) -> bool {
+        let is = |property, code: &u32| self.derived_properties.contains(property, *code);
+        let (before, after) = (&codes[..i], &codes[i + 1..]);
+
+        // Whether the first code point in `codes` that satisfies `found` or
+        // `stop` satisfies `found`.
+        fn scan<'c>(
+            mut codes: impl Iterator<Item = &'c u32>,
+            found: impl Fn(&u32) -> bool,
+            stop: impl Fn(&u32) -> bool,
+        ) -> bool {
+            codes
+                .find(|code| found(code) || stop(code))
+                .is_some_and(found)
+        }
+        let blocks_above = |code: &u32| matches!(self.combining_class(*code), 0 | 230);
+
+        match context {
+            CasingContext::FinalSigma => {
+                let cased = |code: &u32| is(DerivedProperty::Cased, code);
+                let not_ignorable = |code: &u32| !is(DerivedProperty::CaseIgnorable, code);
+                scan(before.iter().rev(), cased, not_ignorable)
+                    && !scan(after.iter(), cased, not_ignorable)
+            }
+            CasingContext::AfterSoftDotted => scan(
+                before.iter().rev(),
+                |code| self.soft_dotted.contains(code),
+                blocks_above,
+            ),
+            CasingContext::MoreAbove => scan(
+                after.iter(),
+                |code| self.combining_class(*code) == 230,
+                |code| self.combining_class(*code) == 0,
+            ),
+            CasingContext::BeforeDot => scan(
+                after.iter(),
+                |code| *code == COMBINING_DOT_ABOVE,
+                blocks_above,
+            ),
+            CasingContext::NotBeforeDot => !self.in_context(codes, i, CasingContext::BeforeDot),
+            CasingContext::AfterI => scan(
+                before.iter().rev(),
+                |code| *code == LATIN_CAPITAL_LETTER_I,
+                blocks_above,
+            ),
+        }
+    }
+}
+
+/// The data a `CaseMapper` over the files embedded in this crate uses.
+struct EmbeddedCaseData {
+    special_casing: SpecialCasingData,
+    table: CodePointTable,
+    bmp: BMPInfo,
+    non_bmp: NonBMPInfo,
+    derived_properties: DerivedCorePropertyData,
+    soft_dotted: CodePointSet,
+}
+
+impl EmbeddedCaseData {
+    fn new() -> Result<EmbeddedCaseData, UcdError> {
+        let source = UcdSource::Embedded;
+        let table = code_point_table::generate_code_point_table(&source)?;
+        let derived_properties = derived_core_properties::process_derived_core_properties(&source)?;
+        Ok(EmbeddedCaseData {
+            special_casing: process_special_casing(&source, &table)?,
+            bmp: bmp::generate_bmp_info(&table, &derived_properties),
+            non_bmp: non_bmp::generate_non_bmp_info(&source, &table)?,
+            soft_dotted: prop_list::process_soft_dotted(&source)?,
+            table,
+            derived_properties,
+        })
+    }
+}
+
+/// A case mapper over the files embedded in this crate, whose data is built
+/// the first time it's needed.
+fn embedded_case_mapper() -> CaseMapper<'static> {
+    static DATA: OnceLock<EmbeddedCaseData> = OnceLock::new();
+    let data = DATA.get_or_init(|| EmbeddedCaseData::new().expect("embedded files are valid"));
+    CaseMapper::new(
+        &data.special_casing,
+        &data.table,
+        &data.bmp,
+        &data.non_bmp,
+        &data.derived_properties,
+        &data.soft_dotted,
+    )
+}
+
+/// Return the full lowercase mapping of `codes`, in `language` if it's
+/// specified, per the files embedded in this crate.  See
+/// [`CaseMapper::to_lower`].
+///
+/// # Example
+///
+/// ```
+/// # use unicode_info::special_casing::{to_lower, Language};
+/// // U+03A3 GREEK CAPITAL LETTER SIGMA is final at the end of a word.
+/// assert_eq!(to_lower(&[0x039F, 0x03A3], None), [0x03BF, 0x03C2]);
+/// assert_eq!(to_lower(&[0x0049], Some(Language::Turkish)), [0x0131]);
+/// ```
+pub fn to_lower(codes: &[u32], language: Option<Language>) -> Vec<u32> {
+    embedded_case_mapper().to_lower(codes, language)
+}
+
+/// Return the full uppercase mapping of `codes`, in `language` if it's
+/// specified, per the files embedded in this crate.  See
+/// [`CaseMapper::to_upper`].
+pub fn to_upper(codes: &[u32], language: Option<Language>) -> Vec<u32> {
+    embedded_case_mapper().to_upper(codes, language)
 }
 
 #[test]
 fn check_special_casing() {
-    let cpt = code_point_table::generate_code_point_table();
-    let dcp = derived_core_properties::process_derived_core_properties();
+    let source = UcdSource::Embedded;
+    let cpt = code_point_table::generate_code_point_table(&source).expect("UnicodeData.txt");
+    let dcp = derived_core_properties::process_derived_core_properties(&source)
+        .expect("DerivedCoreProperties.txt");
     let bmp = bmp::generate_bmp_info(&cpt, &dcp);
 
     let case_info = |code: u32| bmp.table[bmp.index[code as usize] as usize].apply(code);
@@ -265,11 +649,14 @@ fn check_special_casing() {
     let SpecialCasingData {
         unconditional_tolower,
         unconditional_toupper,
+        unconditional_totitle,
         conditional_tolower,
         conditional_toupper,
+        conditional_totitle,
         lang_conditional_tolower,
         lang_conditional_toupper,
-    } = process_special_casing(&bmp);
+        lang_conditional_totitle,
+    } = process_special_casing(&source, &cpt).expect("SpecialCasing.txt");
 
     let lower_case = |code| case_info(code).lower;
     let upper_case = |code| case_info(code).upper;
@@ -293,6 +680,8 @@ fn check_special_casing() {
     assert!(is_empty(unconditional_toupper.keys().filter(accept_ascii)));
     assert!(is_empty(conditional_tolower.keys().filter(accept_ascii)));
     assert!(is_empty(conditional_toupper.keys().filter(accept_ascii)));
+    assert!(is_empty(unconditional_totitle.keys().filter(accept_ascii)));
+    assert!(is_empty(conditional_totitle.keys().filter(accept_ascii)));
 
     // Ensure no Latin-1 code points have special lower case mappings.
     assert!(is_empty(unconditional_tolower.keys().filter(accept_latin1)));
@@ -314,9 +703,10 @@ fn check_special_casing() {
         .iter()
         .eq(unconditional_tolower.keys()));
 
-    // Ensure no code points have language-independent conditional upper case
-    // mappings.
+    // Ensure no code points have language-independent conditional upper or
+    // title case mappings.
     assert!(is_empty(conditional_toupper.iter()));
+    assert!(is_empty(conditional_totitle.iter()));
 
     // Ensure U+03A3 GREEK CAPITAL LETTER SIGMA is the only code point with
     // language-independent conditional lower case mapping.
@@ -336,18 +726,17 @@ fn check_special_casing() {
 
     // Ensure Azeri, Lithuanian, and Turkish are the only languages with
     // conditional case mappings.
-    assert_eq!(
-        vec![&"az", &"lt", &"tr"],
-        lang_conditional_tolower
-            .keys()
-            .collect::<Vec<&&'static str>>()
-    );
-    assert_eq!(
-        vec![&"az", &"lt", &"tr"],
-        lang_conditional_toupper
-            .keys()
-            .collect::<Vec<&&'static str>>()
-    );
+    for (i, language) in Language::all().enumerate() {
+        assert_eq!(language as usize, i, "LANGUAGES is in declaration order");
+        assert_eq!(Language::from_code(language.code()), Some(language));
+    }
+    for lang_conditional in &[
+        &lang_conditional_tolower,
+        &lang_conditional_toupper,
+        &lang_conditional_totitle,
+    ] {
+        assert!(lang_conditional.keys().copied().eq(Language::all()));
+    }
 
     // Verify that the maximum case-mapping length is three characters.
     // (Do we depend/rely on this in specific places?  It would be trivial to
@@ -356,6 +745,7 @@ fn check_special_casing() {
         unconditional_tolower
             .values()
             .chain(unconditional_toupper.values())
+            .chain(unconditional_totitle.values())
             .chain(
                 conditional_tolower
                     .values()
@@ -375,12 +765,19 @@ fn check_special_casing() {
 
     // Ensure all case mapping contexts are known (see Unicode 9.0,
     // §3.13 Default Case Algorithms).
-    assert!(HashSet::<&'static str>::from_iter([
-        "After_I",
-        "After_Soft_Dotted",
-        "Final_Sigma",
-        "More_Above",
-        "Not_Before_Dot",
+    for (i, context) in CasingContext::all().enumerate() {
+        assert_eq!(
+            context as usize, i,
+            "CASING_CONTEXTS is in declaration order"
+        );
+        assert_eq!(CasingContext::from_name(context.name()), Some(context));
+    }
+    assert!(HashSet::<CasingContext>::from_iter([
+        CasingContext::AfterI,
+        CasingContext::AfterSoftDotted,
+        CasingContext::FinalSigma,
+        CasingContext::MoreAbove,
+        CasingContext::NotBeforeDot,
     ])
     .is_superset(
         &(conditional_tolower.values().map(|(_, context)| *context))
@@ -389,21 +786,15 @@ fn check_special_casing() {
                 lang_conditional_tolower
                     .values()
                     .flat_map(|dict| dict.values())
-                    .filter_map(|(_, context)| match *context {
-                        Some(context) => Some(context),
-                        None => None,
-                    }),
+                    .filter_map(|(_, context)| *context),
             )
             .chain(
                 lang_conditional_toupper
                     .values()
                     .flat_map(|dict| dict.values())
-                    .filter_map(|(_, context)| match *context {
-                        Some(context) => Some(context),
-                        None => None,
-                    }),
+                    .filter_map(|(_, context)| *context),
             )
-            .collect::<HashSet<&'static str>>()
+            .collect::<HashSet<CasingContext>>()
     ));
 
     // Special casing for U+00DF LATIN SMALL LETTER SHARP S.
@@ -415,6 +806,20 @@ fn check_special_casing() {
         unconditional_toupper[&LATIN_SMALL_LETTER_SHARP_S],
         [LATIN_CAPITAL_LETTER_S, LATIN_CAPITAL_LETTER_S]
     );
+    assert_eq!(
+        unconditional_totitle[&LATIN_SMALL_LETTER_SHARP_S],
+        [LATIN_CAPITAL_LETTER_S, 's' as u32]
+    );
+
+    // U+01C4 LATIN CAPITAL LETTER DZ WITH CARON has a distinct simple
+    // titlecase mapping, so it has no special titlecase mapping.
+    assert!(!unconditional_totitle.contains_key(&0x01C4));
+    // U+1F80 GREEK SMALL LETTER ALPHA WITH PSILI AND YPOGEGRAMMENI has a
+    // special uppercase mapping, but its titlecase mapping is its simple one.
+    assert_eq!(unconditional_toupper[&0x1F80], [0x1F08, 0x0399]);
+    assert!(!unconditional_totitle.contains_key(&0x1F80));
+    // U+FB00 LATIN SMALL LIGATURE FF
+    assert_eq!(unconditional_totitle[&0xFB00], [0x0046, 0x0066]);
 
     // Special casing for U+0130 LATIN CAPITAL LETTER I WITH DOT ABOVE.
     assert_eq!(
@@ -429,6 +834,159 @@ fn check_special_casing() {
     );
     assert_eq!(
         conditional_tolower[&GREEK_CAPITAL_LETTER_SIGMA],
-        (vec![GREEK_SMALL_LETTER_FINAL_SIGMA], "Final_Sigma")
+        (
+            vec![GREEK_SMALL_LETTER_FINAL_SIGMA],
+            CasingContext::FinalSigma
+        )
+    );
+
+    // Language-specific casing for I in Lithuanian and Turkish.
+    assert_eq!(
+        lang_conditional_tolower[&Language::Lithuanian][&('I' as u32)],
+        (
+            vec![LATIN_SMALL_LETTER_I, COMBINING_DOT_ABOVE],
+            Some(CasingContext::MoreAbove)
+        )
+    );
+    assert_eq!(
+        lang_conditional_tolower[&Language::Turkish][&('I' as u32)],
+        (vec![0x0131], Some(CasingContext::NotBeforeDot))
+    );
+    assert_eq!(
+        lang_conditional_toupper[&Language::Turkish][&LATIN_SMALL_LETTER_I],
+        (vec![LATIN_CAPITAL_LETTER_I_WITH_DOT_ABOVE], None)
+    );
+}
+
+#[test]
+fn check_non_bmp_special_casing() {
+    let cpt =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("UnicodeData.txt");
+
+    let file = UcdFile::new(
+        SPECIAL_CASING_TXT,
+        "# SpecialCasing-16.0.0.txt\n\
+         10428; 10428; 10400 0301; 10400 0301; # DESERET SMALL LETTER LONG I\n\
+         1E922; 1E922; 1E900; 1E900; # ADLAM SMALL LETTER ALIF\n",
+    );
+    let data = parse_special_casing(file, &cpt).expect("SpecialCasing.txt");
+    assert_eq!(data.unconditional_toupper[&0x10428], [0x10400, 0x0301]);
+    assert_eq!(data.unconditional_totitle[&0x10428], [0x10400, 0x0301]);
+    assert!(data.unconditional_tolower.is_empty());
+    // U+1E922's mappings are the same as its simple mappings.
+    assert!(!data.unconditional_toupper.contains_key(&0x1E922));
+    assert!(!data.unconditional_totitle.contains_key(&0x1E922));
+
+    let file = UcdFile::new(
+        SPECIAL_CASING_TXT,
+        "10428; 10428; 110000; 10400; # DESERET SMALL LETTER LONG I\n",
+    );
+    let error = parse_special_casing(file, &cpt)
+        .err()
+        .expect("code point out of range");
+    assert_eq!(
+        error.to_string(),
+        "SpecialCasing.txt:1: expected a hexadecimal code in a mapping, found \"110000\""
+    );
+}
+
+#[test]
+fn check_special_casing_conditions() {
+    let cpt =
+        code_point_table::generate_code_point_table(&UcdSource::Embedded).expect("UnicodeData.txt");
+
+    for (line, error) in &[
+        (
+            "0049; 0131; 0049; 0049; de; # LATIN CAPITAL LETTER I\n",
+            "SpecialCasing.txt:1: expected a language code, found \"de\"",
+        ),
+        (
+            "03A3; 03C2; 03A3; 03A3; Initial_Sigma; # GREEK CAPITAL LETTER SIGMA\n",
+            "SpecialCasing.txt:1: expected a casing context, found \"Initial_Sigma\"",
+        ),
+    ] {
+        let actual = parse_special_casing(UcdFile::new(SPECIAL_CASING_TXT, line), &cpt)
+            .err()
+            .expect("bad condition");
+        assert_eq!(actual.to_string(), *error);
+    }
+}
+
+#[test]
+fn check_case_mapper() {
+    let source = UcdSource::Embedded;
+    let cpt = code_point_table::generate_code_point_table(&source).expect("UnicodeData.txt");
+    let dcp = derived_core_properties::process_derived_core_properties(&source)
+        .expect("DerivedCoreProperties.txt");
+    let bmp = bmp::generate_bmp_info(&cpt, &dcp);
+    let non_bmp = non_bmp::generate_non_bmp_info(&source, &cpt).expect("non-BMP info");
+    let special_casing = process_special_casing(&source, &cpt).expect("SpecialCasing.txt");
+    let soft_dotted = prop_list::process_soft_dotted(&source).expect("Soft_Dotted");
+    let mapper = CaseMapper::new(&special_casing, &cpt, &bmp, &non_bmp, &dcp, &soft_dotted);
+
+    let codes = |s: &str| s.chars().map(|c| c as u32).collect::<Vec<_>>();
+    let lower = |s: &str, language| to_lower(&codes(s), language);
+    let upper = |s: &str, language| to_upper(&codes(s), language);
+    assert_eq!(
+        mapper.to_lower(&codes("\u{3A3}A\u{3A3}"), None),
+        lower("\u{3A3}A\u{3A3}", None)
+    );
+
+    // Simple and unconditional mappings.
+    assert_eq!(lower("ABC", None), codes("abc"));
+    assert_eq!(upper("stra\u{DF}e", None), codes("STRASSE"));
+    assert_eq!(lower("\u{130}", None), codes("i\u{307}"));
+    assert_eq!(lower("\u{10400}", None), codes("\u{10428}"));
+    assert_eq!(upper("\u{1E922}", None), codes("\u{1E900}"));
+
+    // Final_Sigma, skipping case-ignorable code points in both directions.
+    assert_eq!(lower("\u{391}\u{3A3}", None), codes("\u{3B1}\u{3C2}"));
+    assert_eq!(lower("\u{391}\u{3A3} ", None), codes("\u{3B1}\u{3C2} "));
+    assert_eq!(lower("\u{391}'\u{3A3}'", None), codes("\u{3B1}'\u{3C2}'"));
+    assert_eq!(
+        lower("\u{391}\u{3A3}\u{391}", None),
+        codes("\u{3B1}\u{3C3}\u{3B1}")
+    );
+    assert_eq!(
+        lower("\u{391}\u{3A3}'\u{391}", None),
+        codes("\u{3B1}\u{3C3}'\u{3B1}")
+    );
+    assert_eq!(lower("\u{3A3}", None), codes("\u{3C3}"));
+    assert_eq!(lower(" \u{3A3}", None), codes(" \u{3C3}"));
+
+    // Turkish and Azeri: After_I and Not_Before_Dot.
+    for language in &[Language::Turkish, Language::Azeri] {
+        let language = Some(*language);
+        assert_eq!(lower("I", language), codes("\u{131}"));
+        assert_eq!(lower("I\u{307}", language), codes("i"));
+        assert_eq!(lower("I\u{316}\u{307}", language), codes("i\u{316}"));
+        assert_eq!(
+            lower("I\u{301}\u{307}", language),
+            codes("\u{131}\u{301}\u{307}")
+        );
+        assert_eq!(lower("\u{130}", language), codes("i"));
+        assert_eq!(upper("i", language), codes("\u{130}"));
+        assert_eq!(upper("\u{131}", language), codes("I"));
+    }
+    assert_eq!(lower("I\u{307}", None), codes("i\u{307}"));
+    assert_eq!(upper("i", None), codes("I"));
+
+    // Lithuanian: More_Above and After_Soft_Dotted.
+    let lithuanian = Some(Language::Lithuanian);
+    assert_eq!(lower("I", lithuanian), codes("i"));
+    assert_eq!(lower("I\u{301}", lithuanian), codes("i\u{307}\u{301}"));
+    assert_eq!(
+        lower("J\u{316}\u{301}", lithuanian),
+        codes("j\u{307}\u{316}\u{301}")
+    );
+    assert_eq!(lower("\u{CC}", lithuanian), codes("i\u{307}\u{300}"));
+    assert_eq!(upper("i\u{307}", lithuanian), codes("I"));
+    assert_eq!(upper("i\u{316}\u{307}", lithuanian), codes("I\u{316}"));
+    assert_eq!(
+        upper("i\u{301}\u{307}", lithuanian),
+        codes("I\u{301}\u{307}")
     );
+    assert_eq!(upper("a\u{307}", lithuanian), codes("A\u{307}"));
+    // U+0456 CYRILLIC SMALL LETTER BYELORUSSIAN-UKRAINIAN I is Soft_Dotted too.
+    assert_eq!(upper("\u{456}\u{307}", lithuanian), codes("\u{406}"));
 }
@@ -18,11 +18,11 @@ pub struct TableSplit {
 
 /// Compute the type of the smallest integer type that can represent every value
 /// in `data`.
-fn get_element_type(data: &Vec<u32>) -> NumericType {
-    assert!(data.len() > 0);
+fn get_element_type(data: &[u32]) -> NumericType {
+    assert!(!data.is_empty());
 
     let max_data = data.iter().fold(0, |max, v| std::cmp::max(max, *v)) as usize;
-    assert!(max_data <= usize::wrapping_shl(1usize, 32) - 1);
+    assert!(max_data < usize::wrapping_shl(1usize, 32));
 
     if max_data <= u8::MAX as usize {
         NumericType::U8
@@ -48,8 +48,8 @@ fn get_size(t: NumericType) -> usize {
 
 /// Compute the size of the smallest integer type in bytes that can represent
 /// every value in `data`.
-fn get_element_size(data: &Vec<u32>) -> usize {
-    get_size(get_element_type(&data))
+fn get_element_size(data: &[u32]) -> usize {
+    get_size(get_element_type(data))
 }
 
 #[test]
@@ -74,7 +74,7 @@ fn test_get_element_type() {
 }
 
 /// Print diagnostic information about the optimal table splitting.
-fn dump_best_split(s: &TableSplit, original_table: &Vec<u32>, bytes: usize) {
+fn dump_best_split(s: &TableSplit, original_table: &[u32], bytes: usize) {
     eprintln!(
         "Best: {index1_len}+{index2_len} bins at shift {shift}; {bytes} bytes",
         index1_len = s.index1.len(),
@@ -84,27 +84,27 @@ fn dump_best_split(s: &TableSplit, original_table: &Vec<u32>, bytes: usize) {
     );
     eprintln!(
         "Size of original table: {original_size} bytes",
-        original_size = get_element_size(&original_table) * original_table.len(),
+        original_size = get_element_size(original_table) * original_table.len(),
     );
 }
 
 /// Compute the maximum possible `shift` such that `(t.len() - 1) >> shift` is
 /// still nonzero.
-fn compute_maximum_shift(t: &Vec<u32>) -> u32 {
+fn compute_maximum_shift(t: &[u32]) -> u32 {
     t.len().next_power_of_two().trailing_zeros() - 1
 }
 
 #[test]
 fn test_maximum_shift() {
-    assert_eq!(compute_maximum_shift(&vec![0; 2]), 0);
-    assert_eq!(compute_maximum_shift(&vec![0; 3]), 1);
-    assert_eq!(compute_maximum_shift(&vec![0; 4]), 1);
-    assert_eq!(compute_maximum_shift(&vec![0; 5]), 2);
-    assert_eq!(compute_maximum_shift(&vec![0; 6]), 2);
-    assert_eq!(compute_maximum_shift(&vec![0; 7]), 2);
-    assert_eq!(compute_maximum_shift(&vec![0; 8]), 2);
-    assert_eq!(compute_maximum_shift(&vec![0; 9]), 3);
-    assert_eq!(compute_maximum_shift(&vec![0; 10]), 3);
+    assert_eq!(compute_maximum_shift(&[0; 2]), 0);
+    assert_eq!(compute_maximum_shift(&[0; 3]), 1);
+    assert_eq!(compute_maximum_shift(&[0; 4]), 1);
+    assert_eq!(compute_maximum_shift(&[0; 5]), 2);
+    assert_eq!(compute_maximum_shift(&[0; 6]), 2);
+    assert_eq!(compute_maximum_shift(&[0; 7]), 2);
+    assert_eq!(compute_maximum_shift(&[0; 8]), 2);
+    assert_eq!(compute_maximum_shift(&[0; 9]), 3);
+    assert_eq!(compute_maximum_shift(&[0; 10]), 3);
 }
 
 /// Given a (large) table `t` of values, return the best possible splitting of
@@ -119,7 +119,7 @@ fn test_maximum_shift() {
 ///
 /// 1. A `table` array of every unique value in `t`.
 /// 1. An `index` array whose indexes are code points, whose elements are appropriate
-/// indexes into the first array.
+///    indexes into the first array.
 ///
 /// This saves some memory, _if_ `t` elements occupy more memory than the
 /// elements of `index`.  But `index` is still just as long as `t`, so it
@@ -129,10 +129,10 @@ fn test_maximum_shift() {
 ///
 /// 1. A `table` array of every unique value in `t`.
 /// 1. An `index1` array, indexed by the upper `(32 - N)` bits of a code point,
-/// that stores partial index values.
+///    that stores partial index values.
 /// 1. An `index2` array, indexed by an `index1` element shifted `N` bits upward
-/// plus the remaining lower `N` bits of a code point, whose elements are
-/// indexes into `table`.
+///    plus the remaining lower `N` bits of a code point, whose elements are
+///    indexes into `table`.
 ///
 /// Or to say it in pseudocode,
 ///
@@ -152,7 +152,7 @@ fn test_maximum_shift() {
 /// (When `N = 0`, you can think of `index1` as an array of elements whose
 /// values are their indexes and `index2` as identical to `index` in the first
 /// optimization scheme.)
-pub fn split_table(t: &Vec<u32>) -> TableSplit {
+pub fn split_table(t: &[u32]) -> TableSplit {
     assert!(
         t.len() <= u32::MAX as usize,
         "assumed below that t2's length won't exceed u32"
@@ -174,13 +174,19 @@ pub fn split_table(t: &Vec<u32>) -> TableSplit {
 
     // The maximum possible downshift of a valid index of `t` that will produce
     // _some_ nonzero value.
-    let max_shift = compute_maximum_shift(&t);
+    let max_shift = compute_maximum_shift(t);
 
     // For every possible shift that leaves some index into `t` nonzero...
     for candidate_shift in 0..=max_shift {
         //  Let `t` be split into chunks of the corresponding size.
         let size = 1 << candidate_shift;
 
+        // Chunks must evenly divide `t`.  (This isn't an issue for the BMP,
+        // but the full code point range is 17 * 2**16 elements long.)
+        if !t.len().is_multiple_of(size) {
+            break;
+        }
+
         // Let `index1` and `index2` be empty arrays.
         let mut index1 = vec![];
         let mut index2 = vec![];
@@ -232,7 +238,7 @@ pub fn split_table(t: &Vec<u32>) -> TableSplit {
         }
     }
 
-    dump_best_split(&best, &t, best_bytes);
+    dump_best_split(&best, t, best_bytes);
 
     #[cfg(test)]
     {
@@ -6,6 +6,66 @@ use quote::quote;
 /// A set of code point values.
 pub type CodePointSet = std::collections::BTreeSet<u32>; // BTreeSet for sorting
 
+/// A set of strings, each a sequence of code points, such as the value of a
+/// property of strings like RGI_Emoji.  Strings are kept in sorted order, and
+/// the strings consisting of a single code point are also tracked as a
+/// `CodePointSet`, as regular expression engines typically match them
+/// separately from longer strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringSet {
+    strings: std::collections::BTreeSet<Vec<u32>>,
+    code_points: CodePointSet,
+}
+
+impl StringSet {
+    pub fn new() -> StringSet {
+        StringSet::default()
+    }
+
+    /// Add `string` to the set, returning whether it wasn't already present.
+    pub fn insert(&mut self, string: Vec<u32>) -> bool {
+        if let [code] = string[..] {
+            self.code_points.insert(code);
+        }
+        self.strings.insert(string)
+    }
+
+    /// Add every string in `other` to the set.
+    pub fn extend(&mut self, other: &StringSet) {
+        self.strings.extend(other.strings.iter().cloned());
+        self.code_points.extend(other.code_points.iter().copied());
+    }
+
+    pub fn contains(&self, string: &[u32]) -> bool {
+        self.strings.contains(string)
+    }
+
+    /// The number of strings in the set, including single code points.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Return an iterator over every string in the set, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &[u32]> {
+        self.strings.iter().map(Vec::as_slice)
+    }
+
+    /// The strings in the set consisting of a single code point.
+    pub fn code_points(&self) -> &CodePointSet {
+        &self.code_points
+    }
+
+    /// Return an iterator over the strings in the set that aren't a single
+    /// code point, in sorted order.
+    pub fn multi_code_point_strings(&self) -> impl Iterator<Item = &[u32]> {
+        self.iter().filter(|string| string.len() != 1)
+    }
+}
+
 /// A mapping from code points to their case-mapped form (uppercase or lowercase
 /// as stated in context).
 pub type CaseMap = std::collections::BTreeMap<u32, u32>; // BTreeMap for sorting
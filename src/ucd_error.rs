@@ -0,0 +1,107 @@
+//! The error type for failures to read or parse UCD files.
+
+use std::fmt;
+use std::io;
+
+/// An error encountered reading or parsing a UCD file.
+#[derive(Debug)]
+pub enum UcdError {
+    /// The file couldn't be read.
+    Io {
+        /// The name of the file, e.g. "UnicodeData.txt".
+        file: String,
+
+        /// The underlying error.
+        error: io::Error,
+    },
+
+    /// The file contained something other than what was expected.
+    Parse {
+        /// The name of the file, e.g. "UnicodeData.txt".
+        file: String,
+
+        /// The 1-based number of the offending line.
+        line: usize,
+
+        /// The offending text: a whole line, or the field within it that
+        /// couldn't be parsed.
+        text: String,
+
+        /// A description of what was expected instead, e.g. "a hexadecimal
+        /// code point".
+        expected: String,
+    },
+}
+
+impl UcdError {
+    /// Construct an error for a failure to read `file`.
+    pub fn io(file: &str, error: io::Error) -> UcdError {
+        UcdError::Io {
+            file: file.to_string(),
+            error,
+        }
+    }
+
+    /// Construct an error for unexpected `text` on line `line` of `file`.
+    pub fn parse(file: &str, line: usize, text: &str, expected: impl Into<String>) -> UcdError {
+        UcdError::Parse {
+            file: file.to_string(),
+            line,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Whether this error indicates the file doesn't exist.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            UcdError::Io { error, .. } => error.kind() == io::ErrorKind::NotFound,
+            UcdError::Parse { .. } => false,
+        }
+    }
+
+    /// The name of the file the error pertains to.
+    pub fn file(&self) -> &str {
+        match self {
+            UcdError::Io { file, .. } => file,
+            UcdError::Parse { file, .. } => file,
+        }
+    }
+}
+
+impl fmt::Display for UcdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UcdError::Io { file, error } => write!(f, "{}: {}", file, error),
+            UcdError::Parse {
+                file,
+                line,
+                text,
+                expected,
+            } => write!(
+                f,
+                "{}:{}: expected {}, found {:?}",
+                file, line, expected, text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UcdError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UcdError::Io { error, .. } => Some(error),
+            UcdError::Parse { .. } => None,
+        }
+    }
+}
+
+#[test]
+fn check_error_display() {
+    let error = UcdError::parse("CaseFolding.txt", 12, "004G", "a hexadecimal code point");
+    assert_eq!(error.file(), "CaseFolding.txt");
+    assert_eq!(
+        error.to_string(),
+        "CaseFolding.txt:12: expected a hexadecimal code point, found \"004G\""
+    );
+}
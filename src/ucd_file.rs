@@ -0,0 +1,371 @@
+//! A tokenizer for the line-oriented format shared by most UCD files, e.g.
+//!
+//! ```text
+//! # A comment.
+//! # @missing: 0000..10FFFF; Unknown
+//! 0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z
+//! 00AA          ; Latin # Lo       FEMININE ORDINAL INDICATOR
+//! ```
+//!
+//! Each data line consists of semicolon-separated fields, optionally followed
+//! by a `#` comment.  In most files the first field is a code point or a range
+//! of code points.  `# @missing` comment lines specify the default property
+//! value for code points not otherwise listed.
+//!
+//! See <https://www.unicode.org/reports/tr44/#Format_Conventions> for details.
+//!
+//! # Example
+//!
+//! ```
+//! # use unicode_info::ucd_file::{Entry, UcdFile};
+//! let mut file = UcdFile::new("Example.txt", "0041..005A ; Latin # capitals\n");
+//! match file.next().expect("a line").expect("well-formed") {
+//!     Entry::Record(record) => {
+//!         assert_eq!(record.range.iter().count(), 26);
+//!         assert_eq!(record.fields, ["Latin"]);
+//!         assert_eq!(record.comment, Some("capitals"));
+//!     }
+//!     Entry::Missing(_) => unreachable!(),
+//! }
+//! ```
+
+use crate::ucd_error::UcdError;
+use crate::ucd_source::UcdSource;
+
+/// An inclusive range of code points, e.g. `0041..005A`, or a single code point
+/// (a range whose first and last code points are the same).
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CodePointRange {
+    pub first: u32,
+    pub last: u32,
+}
+
+impl CodePointRange {
+    /// Parse a single hexadecimal code point or a `first..last` range of them.
+    pub fn parse(s: &str) -> Option<CodePointRange> {
+        let code = |s: &str| u32::from_str_radix(s.trim(), 16).ok();
+        let (first, last) = match s.find("..") {
+            Some(i) => (code(&s[..i])?, code(&s[i + 2..])?),
+            None => {
+                let code = code(s)?;
+                (code, code)
+            }
+        };
+        if first > last {
+            return None;
+        }
+        Some(CodePointRange { first, last })
+    }
+
+    /// Whether `code` is within this range.
+    pub fn contains(&self, code: u32) -> bool {
+        self.first <= code && code <= self.last
+    }
+
+    /// Return an iterator over every code point in this range.
+    pub fn iter(&self) -> std::ops::RangeInclusive<u32> {
+        self.first..=self.last
+    }
+}
+
+impl IntoIterator for CodePointRange {
+    type Item = u32;
+    type IntoIter = std::ops::RangeInclusive<u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A data line in a UCD file, split into fields but otherwise uninterpreted.
+///
+/// This is the form to use for files whose first field isn't a code point
+/// range, e.g. `emoji-sequences.txt` or `NormalizationTest.txt`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataLine {
+    /// The 1-based number of the line.
+    pub line: usize,
+
+    /// The trimmed, semicolon-separated fields of the line.  (A trailing empty
+    /// field, produced by a `;` just before the comment, is omitted.)
+    pub fields: Vec<&'static str>,
+
+    /// The trimmed text following `#` on the line, if any.
+    pub comment: Option<&'static str>,
+
+    /// Whether this line is a `# @missing:` directive, rather than data.
+    pub missing: bool,
+}
+
+/// An iterator over the data lines (including `@missing` directives) in the
+/// contents of a UCD file, skipping blank lines and comments.
+pub struct DataLines {
+    lines: std::iter::Enumerate<std::str::Lines<'static>>,
+}
+
+impl DataLines {
+    pub fn new(contents: &'static str) -> DataLines {
+        DataLines {
+            lines: contents.lines().enumerate(),
+        }
+    }
+}
+
+impl Iterator for DataLines {
+    type Item = DataLine;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, line) = self.lines.next()?;
+
+            let (data, comment) = match line.find('#') {
+                Some(i) => (&line[..i], Some(line[i + 1..].trim())),
+                None => (line, None),
+            };
+
+            let (data, comment, missing) = match comment {
+                Some(comment) if data.trim().is_empty() => {
+                    match comment.strip_prefix("@missing:") {
+                        Some(missing) => (missing, None, true),
+                        None => continue,
+                    }
+                }
+                _ => (data, comment, false),
+            };
+
+            let data = data.trim();
+            if data.is_empty() {
+                continue;
+            }
+
+            let mut fields = data.split(';').map(str::trim).collect::<Vec<_>>();
+            if fields.len() > 1 && fields.last() == Some(&"") {
+                fields.pop();
+            }
+
+            return Some(DataLine {
+                line: index + 1,
+                fields,
+                comment,
+                missing,
+            });
+        }
+    }
+}
+
+/// A data line whose first field is a code point or code point range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The name of the file containing this record.
+    pub file: &'static str,
+
+    /// The 1-based number of the line containing this record.
+    pub line: usize,
+
+    /// The code points the record applies to.
+    pub range: CodePointRange,
+
+    /// The trimmed fields following the code point field.
+    pub fields: Vec<&'static str>,
+
+    /// The trimmed text following `#` on the line, if any.
+    pub comment: Option<&'static str>,
+}
+
+impl Record {
+    /// Construct an error for unexpected `text` in this record.
+    pub fn error(&self, text: &str, expected: impl Into<String>) -> UcdError {
+        UcdError::parse(self.file, self.line, text, expected)
+    }
+
+    /// Return the field at `index` (not counting the code point field), or an
+    /// error describing the field as `expected` if there's no such field.
+    pub fn field(&self, index: usize, expected: &str) -> Result<&'static str, UcdError> {
+        self.fields
+            .get(index)
+            .copied()
+            .ok_or_else(|| self.error(&self.fields.join("; "), expected))
+    }
+}
+
+/// An entry in a UCD file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// A data line.
+    Record(Record),
+
+    /// A `# @missing:` directive, specifying the value for code points in its
+    /// range that aren't listed in any `Record`.
+    Missing(Record),
+}
+
+/// An iterator over the entries in a UCD file whose data lines begin with a
+/// code point or code point range.
+pub struct UcdFile {
+    file: &'static str,
+    lines: DataLines,
+}
+
+impl UcdFile {
+    /// Tokenize `contents`, the contents of the file named `file`.
+    pub fn new(file: &'static str, contents: &'static str) -> UcdFile {
+        UcdFile {
+            file,
+            lines: DataLines::new(contents),
+        }
+    }
+
+    /// Tokenize the file named `file` in `source`.
+    pub fn read(source: &UcdSource, file: &'static str) -> Result<UcdFile, UcdError> {
+        Ok(UcdFile::new(file, source.read(file)?))
+    }
+
+    /// Return an iterator over only the data records in this file, skipping
+    /// `@missing` directives.
+    pub fn records(self) -> impl Iterator<Item = Result<Record, UcdError>> {
+        self.filter_map(|entry| match entry {
+            Ok(Entry::Record(record)) => Some(Ok(record)),
+            Ok(Entry::Missing(_)) => None,
+            Err(error) => Some(Err(error)),
+        })
+    }
+}
+
+impl Iterator for UcdFile {
+    type Item = Result<Entry, UcdError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let DataLine {
+            line,
+            mut fields,
+            comment,
+            missing,
+        } = self.lines.next()?;
+
+        let code_points = fields.remove(0);
+        let range = match CodePointRange::parse(code_points) {
+            Some(range) => range,
+            None => {
+                return Some(Err(UcdError::parse(
+                    self.file,
+                    line,
+                    code_points,
+                    "a hexadecimal code point or code point range",
+                )))
+            }
+        };
+
+        let record = Record {
+            file: self.file,
+            line,
+            range,
+            fields,
+            comment,
+        };
+        Some(Ok(if missing {
+            Entry::Missing(record)
+        } else {
+            Entry::Record(record)
+        }))
+    }
+}
+
+#[test]
+fn check_ucd_file_tokenizing() {
+    let contents = "# Example-1.0.0.txt\n\
+                    \n\
+                    # @missing: 0000..10FFFF; Unknown\n\
+                    0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..\n\
+                    00DF; 00DF; 0053 0073; 0053 0053; # LATIN SMALL LETTER SHARP S\n\
+                    03A3; 03C2; 03A3; 03A3; Final_Sigma; # GREEK CAPITAL LETTER SIGMA\n\
+                    0370..036F ; Bogus\n";
+
+    let entries = UcdFile::new("Example.txt", contents).collect::<Vec<_>>();
+    assert_eq!(entries.len(), 5);
+
+    match &entries[0] {
+        Ok(Entry::Missing(Record {
+            line,
+            range,
+            fields,
+            ..
+        })) => {
+            assert_eq!(*line, 3);
+            assert_eq!(
+                *range,
+                CodePointRange {
+                    first: 0,
+                    last: 0x10FFFF
+                }
+            );
+            assert_eq!(*fields, ["Unknown"]);
+        }
+        entry => panic!("unexpected {:?}", entry),
+    }
+
+    match &entries[1] {
+        Ok(Entry::Record(record)) => {
+            assert_eq!(record.range.iter().count(), 26);
+            assert!(record.range.contains('Q' as u32));
+            assert_eq!(record.fields, ["Latin"]);
+            assert_eq!(record.comment, Some("L&  [26] LATIN CAPITAL LETTER A.."));
+        }
+        entry => panic!("unexpected {:?}", entry),
+    }
+
+    match &entries[2] {
+        Ok(Entry::Record(record)) => {
+            assert_eq!(record.fields, ["00DF", "0053 0073", "0053 0053"]);
+        }
+        entry => panic!("unexpected {:?}", entry),
+    }
+
+    match &entries[3] {
+        Ok(Entry::Record(record)) => {
+            assert_eq!(record.fields.len(), 4);
+            assert_eq!(
+                record.field(3, "a condition").expect("field"),
+                "Final_Sigma"
+            );
+            assert!(record.field(4, "nothing").is_err());
+        }
+        entry => panic!("unexpected {:?}", entry),
+    }
+
+    match &entries[4] {
+        Err(error) => assert_eq!(
+            error.to_string(),
+            "Example.txt:7: expected a hexadecimal code point or code point range, \
+             found \"0370..036F\""
+        ),
+        entry => panic!("unexpected {:?}", entry),
+    }
+
+    assert_eq!(
+        UcdFile::new("Example.txt", contents).records().count(),
+        4,
+        "records() skips @missing lines"
+    );
+}
+
+#[test]
+fn check_data_lines() {
+    let lines = DataLines::new(
+        "# emoji-zwj-sequences.txt\n\
+         1F468 200D 2764 FE0F 200D 1F468 ; RGI_Emoji_ZWJ_Sequence ; couple with heart: man, man # E2.0\n",
+    )
+    .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [DataLine {
+            line: 2,
+            fields: vec![
+                "1F468 200D 2764 FE0F 200D 1F468",
+                "RGI_Emoji_ZWJ_Sequence",
+                "couple with heart: man, man"
+            ],
+            comment: Some("E2.0"),
+            missing: false,
+        }]
+    );
+}
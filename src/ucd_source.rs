@@ -0,0 +1,216 @@
+//! Where the Unicode Character Database files processed by this crate come
+//! from: either the copies embedded in this crate, or an unpacked UCD directory
+//! on disk.
+
+use crate::ucd_error::UcdError;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+
+static UNICODE_DATA_TXT: &str = include_str!("data/UnicodeData.txt");
+static CASE_FOLDING_TXT: &str = include_str!("data/CaseFolding.txt");
+static DERIVED_CORE_PROPERTIES_TXT: &str = include_str!("data/DerivedCoreProperties.txt");
+static NAME_ALIASES_TXT: &str = include_str!("data/NameAliases.txt");
+static PROP_LIST_TXT: &str = include_str!("data/PropList.txt");
+static SPECIAL_CASING_TXT: &str = include_str!("data/SpecialCasing.txt");
+
+/// A source of Unicode Character Database files.
+///
+/// Every `generate_*` and `process_*` function that reads a UCD file accepts a
+/// `UcdSource`, so that the same processing can be applied to the data embedded
+/// in this crate or to any other Unicode release (e.g. a beta) unpacked on disk.
+///
+/// # Example
+///
+/// ```
+/// # use unicode_info::code_point_table::generate_code_point_table;
+/// # use unicode_info::ucd_source::UcdSource;
+/// let table = generate_code_point_table(&UcdSource::Embedded).expect("embedded data");
+/// assert_eq!(table.name('A' as u32), "LATIN CAPITAL LETTER A");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum UcdSource {
+    /// The UCD files embedded in this crate at compile time (see `src/data`).
+    #[default]
+    Embedded,
+
+    /// A directory containing the UCD files, laid out as in the `ucd`
+    /// directory of a Unicode release (e.g. the contents of `UCD.zip`).
+    Directory(PathBuf),
+}
+
+impl UcdSource {
+    /// Return the contents of the UCD file named `file_name`, e.g.
+    /// `"UnicodeData.txt"`.
+    ///
+    /// Parsed data borrows from file contents for the life of the program, so
+    /// files read from a directory are intentionally leaked.  This is fine for
+    /// the run-once-and-exit generators this crate supports, but it means a
+    /// long-running program shouldn't repeatedly read from a directory source.
+    pub fn read(&self, file_name: &str) -> Result<&'static str, UcdError> {
+        match self {
+            UcdSource::Embedded => match file_name {
+                "UnicodeData.txt" => Ok(UNICODE_DATA_TXT),
+                "CaseFolding.txt" => Ok(CASE_FOLDING_TXT),
+                "DerivedCoreProperties.txt" => Ok(DERIVED_CORE_PROPERTIES_TXT),
+                "NameAliases.txt" => Ok(NAME_ALIASES_TXT),
+                "PropList.txt" => Ok(PROP_LIST_TXT),
+                "SpecialCasing.txt" => Ok(SPECIAL_CASING_TXT),
+                _ => Err(UcdError::io(
+                    file_name,
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "{file_name} isn't embedded in this crate; use \
+                         UcdSource::Directory to read it",
+                            file_name = file_name
+                        ),
+                    ),
+                )),
+            },
+            UcdSource::Directory(dir) => {
+                let contents = std::fs::read_to_string(dir.join(file_name))
+                    .map_err(|error| UcdError::io(file_name, error))?;
+                Ok(Box::leak(contents.into_boxed_str()))
+            }
+        }
+    }
+
+    /// Return the first `count` lines of the UCD file named `file_name`.
+    ///
+    /// Unlike [`read`](UcdSource::read), this reads only as much of a file in
+    /// a directory as it needs, and doesn't leak it.
+    pub fn read_head(&self, file_name: &str, count: usize) -> Result<String, UcdError> {
+        let lines = match self {
+            UcdSource::Embedded => self
+                .read(file_name)?
+                .lines()
+                .take(count)
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+            UcdSource::Directory(dir) => {
+                let file = std::fs::File::open(dir.join(file_name))
+                    .map_err(|error| UcdError::io(file_name, error))?;
+                io::BufReader::new(file)
+                    .lines()
+                    .take(count)
+                    .collect::<io::Result<Vec<_>>>()
+                    .map_err(|error| UcdError::io(file_name, error))?
+            }
+        };
+        Ok(lines.join("\n"))
+    }
+
+    /// Return the contents of the UCD file named `file_name`, or `None` if
+    /// there's no such file.
+    pub fn read_optional(&self, file_name: &str) -> Result<Option<&'static str>, UcdError> {
+        match self.read(file_name) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.is_not_found() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// A uniquely named temporary directory of UCD files for tests, removed when
+/// it's dropped.
+#[cfg(test)]
+pub(crate) struct TestDirectory {
+    path: PathBuf,
+}
+
+#[cfg(test)]
+impl TestDirectory {
+    /// Create an empty directory whose name includes `name`, the name of the
+    /// test using it.
+    pub(crate) fn new(name: &str) -> TestDirectory {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "unicode_info_{}_{}_{}",
+            name,
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        // Discard anything left by an earlier process with the same id.
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).expect("create test directory");
+        TestDirectory { path }
+    }
+
+    /// Write `contents` to the file named `file_name`, e.g. `"PropList.txt"` or
+    /// `"auxiliary/LineBreakTest.txt"`.
+    pub(crate) fn write(&self, file_name: &str, contents: &str) -> &TestDirectory {
+        let path = self.path.join(file_name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create test subdirectory");
+        }
+        std::fs::write(&path, contents)
+            .unwrap_or_else(|error| panic!("write {}: {}", file_name, error));
+        self
+    }
+
+    /// A source reading the files in this directory.
+    pub(crate) fn source(&self) -> UcdSource {
+        UcdSource::Directory(self.path.clone())
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDirectory {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+#[test]
+fn read_embedded_and_directory() {
+    let embedded = UcdSource::Embedded;
+    assert!(embedded
+        .read("UnicodeData.txt")
+        .expect("embedded")
+        .starts_with("0000;<control>;Cc;"));
+    assert!(embedded
+        .read("Scripts.txt")
+        .expect_err("not embedded")
+        .is_not_found());
+
+    let dir = UcdSource::Directory(PathBuf::from(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/data"
+    )));
+    assert_eq!(
+        dir.read("SpecialCasing.txt").expect("directory"),
+        embedded.read("SpecialCasing.txt").expect("embedded")
+    );
+    assert_eq!(
+        dir.read_head("SpecialCasing.txt", 1).expect("directory"),
+        "# SpecialCasing-13.0.0.txt"
+    );
+    assert_eq!(
+        embedded
+            .read_head("SpecialCasing.txt", 2)
+            .expect("embedded"),
+        dir.read_head("SpecialCasing.txt", 2).expect("directory")
+    );
+    assert!(dir
+        .read_head("Scripts.txt", 1)
+        .expect_err("not present")
+        .is_not_found());
+}
+
+#[test]
+fn check_test_directory() {
+    let dir = TestDirectory::new("check_test_directory");
+    dir.write("auxiliary/Example.txt", "# Example-1.0.0.txt\n");
+    let path = match dir.source() {
+        UcdSource::Directory(path) => path,
+        UcdSource::Embedded => unreachable!(),
+    };
+    assert_eq!(
+        dir.source().read("auxiliary/Example.txt").expect("written"),
+        "# Example-1.0.0.txt\n"
+    );
+    drop(dir);
+    assert!(!path.exists(), "removed when dropped");
+}
@@ -0,0 +1,286 @@
+//! Detection of the Unicode version of a set of UCD files, and verification
+//! that every file is from the same version.
+
+use crate::ucd_error::UcdError;
+use crate::ucd_source::UcdSource;
+use std::fmt;
+
+/// A Unicode version, e.g. 13.0.0.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnicodeVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub update: u32,
+}
+
+impl UnicodeVersion {
+    /// Parse a version written as `major.minor.update` or `major.minor` (in
+    /// which case the update version is `0`).
+    pub fn parse(s: &str) -> Option<UnicodeVersion> {
+        let mut parts = s.split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let update = match parts.next() {
+            Some(update) => update?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(UnicodeVersion {
+            major,
+            minor,
+            update,
+        })
+    }
+}
+
+impl fmt::Display for UnicodeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.update)
+    }
+}
+
+/// Files whose first line is a header of the form `# <Name>-<version>.txt`,
+/// e.g. `# CaseFolding-13.0.0.txt`, and whether each file must be present.
+const HEADER_FILES: [(&str, bool); 25] = [
+    ("BidiBrackets.txt", false),
+    ("BidiMirroring.txt", false),
+    ("Blocks.txt", false),
+    ("CaseFolding.txt", true),
+    ("CompositionExclusions.txt", false),
+    ("DerivedAge.txt", false),
+    ("DerivedCoreProperties.txt", true),
+    ("DerivedNormalizationProps.txt", false),
+    ("EastAsianWidth.txt", false),
+    ("LineBreak.txt", false),
+    ("NameAliases.txt", false),
+    ("NamedSequences.txt", false),
+    ("NormalizationTest.txt", false),
+    ("PropList.txt", false),
+    ("PropertyAliases.txt", false),
+    ("PropertyValueAliases.txt", false),
+    ("ScriptExtensions.txt", false),
+    ("Scripts.txt", false),
+    ("SpecialCasing.txt", true),
+    ("auxiliary/GraphemeBreakProperty.txt", false),
+    ("auxiliary/GraphemeBreakTest.txt", false),
+    ("auxiliary/LineBreakTest.txt", false),
+    ("auxiliary/SentenceBreakProperty.txt", false),
+    ("auxiliary/WordBreakProperty.txt", false),
+    ("extracted/DerivedBidiClass.txt", false),
+];
+
+/// Return the version in the `# <Name>-<version>.txt` header of `contents`,
+/// the contents of the file named `file`.
+pub fn header_version(file: &str, contents: &str) -> Result<UnicodeVersion, UcdError> {
+    let first_line = contents.lines().next().unwrap_or("");
+    let expected = || {
+        format!(
+            "a \"# {}-<version>.txt\" header",
+            file.trim_end_matches(".txt")
+        )
+    };
+
+    let header = first_line
+        .strip_prefix("# ")
+        .and_then(|header| header.strip_suffix(".txt"))
+        .ok_or_else(|| UcdError::parse(file, 1, first_line, expected()))?;
+    let version = match header.rfind('-') {
+        Some(i) => UnicodeVersion::parse(&header[i + 1..]),
+        None => None,
+    };
+    version.ok_or_else(|| UcdError::parse(file, 1, first_line, expected()))
+}
+
+/// Files that record their version in running text rather than a header, and
+/// whether the recorded version includes an update number.
+///
+/// `ReadMe.txt` contains a sentence like "This directory contains the final
+/// data files for the Unicode Character Database, for Version 13.0.0 of the
+/// Unicode Standard."  Emoji files contain a line like "# Version: 13.0" or
+/// "# Used with Emoji Version 15.1 and subsequent minor revisions (if any)",
+/// and the same emoji data is used by every update of a Unicode version.
+const TEXT_FILES: [(&str, bool); 4] = [
+    ("ReadMe.txt", true),
+    ("emoji/emoji-data.txt", false),
+    ("emoji/emoji-sequences.txt", false),
+    ("emoji/emoji-zwj-sequences.txt", false),
+];
+
+/// Return the version following the first occurrence of "Version" in
+/// `contents`, the contents of the file named `file`.
+fn text_version(file: &str, contents: &str) -> Result<UnicodeVersion, UcdError> {
+    for (index, line) in contents.lines().enumerate() {
+        if let Some(i) = line.find("Version") {
+            let version = line[i + "Version".len()..]
+                .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+                .split(|c: char| c != '.' && !c.is_ascii_digit())
+                .next()
+                .expect("splitting returns at least one string");
+            return UnicodeVersion::parse(version)
+                .ok_or_else(|| UcdError::parse(file, index + 1, line, "Version <version>"));
+        }
+    }
+    Err(UcdError::parse(
+        file,
+        1,
+        contents.lines().next().unwrap_or(""),
+        "a line containing \"Version <version>\"",
+    ))
+}
+
+/// The file whose version every other file is checked against: the first
+/// file in `HEADER_FILES` that must be present.
+const ANCHOR_FILE: &str = "CaseFolding.txt";
+
+/// The number of lines at the start of each of `TEXT_FILES` that are searched
+/// for its version.
+const TEXT_VERSION_LINES: usize = 20;
+
+/// Determine the Unicode version of the UCD files in `source`, from every file
+/// that records its version, verifying that all files are from the same
+/// version as `CaseFolding.txt`.
+///
+/// Only the first few lines of each file are read.
+///
+/// `UnicodeData.txt` contains no version, so a `UnicodeData.txt` from a
+/// different version can't be detected -- unless `ReadMe.txt` is present, this
+/// check is only as good as the files that record their versions.
+pub fn check_unicode_version(source: &UcdSource) -> Result<UnicodeVersion, UcdError> {
+    // Return the first `count` lines of `file`, or `None` if it's optional and
+    // absent.
+    let head = |file: &str, count: usize, required: bool| match source.read_head(file, count) {
+        Ok(head) => Ok(Some(head)),
+        Err(error) if !required && error.is_not_found() => Ok(None),
+        Err(error) => Err(error),
+    };
+
+    let version = header_version(
+        ANCHOR_FILE,
+        &head(ANCHOR_FILE, 1, true)?.expect("required files are present"),
+    )?;
+
+    let mismatch = |file: &str, file_version: UnicodeVersion| {
+        UcdError::parse(
+            file,
+            1,
+            &file_version.to_string(),
+            format!("version {} (the version of {})", version, ANCHOR_FILE),
+        )
+    };
+
+    for (file, required) in HEADER_FILES.iter() {
+        if *file == ANCHOR_FILE {
+            continue;
+        }
+        if let Some(contents) = head(file, 1, *required)? {
+            let file_version = header_version(file, &contents)?;
+            if file_version != version {
+                return Err(mismatch(file, file_version));
+            }
+        }
+    }
+
+    for (file, has_update) in TEXT_FILES.iter() {
+        if let Some(contents) = head(file, TEXT_VERSION_LINES, false)? {
+            let file_version = text_version(file, &contents)?;
+            let matches = if *has_update {
+                file_version == version
+            } else {
+                (file_version.major, file_version.minor) == (version.major, version.minor)
+            };
+            if !matches {
+                return Err(mismatch(file, file_version));
+            }
+        }
+    }
+
+    Ok(version)
+}
+
+#[test]
+fn check_embedded_version() {
+    assert_eq!(
+        check_unicode_version(&UcdSource::Embedded).expect("consistent versions"),
+        UnicodeVersion {
+            major: 13,
+            minor: 0,
+            update: 0
+        }
+    );
+}
+
+#[test]
+fn check_version_parsing() {
+    assert_eq!(
+        header_version("CaseFolding.txt", "# CaseFolding-15.1.0.txt\n# Date: ...")
+            .expect("header")
+            .to_string(),
+        "15.1.0"
+    );
+    assert!(header_version("CaseFolding.txt", "# CaseFolding.txt\n").is_err());
+
+    assert_eq!(
+        text_version(
+            "ReadMe.txt",
+            "# Unicode Character Database\n\
+             # This directory contains the final data files\n\
+             # for the Unicode Character Database, for Version 13.0.0 of the Unicode Standard.\n"
+        )
+        .expect("version"),
+        UnicodeVersion::parse("13.0").expect("version")
+    );
+
+    for emoji_data in &[
+        "# emoji-data.txt\n# Emoji Data for UTS #51\n# Version: 13.0\n",
+        "# emoji-data.txt\n# Used with Emoji Version 13.0 and subsequent minor revisions (if any)\n",
+    ] {
+        assert_eq!(
+            text_version("emoji/emoji-data.txt", emoji_data).expect("version"),
+            UnicodeVersion::parse("13.0").expect("version")
+        );
+    }
+}
+
+#[test]
+fn check_mismatched_versions() {
+    let dir = crate::ucd_source::TestDirectory::new("check_mismatched_versions");
+    for (file, _) in HEADER_FILES.iter() {
+        let version = if *file == "SpecialCasing.txt" {
+            "14.0.0"
+        } else {
+            "13.0.0"
+        };
+        let header = format!("# {}-{}.txt\n", file.trim_end_matches(".txt"), version);
+        dir.write(file, &header);
+    }
+
+    let error = check_unicode_version(&dir.source()).expect_err("mismatched versions");
+    assert_eq!(
+        error.to_string(),
+        "SpecialCasing.txt:1: expected version 13.0.0 (the version of CaseFolding.txt), \
+         found \"14.0.0\""
+    );
+
+    // An off-version optional file is blamed, rather than serving as the
+    // version other files are checked against.
+    dir.write("SpecialCasing.txt", "# SpecialCasing-13.0.0.txt\n");
+    dir.write("BidiBrackets.txt", "# BidiBrackets-12.1.0.txt\n");
+    let error = check_unicode_version(&dir.source()).expect_err("mismatched versions");
+    assert_eq!(
+        error.to_string(),
+        "BidiBrackets.txt:1: expected version 13.0.0 (the version of CaseFolding.txt), \
+         found \"12.1.0\""
+    );
+
+    dir.write("BidiBrackets.txt", "# BidiBrackets-13.0.0.txt\n");
+    dir.write(
+        "emoji/emoji-data.txt",
+        "# emoji-data.txt\n# Emoji Data for UTS #51\n# Version: 13.0\n",
+    );
+    assert_eq!(
+        check_unicode_version(&dir.source()).expect("consistent versions"),
+        UnicodeVersion::parse("13.0.0").expect("version")
+    );
+}